@@ -1,9 +1,12 @@
+use quest_core::Binding;
+use quest_core::types::List;
+use quest_parser::Block;
 use std::fmt::{self, Display, Formatter};
 
 #[derive(Debug)]
 pub enum Error {
 	Quest(quest_core::Error),
-	Parser(quest_parser::Error),
+	Parser(Box<quest_parser::Error>),
 	Io(std::io::Error)
 }
 
@@ -21,14 +24,72 @@ impl From<quest_core::Error> for Error {
 
 impl From<quest_parser::Error> for Error {
 	fn from(error: quest_parser::Error) -> Self {
-		Error::Parser(error)
+		Error::Parser(Box::new(error))
+	}
+}
+
+/// Finds the [`Block`] that a stackframe was created for (or that its compiled block came from), if any.
+fn frame_block(frame: &Binding) -> Option<Block> {
+	let parents = frame.get_attr_lit("__parents__").ok()?;
+	let block = parents.downcast::<List>()?
+		.iter()
+		.find_map(|parent| {
+			if let Some(block) = parent.downcast::<Block>() {
+				return Some(Block::clone(&block));
+			}
+
+			let compiled = parent.downcast::<qvm::CompiledBlock>()?;
+			let source = compiled.code().source()?.downcast::<Block>()?;
+			Some(Block::clone(&source))
+		});
+
+	block
+}
+
+/// The [`Block`] frames of an error's backtrace, innermost first.
+///
+/// Deep backtraces (eg from runaway recursion) only show the first and last [`Backtrace::EDGE`]
+/// frames.
+struct Backtrace<'a>(&'a [Binding]);
+
+impl Backtrace<'_> {
+	const EDGE: usize = 10;
+}
+
+impl Display for Backtrace<'_> {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		let blocks = self.0.iter().filter_map(frame_block).collect::<Vec<_>>();
+		let omitted = blocks.len().saturating_sub(2 * Self::EDGE);
+
+		for (idx, block) in blocks.iter().enumerate() {
+			if omitted != 0 && Self::EDGE <= idx && idx < Self::EDGE + omitted {
+				if idx == Self::EDGE {
+					write!(f, "\n... {} frames omitted", omitted)?;
+				}
+
+				continue;
+			}
+
+			writeln!(f)?;
+			block.context().write_snippet(f, "in block")?;
+		}
+
+		Ok(())
 	}
 }
 
 impl Display for Error {
 	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
 		match self {
-			Error::Quest(err) => Display::fmt(&err, f),
+			Error::Quest(err) => {
+				Display::fmt(&err, f)?;
+
+				if let Some(backtrace) = err.backtrace() {
+					Display::fmt(&Backtrace(backtrace), f)?;
+				}
+
+				Ok(())
+			},
 			Error::Parser(err) => Display::fmt(&err, f),
 			Error::Io(err) => Display::fmt(&err, f),
 		}
@@ -44,4 +105,40 @@ impl std::error::Error for Error {
 	}
 }
 
-pub type Result<T> = std::result::Result<T, Error>;
\ No newline at end of file
+pub type Result<T> = std::result::Result<T, Error>;
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use quest_core::Object;
+	use quest_core::types::Scope;
+	use quest_parser::{Expression, Stream, stream::BufStream, expression::Executable};
+
+	fn frames(count: usize) -> Vec<Binding> {
+		let block = Expression::parse_stream(BufStream::from("{ 1 }".to_string()).tokens())
+			.unwrap()
+			.execute()
+			.unwrap();
+
+		(0..count)
+			.map(|_| {
+				let frame = Object::new(Scope);
+				frame.add_parent(block.clone()).unwrap();
+				Binding::from(frame)
+			})
+			.collect()
+	}
+
+	#[test]
+	fn backtrace_truncation() {
+		quest_core::init();
+		quest_parser::init();
+
+		let short = Backtrace(&frames(2 * Backtrace::EDGE)).to_string();
+		assert_eq!(short.matches("in block").count(), 2 * Backtrace::EDGE);
+		assert!(!short.contains("omitted"));
+
+		let long = Backtrace(&frames(2 * Backtrace::EDGE + 5)).to_string();
+		assert_eq!(long.matches("in block").count(), 2 * Backtrace::EDGE);
+		assert_eq!(long.matches("... 5 frames omitted").count(), 1);
+	}
+}
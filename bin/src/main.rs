@@ -17,6 +17,10 @@ struct Opts {
 	#[clap(short, long, conflicts_with="file")]
 	eval: Option<String>,
 
+	/// Run using the bytecode VM instead of the tree-walking interpreter.
+	#[clap(long)]
+	vm: bool,
+
 	#[clap(last=true)]
 	args: Vec<String>
 
@@ -27,7 +31,7 @@ struct Opts {
 }
 
 
-fn run_options(Opts { file, eval, args, .. }: Opts) -> Result<Object> {
+fn run_options(Opts { file, eval, vm, args, .. }: Opts) -> Result<Object> {
 	let mut args: Vec<Object> = args.into_iter().map(Object::from).collect();
 
 	if let Some(file) = file.as_ref() {
@@ -38,9 +42,9 @@ fn run_options(Opts { file, eval, args, .. }: Opts) -> Result<Object> {
 
 	match (file, eval) {
 		(Some(_), Some(_)) => panic!("both options set?"),
-		(Some(file), None) if file.to_str() == Some("-") => run::run_stdin(args),
-		(Some(file), None) => run::run_file(file, args),
-		(None, Some(expr)) => run::run_expression(expr, args),
+		(Some(file), None) if file.to_str() == Some("-") => run::run_stdin(args, vm),
+		(Some(file), None) => run::run_file(file, args, vm),
+		(None, Some(expr)) => run::run_expression(expr, args, vm),
 		(None, None)       => run::run_repl(args)
 	}
 }
@@ -87,6 +91,7 @@ fn main() {
 
 	quest_core::init();
 	quest_parser::init();
+	qvm::init();
 
 	// The following line is used by me internally for benchmarking.
 	// if true {run::run_file("code.ignore/fib.qs", Default::default()).unwrap(); return}
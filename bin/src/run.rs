@@ -1,7 +1,9 @@
 mod repl;
 mod buf_stream;
+mod compiled;
 
 pub use buf_stream::BufStream;
+pub use compiled::Compiled;
 use repl::Repl;
 use quest_core::{Object, Binding, Args};
 use crate::Result;
@@ -12,16 +14,24 @@ pub trait Runner {
 	fn run(self) -> Result<Object>;
 }
 
-pub fn run_file<P: AsRef<Path>>(path: P, args: Args) -> Result<Object> { 
-	run(BufStream::try_from(path.as_ref())?, args).map_err(From::from)
+pub fn run_file<P: AsRef<Path>>(path: P, args: Args, vm: bool) -> Result<Object> { 
+	run_stream(BufStream::try_from(path.as_ref())?, args, vm)
 }
 
-pub fn run_expression(expr: String, args: Args) -> Result<Object> {
-	run(BufStream::from(expr), args).map_err(From::from)
+pub fn run_expression(expr: String, args: Args, vm: bool) -> Result<Object> {
+	run_stream(BufStream::from(expr), args, vm)
 }
 
-pub fn run_stdin(args: Args) -> Result<Object> {
-	run(BufStream::stdin(), args).map_err(From::from)
+pub fn run_stdin(args: Args, vm: bool) -> Result<Object> {
+	run_stream(BufStream::stdin(), args, vm)
+}
+
+fn run_stream<B: std::io::BufRead>(stream: BufStream<B>, args: Args, vm: bool) -> Result<Object> {
+	if vm {
+		run(Compiled(stream), args).map_err(From::from)
+	} else {
+		run(stream, args).map_err(From::from)
+	}
 }
 
 pub fn run_repl(args: Args) -> Result<Object> {
@@ -33,7 +43,10 @@ pub fn run<R: Runner>(runner: R, args: Args) -> quest_core::Result<Object> {
 	main.set_attr_lit("name", Object::from("main"))?;
 
 	Binding::new_stackframe(Some(main), args, move |_| {
-		runner.run().map_err(|err| quest_core::Error::Boxed(Box::new(err)))
+		runner.run().map_err(|err| match err {
+			crate::error::Error::Quest(err) => err,
+			other => quest_core::Error::Boxed(Box::new(other))
+		})
 	})
 }
 
@@ -3,7 +3,11 @@ use std::io::BufRead;
 
 impl<B: BufRead> crate::run::Runner for BufStream<B> {
 	fn run(self) -> crate::Result<quest_core::Object> {
-		use quest_parser::{Stream, Expression, expression::Executable};
+		use quest_parser::{Stream, Contexted, Expression, expression::Executable};
+
+		if let Some(file) = &self.context().file {
+			quest_core::Binding::instance().set_attr_lit("__file__", file.display().to_string().into())?;
+		}
 
 		Expression::parse_stream(self.tokens())?
 			.execute()
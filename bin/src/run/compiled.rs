@@ -0,0 +1,21 @@
+use super::BufStream;
+use std::io::BufRead;
+
+/// Runs a [`BufStream`] by compiling it and running it on the [`qvm`] instead of walking the
+/// parsed expression.
+#[derive(Debug)]
+pub struct Compiled<B: BufRead>(pub BufStream<B>);
+
+impl<B: BufRead> crate::run::Runner for Compiled<B> {
+	fn run(self) -> crate::Result<quest_core::Object> {
+		use quest_parser::{Stream, Contexted, Expression};
+
+		if let Some(file) = &self.0.context().file {
+			quest_core::Binding::instance().set_attr_lit("__file__", file.display().to_string().into())?;
+		}
+
+		let code = qvm::compile(&Expression::parse_stream(self.0.tokens())?);
+
+		qvm::run(&code).map_err(Into::into)
+	}
+}
@@ -1,46 +1,144 @@
-use quest_parser::{Stream, Context, Contexted};
+use quest_parser::{Stream, Token, Expression, ErrorType, expression::Executable};
+use quest_parser::stream::BufStream;
+use quest_core::{Object, types::Text};
 use crate::run::Runner;
-use std::io::{self, Seek, SeekFrom};
+use rustyline::{Editor, error::ReadlineError};
+use std::io::Cursor;
+use std::path::PathBuf;
 
-#[derive(Debug, Clone)]
+/// The prompt shown when starting a new entry.
+const PROMPT: &str = "quest> ";
+
+/// The prompt shown when an entry is being continued onto the next line.
+const CONTINUE_PROMPT: &str = "  ...> ";
+
+/// The file that's used for history if `QUEST_HISTORY` isn't set.
+const HISTORY_FILE: &str = ".quest_history";
+
+/// An interactive Quest session.
+///
+/// Each entry is evaluated within the same stackframe, so variables persist between them.
 pub struct Repl {
-	context: Context
+	editor: Editor<()>,
+	history: Option<PathBuf>
 }
 
-impl Runner for Repl {
-	fn run(self) -> crate::Result<quest_core::Object> {
-		unimplemented!()
-	}
+/// Whether an entry is able to be parsed, or needs more lines first.
+enum Entry {
+	Complete,
+	Incomplete
 }
 
 impl Repl {
 	pub fn new() -> Self {
-		Repl { context: Context::new(Some("<repl>".into())) }
+		let history = std::env::var_os("QUEST_HISTORY")
+			.map(PathBuf::from)
+			.or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(HISTORY_FILE)));
+
+		let mut editor = Editor::<()>::new();
+
+		if let Some(ref history) = history {
+			// it's fine if there's no history yet.
+			let _ = editor.load_history(history);
+		}
+
+		Repl { editor, history }
 	}
-}
-impl Iterator for Repl {
-	type Item = quest_parser::Result<char>;
-	fn next(&mut self) -> Option<Self::Item> {
-		unimplemented!()
+
+	fn stream(entry: &str) -> BufStream<Cursor<&str>> {
+		BufStream::new(Cursor::new(entry), Some("<repl>".into()))
 	}
-}
 
-impl Seek for Repl {
-	fn seek(&mut self, _pos: SeekFrom) -> io::Result<u64> {
-		unimplemented!()
+	/// Checks to see if all the brackets within `entry` are balanced.
+	///
+	/// Unterminated quotes and block comments are also considered incomplete.
+	fn check_entry(entry: &str) -> Entry {
+		let mut depth = 0isize;
+
+		for token in Self::stream(entry).tokens() {
+			match token {
+				Ok(Token::Left(_)) => depth += 1,
+				Ok(Token::Right(_)) => depth -= 1,
+				Ok(_) => {},
+				Err(err) => match err.error_type() {
+					ErrorType::UnterminatedQuote | ErrorType::UnterminatedBlockComment
+						=> return Entry::Incomplete,
+					// let the actual parse report the error.
+					_ => return Entry::Complete
+				}
+			}
+		}
+
+		if depth > 0 {
+			Entry::Incomplete
+		} else {
+			Entry::Complete
+		}
 	}
-}
 
-impl Contexted for Repl {
-	fn context(&self) -> &Context {
-		&self.context
+	/// Reads lines until a complete entry is given, returning `None` at EOF.
+	fn read_entry(&mut self) -> Option<String> {
+		let mut entry = String::new();
+
+		loop {
+			let prompt = if entry.is_empty() { PROMPT } else { CONTINUE_PROMPT };
+
+			match self.editor.readline(prompt) {
+				Ok(line) => {
+					entry.push_str(&line);
+					entry.push('\n');
+				},
+				// `^C` abandons the current entry.
+				Err(ReadlineError::Interrupted) => entry.clear(),
+				Err(ReadlineError::Eof) if !entry.is_empty() => return Some(entry),
+				Err(ReadlineError::Eof) => return None,
+				Err(err) => {
+					eprintln!("unable to read line: {}", err);
+					return None;
+				}
+			}
+
+			if !entry.trim().is_empty() {
+				if let Entry::Complete = Self::check_entry(&entry) {
+					return Some(entry);
+				}
+			}
+		}
+	}
+
+	fn evaluate(entry: &str) -> crate::Result<Object> {
+		Expression::parse_stream(Self::stream(entry).tokens())?
+			.execute()
+			.map_err(From::from)
+	}
+
+	fn inspect(object: &Object) -> crate::Result<String> {
+		Ok(object.call_attr_lit("inspect", &[])?.call_downcast::<Text>()?.to_string())
 	}
 }
 
-impl Stream for Repl {
-	fn starts_with(&mut self, _s: &str) -> quest_parser::Result<bool> {
-		unimplemented!()
-		// self.read_next_line_if_applicable()?;
-		// Ok(self.as_ref().starts_with(s))
+impl Runner for Repl {
+	fn run(mut self) -> crate::Result<Object> {
+		let mut last = Object::default();
+
+		while let Some(entry) = self.read_entry() {
+			self.editor.add_history_entry(entry.trim_end());
+
+			match Self::evaluate(&entry).and_then(|result| Ok((Self::inspect(&result)?, result))) {
+				Ok((inspected, result)) => {
+					println!("{}", inspected);
+					last = result;
+				},
+				Err(err) => eprintln!("{}", err)
+			}
+		}
+
+		if let Some(ref history) = self.history {
+			if let Err(err) = self.editor.save_history(history) {
+				eprintln!("unable to save history: {}", err);
+			}
+		}
+
+		Ok(last)
 	}
 }
@@ -12,8 +12,7 @@ fn should_skip(p: &Path) -> bool {
 	SKIP.iter().any(|to_skip| p.ends_with(*to_skip))
 }
 
-#[test]
-fn run_examples() {
+fn run_examples_with(extra_args: &[&str]) {
 	let exe = dbg!(env!("CARGO_BIN_EXE_quest-bin"));
 	let manifest_dir = dbg!(env!("CARGO_MANIFEST_DIR"));
 	let examples_dir = dbg!(Path::new(manifest_dir).join("../examples"));
@@ -22,7 +21,7 @@ fn run_examples() {
 		let example = example.unwrap();
 		eprintln!("Running example {:?}... ", example);
 		let example_path = example.path();
-		if should_skip(&example_path) {
+		if should_skip(&example_path) || example_path.is_dir() {
 			eprintln!("\tSKIP");
 			continue;
 		}
@@ -30,18 +29,23 @@ fn run_examples() {
 		let mut cmd = Command::new(exe);
 		// Todo: Should run with timeout
 		let out = cmd
+			.args(extra_args)
 			.arg("-f")
 			.arg(&example_path)
 			.output()
 			.unwrap_or_else(|e| {
 				panic!(
-					"Failed to run: `{} -f {}`: {:?}",
+					"Failed to run: `{} {} -f {}`: {:?}",
 					exe,
+					extra_args.join(" "),
 					example_path.display(),
 					e,
 				)
 			});
-		if out.status.success() {
+		// uncaught errors are reported on stderr, but don't change the exit status.
+		let uncaught = String::from_utf8_lossy(&out.stderr).contains("uncaught error");
+
+		if out.status.success() && !uncaught {
 			eprintln!("\tPASS");
 		} else {
 			eprintln!("\tFAIL\nOutput: {:#?}", out);
@@ -53,3 +57,13 @@ fn run_examples() {
 		"One or more tests failed to run, see stderr for details"
 	);
 }
+
+#[test]
+fn run_examples() {
+	run_examples_with(&[]);
+}
+
+#[test]
+fn run_examples_vm() {
+	run_examples_with(&["--vm"]);
+}
@@ -39,6 +39,17 @@ pub enum Error {
 	/// Boxed error
 	Boxed(Box<dyn std::error::Error + Send + Sync + 'static>),
 
+	/// An arbitrary object was thrown via `Kernel::throw`.
+	Thrown(Object),
+
+	/// An error that was raised within a stackframe, along with the stack at the time.
+	Raised {
+		/// The error that was raised.
+		error: Box<Error>,
+		/// The stack when the error was raised, with the innermost frame first.
+		stack: Vec<Binding>
+	},
+
 	/// Returning a value.
 	///
 	/// While this isn't technically an "error" in the strict sense of an error, it's much easier
@@ -68,12 +79,50 @@ impl Display for Error {
 			Self::AssertionFailed(Some(err)) => write!(f, "assertion failed: {}", err),
 			Self::AssertionFailed(None) => write!(f, "assertion failed"),
 			Self::Boxed(err) => Display::fmt(&err, f),
+			Self::Thrown(obj) =>
+				match obj.call_downcast::<crate::types::Text>() {
+					Ok(text) => write!(f, "uncaught exception: {}", text.as_ref()),
+					Err(_) => write!(f, "uncaught exception: {:?}", obj)
+				},
+			Self::Raised { error, .. } => Display::fmt(&error, f),
 			Self::Return { to, obj } => write!(f, "uncaught return to {:?}: {:?}", to, obj)
 		}
 	}
 }
 
 
+impl Error {
+	/// Annotate `self` with the current stack, if it hasn't been already.
+	///
+	/// This should be called in the innermost stackframe that the error passes through, so that
+	/// the stack reflects where the error was raised. [`Return`](Error::Return)s are left untouched,
+	/// as they aren't really errors.
+	pub fn raised(self) -> Self {
+		match self {
+			err @ Self::Return { .. } | err @ Self::Raised { .. } => err,
+			err => Self::Raised { error: Box::new(err), stack: Binding::stack() }
+		}
+	}
+
+	/// Gets the stack at the time `self` was raised, with the innermost frame first.
+	pub fn backtrace(&self) -> Option<&[Binding]> {
+		match self {
+			Self::Raised { stack, .. } => Some(stack),
+			Self::Boxed(err) => err.downcast_ref::<Self>().and_then(Self::backtrace),
+			_ => None
+		}
+	}
+
+	/// Gets the object that was [thrown](Error::Thrown), looking through [`Raised`](Error::Raised).
+	pub fn thrown(&self) -> Option<&Object> {
+		match self {
+			Self::Thrown(obj) => Some(obj),
+			Self::Raised { error, .. } => error.thrown(),
+			_ => None
+		}
+	}
+}
+
 impl From<std::io::Error> for Error {
 	#[inline]
 	fn from(err: std::io::Error) -> Self {
@@ -82,10 +131,10 @@ impl From<std::io::Error> for Error {
 }
 impl std::error::Error for Error {
 	fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
-		if let Self::Boxed(err) = self {
-			Some(err.as_ref())
-		} else {
-			None
+		match self {
+			Self::Boxed(err) => Some(err.as_ref()),
+			Self::Raised { error, .. } => Some(error.as_ref()),
+			_ => None
 		}
 	}
 }
@@ -41,11 +41,17 @@ pub fn init() {
 
 	static INITIALIZE: Once = Once::new();
 
-	INITIALIZE.call_once(||
+	INITIALIZE.call_once(|| {
 		initialize!(
 			Pristine, Basic, Boolean, BoundFunction, Comparable, Function, Kernel,
-			List, Null, Number, Regex, RustFn, RustClosure, Scope, Text, Iterable, Tcp,
-			BoundRustFn, Io, types::io::File // todo: remove it?
-		)
-	)
+			List, Map, Set, Null, Number, Complex, Regex, Path, RustFn, RustClosure, Scope, Text,
+			Bytes, Iterable, Tcp, TcpServer, Udp, Duration, Date, Time, Json, Http, HttpServer, Process,
+			Mutex, Condvar, Channel, Atomic, Thread, ThreadPool, types::thread::Future, Pattern,
+			BoundRustFn, Io, Exception, types::http::Response, types::http_server::Request,
+			types::process::Status, types::process::Output, types::io::Dir, types::io::dir::Metadata, types::io::File // todo: remove it?
+		);
+
+		#[cfg(unix)]
+		initialize!(UnixSocket, UnixListener);
+	})
 }
@@ -931,13 +931,13 @@ mod tests {
 		#[test]
 		fn hash() {
 			assert_eq!(
-				call_unwrap!(Boolean::qs_hash(true) -> Number; |n| *n),
-				call_unwrap!(Boolean::qs_hash(true) -> Number; |n| *n)
+				call_unwrap!(Boolean::qs_hash(true) -> Number; |n| n.clone()),
+				call_unwrap!(Boolean::qs_hash(true) -> Number; |n| n.clone())
 			);
 
 			assert_eq!(
-				call_unwrap!(Boolean::qs_hash(false) -> Number; |n| *n),
-				call_unwrap!(Boolean::qs_hash(false) -> Number; |n| *n)
+				call_unwrap!(Boolean::qs_hash(false) -> Number; |n| n.clone()),
+				call_unwrap!(Boolean::qs_hash(false) -> Number; |n| n.clone())
 			);
 		}
 	}
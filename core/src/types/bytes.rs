@@ -0,0 +1,503 @@
+use crate::{Object, Args};
+use crate::error::{ValueError, TypeError};
+use crate::utils::{correct_index, IndexError};
+use crate::types::{Convertible, Text, Boolean, Number, List, Iter};
+use std::convert::{TryFrom, TryInto};
+use std::fmt::{self, Debug, Display, Formatter};
+use tracing::instrument;
+
+/// A sequence of raw bytes.
+///
+/// Unlike [`Text`], bytes don't have to be valid UTF-8, which makes them suitable for binary files
+/// and network protocols.
+#[derive(Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Bytes(Vec<u8>);
+
+impl Debug for Bytes {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		if f.alternate() {
+			f.debug_tuple("Bytes").field(&self.0).finish()
+		} else {
+			Display::fmt(self, f)
+		}
+	}
+}
+
+impl Display for Bytes {
+	/// Writes the bytes in the form `b"..."`, escaping any non-printable characters.
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		f.write_str("b\"")?;
+
+		for &byte in &self.0 {
+			for chr in std::ascii::escape_default(byte) {
+				write!(f, "{}", chr as char)?;
+			}
+		}
+
+		f.write_str("\"")
+	}
+}
+
+impl From<Vec<u8>> for Bytes {
+	#[inline]
+	fn from(bytes: Vec<u8>) -> Self {
+		Self(bytes)
+	}
+}
+
+impl From<&[u8]> for Bytes {
+	#[inline]
+	fn from(bytes: &[u8]) -> Self {
+		Self(bytes.to_vec())
+	}
+}
+
+impl From<Bytes> for Vec<u8> {
+	#[inline]
+	fn from(bytes: Bytes) -> Self {
+		bytes.0
+	}
+}
+
+impl AsRef<[u8]> for Bytes {
+	#[inline]
+	fn as_ref(&self) -> &[u8] {
+		&self.0
+	}
+}
+
+impl From<&Text> for Bytes {
+	#[inline]
+	fn from(text: &Text) -> Self {
+		Self(text.as_ref().as_bytes().to_vec())
+	}
+}
+
+impl TryFrom<&Bytes> for Text {
+	type Error = ValueError;
+
+	/// Decodes the bytes as UTF-8, returning an error describing where the invalid data starts.
+	fn try_from(bytes: &Bytes) -> Result<Self, Self::Error> {
+		match std::str::from_utf8(&bytes.0) {
+			Ok(text) => Ok(text.to_string().into()),
+			Err(err) => Err(ValueError::Messaged(
+				format!("invalid utf-8 at byte {}: {}", err.valid_up_to(), err)))
+		}
+	}
+}
+
+impl std::ops::Add<&Bytes> for &Bytes {
+	type Output = Bytes;
+
+	fn add(self, rhs: &Bytes) -> Bytes {
+		let mut bytes = self.clone();
+		bytes += rhs;
+		bytes
+	}
+}
+
+impl std::ops::AddAssign<&Bytes> for Bytes {
+	#[inline]
+	fn add_assign(&mut self, rhs: &Bytes) {
+		self.0.extend_from_slice(&rhs.0);
+	}
+}
+
+/// Rust-centric bytes methods
+impl Bytes {
+	/// Create new bytes.
+	#[inline]
+	pub fn new(bytes: impl Into<Vec<u8>>) -> Self {
+		Self(bytes.into())
+	}
+
+	/// Get the amount of bytes.
+	#[inline]
+	pub fn len(&self) -> usize {
+		self.0.len()
+	}
+
+	/// Checks to see if there are no bytes.
+	#[inline]
+	pub fn is_empty(&self) -> bool {
+		self.0.is_empty()
+	}
+
+	/// Returns the internal vector.
+	#[inline]
+	pub fn into_inner(self) -> Vec<u8> {
+		self.0
+	}
+
+	/// Get the byte at `index`, which may be negative to index from the end.
+	pub fn get(&self, index: isize) -> Option<u8> {
+		correct_index(index, self.len()).ok().map(|index| self.0[index])
+	}
+
+	/// Get the bytes from `start` to `stop`, inclusive.
+	pub fn get_rng(&self, start: isize, stop: isize) -> Option<&[u8]> {
+		let start = correct_index(start, self.len()).ok()?;
+		let stop =
+			match correct_index(stop, self.len()) {
+				Ok(stop) => stop + 1,
+				Err(IndexError::TooPositive) => self.len(),
+				Err(IndexError::TooNegative) => return None
+			};
+
+		if stop < start {
+			None
+		} else {
+			Some(&self.0[start..stop])
+		}
+	}
+
+	/// Encode the bytes as lowercase hexadecimal.
+	pub fn to_hex(&self) -> String {
+		self.0.iter().map(|byte| format!("{:02x}", byte)).collect()
+	}
+
+	/// Decode bytes from hexadecimal, ignoring case.
+	pub fn from_hex(hex: &str) -> Result<Self, ValueError> {
+		if !hex.len().is_multiple_of(2) {
+			return Err(ValueError::Messaged(format!("hex has an odd length: {}", hex.len())));
+		}
+
+		(0..hex.len()).step_by(2)
+			.map(|idx| hex.get(idx..idx + 2)
+				.and_then(|digits| u8::from_str_radix(digits, 16).ok())
+				.ok_or_else(|| ValueError::Messaged(format!("invalid hex digits at index {}", idx))))
+			.collect::<Result<Vec<_>, _>>()
+			.map(Self)
+	}
+}
+
+/// Converts `obj` to a byte, raising a [`ValueError`] if it isn't within `0..=255`.
+fn to_byte(obj: &Object) -> crate::Result<u8> {
+	let num = obj.call_downcast::<Number>()?.clone();
+
+	// go through a big integer, as converting straight to a `u8` would truncate it.
+	num.try_to_big().ok()
+		.and_then(|big| u8::try_from(&big).ok())
+		.ok_or_else(|| ValueError::Messaged(format!("byte out of range: {}", num)).into())
+}
+
+/// Quest methods
+impl Bytes {
+	/// Create new bytes.
+	///
+	/// [`Text`]s are encoded as UTF-8, and any other iterable must contain numbers within
+	/// `0..=255`.
+	///
+	/// # Arguments
+	/// 1. (optional, `Text` or `@iter`) The initial bytes; defaults to empty.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// assert(Bytes("hi") == Bytes([104, 105]));
+	/// ```
+	#[instrument(name="Bytes::()", level="trace", skip(args), fields(?args))]
+	pub fn qs_call(_: &Object, args: Args) -> crate::Result<Object> {
+		let arg =
+			if let Some(arg) = args.arg(0) {
+				arg
+			} else {
+				return Ok(Self::default().into());
+			};
+
+		if let Some(bytes) = arg.downcast::<Self>() {
+			return Ok(bytes.clone().into());
+		} else if let Some(text) = arg.downcast::<Text>() {
+			return Ok(Self::from(&*text).into());
+		}
+
+		let mut bytes = Vec::new();
+
+		for obj in Iter::from_object(arg)? {
+			bytes.push(to_byte(&obj?)?);
+		}
+
+		Ok(Self::from(bytes).into())
+	}
+
+	/// Decode bytes from the hexadecimal given as the first argument.
+	///
+	/// # Arguments
+	/// 1. (required, `@text`) The hexadecimal to decode.
+	#[instrument(name="Bytes::from_hex", level="trace", skip(args), fields(?args))]
+	pub fn qs_from_hex(_: &Object, args: Args) -> crate::Result<Object> {
+		let hex = args.try_arg(0)?.call_downcast::<Text>()?;
+
+		Ok(Self::from_hex(hex.as_ref())?.into())
+	}
+
+	/// Simply returns `this`.
+	#[instrument(name="Bytes::@bytes", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_at_bytes(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.clone())
+	}
+
+	/// Decodes `this` as UTF-8.
+	///
+	/// If `this` isn't valid UTF-8, a [`ValueError`] is raised with the position of the first
+	/// invalid byte.
+	#[instrument(name="Bytes::@text", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_at_text(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(Text::try_from(&*this.try_downcast::<Self>()?)?.into())
+	}
+
+	/// Inspects `this`, returning text of the form `b"..."`.
+	#[instrument(name="Bytes::inspect", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_inspect(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.to_string().into())
+	}
+
+	/// Converts `this` into a [`Boolean`]; only empty bytes are false.
+	#[instrument(name="Bytes::@bool", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_at_bool(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(Boolean::from(!this.try_downcast::<Self>()?.is_empty()).into())
+	}
+
+	/// Converts `this` into a [`List`] of numbers.
+	#[instrument(name="Bytes::@list", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_at_list(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.0.iter().map(|&byte| Object::from(byte)).collect::<List>().into())
+	}
+
+	/// Get an [`Iter`] over the bytes of `this`, as numbers.
+	#[instrument(name="Bytes::@iter", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_at_iter(this: &Object, _: Args) -> crate::Result<Object> {
+		let bytes = this.try_downcast::<Self>()?.clone();
+
+		Ok(Iter::with_objects(bytes.0.into_iter().map(Object::from)).into())
+	}
+
+	/// Get the amount of bytes in `this`.
+	#[instrument(name="Bytes::len", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_len(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.len().into())
+	}
+
+	/// Get either a single byte as a [`Number`], or a range of bytes.
+	///
+	/// # Arguments
+	/// 1. (required, `@num`) The index of the byte, or the start of the range.
+	/// 2. (optional, `@num`) The end of the range, inclusive.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// bytes = Bytes("abc");
+	///
+	/// assert((bytes[0]) == 97);
+	/// assert((bytes[-1]) == 99);
+	/// assert(bytes.get(1, 2) == Bytes("bc"));
+	/// ```
+	#[instrument(name="Bytes::get", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_get(this: &Object, args: Args) -> crate::Result<Object> {
+		let start: isize = args.try_arg(0)?.call_downcast::<Number>()?.clone().try_into()?;
+		let stop =
+			args.arg(1)
+				.map(|n| n.call_downcast::<Number>().map(|n| n.clone()))
+				.transpose()?
+				.map(isize::try_from)
+				.transpose()?;
+
+		let this = this.try_downcast::<Self>()?;
+
+		if let Some(stop) = stop {
+			Ok(this.get_rng(start, stop).map(|bytes| Self::from(bytes).into()).unwrap_or_default())
+		} else {
+			Ok(this.get(start).map(Object::from).unwrap_or_default())
+		}
+	}
+
+	/// Concatenates `this` and the first argument.
+	///
+	/// # Arguments
+	/// 1. (required, `@bytes`) The bytes to append.
+	#[instrument(name="Bytes::+", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_add(this: &Object, args: Args) -> crate::Result<Object> {
+		let rhs = args.try_arg(0)?.call_downcast::<Self>()?.clone();
+		let this = this.try_downcast::<Self>()?;
+
+		Ok((&*this + &rhs).into())
+	}
+
+	/// Appends the first argument to `this`, in place.
+	///
+	/// # Arguments
+	/// 1. (required, `@bytes`) The bytes to append.
+	#[instrument(name="Bytes::+=", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_add_assign(this: &Object, args: Args) -> crate::Result<Object> {
+		let rhs = args.try_arg(0)?.call_downcast::<Self>()?.clone();
+
+		*this.try_downcast_mut::<Self>()? += &rhs;
+
+		Ok(this.clone())
+	}
+
+	/// Appends a single byte to the end of `this`.
+	///
+	/// # Arguments
+	/// 1. (required, `@num`) The byte, which must be within `0..=255`.
+	#[instrument(name="Bytes::push", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_push(this: &Object, args: Args) -> crate::Result<Object> {
+		let byte = to_byte(args.try_arg(0)?)?;
+
+		this.try_downcast_mut::<Self>()?.0.push(byte);
+
+		Ok(this.clone())
+	}
+
+	/// Encodes `this` as lowercase hexadecimal [`Text`].
+	#[instrument(name="Bytes::hex", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_hex(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.to_hex().into())
+	}
+
+	/// Checks to see if `this` is equal to the first argument.
+	///
+	/// # Arguments
+	/// 1. (required) The other object to compare against.
+	#[instrument(name="Bytes::==", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_eql(this: &Object, args: Args) -> crate::Result<Object> {
+		let rhs = args.try_arg(0)?.downcast::<Self>();
+		let this = this.try_downcast::<Self>()?;
+
+		Ok(rhs.map(|rhs| *this == *rhs).unwrap_or(false).into())
+	}
+
+	/// Compares `this` to the first argument, byte-by-byte.
+	///
+	/// # Arguments
+	/// 1. (required, `@bytes`) The other bytes.
+	#[instrument(name="Bytes::<=>", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_cmp(this: &Object, args: Args) -> crate::Result<Object> {
+		let rhs = args.try_arg(0)?.call_downcast::<Self>()?.clone();
+		let this = this.try_downcast::<Self>()?;
+
+		Ok(this.cmp(&rhs).into())
+	}
+
+	/// Hashes `this`.
+	#[instrument(name="Bytes::hash", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_hash(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(crate::utils::hash(&*this.try_downcast::<Self>()?).into())
+	}
+}
+
+impl Convertible for Bytes {
+	const CONVERT_FUNC: crate::Literal = crate::Literal::new("@bytes");
+}
+
+/// Converts `obj` to bytes to be written: [`Bytes`] are written as-is, and everything else is
+/// converted to [`Text`] and written as UTF-8.
+pub(crate) fn to_write(obj: &Object) -> crate::Result<Vec<u8>> {
+	if let Some(bytes) = obj.downcast::<Bytes>() {
+		Ok(bytes.0.clone())
+	} else {
+		obj.call_downcast::<Text>()
+			.map(|text| text.as_ref().as_bytes().to_vec())
+			.map_err(|_| TypeError::Messaged(format!("can only write Bytes or @text, not {:?}", obj)).into())
+	}
+}
+
+/// Reads from `reader`: either exactly the amount given by `amnt`, or until the end of the stream if
+/// it's omitted.
+pub(crate) fn read_from<R: std::io::Read>(mut reader: R, amnt: Option<&Object>) -> crate::Result<Bytes> {
+	let mut buf =
+		if let Some(amnt) = amnt {
+			vec![0; to_len(amnt)?]
+		} else {
+			Vec::new()
+		};
+
+	if amnt.is_some() {
+		reader.read_exact(&mut buf)?;
+	} else {
+		reader.read_to_end(&mut buf)?;
+	}
+
+	Ok(buf.into())
+}
+
+/// Converts `amnt` to an amount of bytes, which can't be negative.
+pub(crate) fn to_len(amnt: &Object) -> crate::Result<usize> {
+	let amnt = amnt.call_downcast::<Number>()?.truncate();
+
+	usize::try_from(amnt)
+		.map_err(|_| ValueError::Messaged(format!("amount of bytes cannot be negative: {}", amnt)).into())
+}
+
+impl_object_type!{
+for Bytes [(init_parent super::Basic super::Comparable super::Iterable) (parents super::Basic)]:
+	"()" => method Self::qs_call,
+	"from_hex" => method Self::qs_from_hex,
+	"@bytes" => method Self::qs_at_bytes,
+	"@text" => method Self::qs_at_text,
+	"inspect" => method Self::qs_inspect,
+	"@bool" => method Self::qs_at_bool,
+	"@list" => method Self::qs_at_list,
+	"@iter" => method Self::qs_at_iter,
+
+	"len" => method Self::qs_len,
+	"get" => method Self::qs_get,
+	"[]" => method Self::qs_get,
+	"+" => method Self::qs_add,
+	"+=" => method Self::qs_add_assign,
+	"push" => method Self::qs_push,
+	"<<" => method Self::qs_push,
+	"hex" => method Self::qs_hex,
+	"==" => method Self::qs_eql,
+	"<=>" => method Self::qs_cmp,
+	"hash" => method Self::qs_hash,
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn hex() {
+		let bytes = Bytes::new(vec![0, 15, 16, 255]);
+
+		assert_eq!(bytes.to_hex(), "000f10ff");
+		assert_eq!(Bytes::from_hex("000F10ff").unwrap(), bytes);
+		assert!(Bytes::from_hex("abc").is_err());
+		assert!(Bytes::from_hex("zz").is_err());
+	}
+
+	#[test]
+	fn byte_range() {
+		crate::init();
+
+		assert_eq!(to_byte(&Object::from(255)).unwrap(), 255);
+		assert!(to_byte(&Object::from(256)).is_err());
+		assert!(to_byte(&Object::from(-1)).is_err());
+		assert!(to_byte(&Object::from(Number::from(2).pow(Number::from(64)))).is_err());
+
+		let list = |n: i64| Object::from(crate::types::List::from(vec![Object::from(n)]));
+		assert!(Bytes::qs_call(&Object::default(), args!(list(256))).is_err());
+		assert!(Bytes::qs_call(&Object::default(), args!(list(-1))).is_err());
+	}
+
+	#[test]
+	fn indexing() {
+		let bytes = Bytes::from(&b"hello"[..]);
+
+		assert_eq!(bytes.get(0), Some(b'h'));
+		assert_eq!(bytes.get(-1), Some(b'o'));
+		assert_eq!(bytes.get(5), None);
+		assert_eq!(bytes.get_rng(1, 2), Some(&b"el"[..]));
+		assert_eq!(bytes.get_rng(3, 100), Some(&b"lo"[..]));
+	}
+
+	#[test]
+	fn text() {
+		assert_eq!(Text::try_from(&Bytes::from(&b"hi"[..])).unwrap().as_ref(), "hi");
+
+		let err = Text::try_from(&Bytes::new(vec![b'a', 0xff])).unwrap_err();
+		assert!(err.to_string().contains("byte 1"), "{}", err);
+
+		assert_eq!(Bytes::new(vec![b'a', 0, b'"']).to_string(), r#"b"a\x00\"""#);
+	}
+}
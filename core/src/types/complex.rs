@@ -0,0 +1,349 @@
+use crate::{Object, Args};
+use crate::types::{Number, Text, Boolean};
+use crate::types::number::FloatType;
+use num_complex::Complex64;
+use std::fmt::{self, Display, Formatter};
+use std::hash::{Hash, Hasher};
+use std::ops;
+use tracing::instrument;
+
+/// A complex number, whose real and imaginary parts are floats.
+///
+/// [`Number`]s are promoted to complex numbers when used in arithmetic with them.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Complex(Complex64);
+
+impl Hash for Complex {
+	fn hash<H: Hasher>(&self, h: &mut H) {
+		self.0.re.to_bits().hash(h);
+		self.0.im.to_bits().hash(h);
+	}
+}
+
+impl Display for Complex {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		// we go through `Number` so that whole parts are written without a trailing `.0`.
+		let sign = if self.0.im.is_sign_negative() { '-' } else { '+' };
+
+		write!(f, "{}{}{}i", Number::from(self.0.re), sign, Number::from(self.0.im.abs()))
+	}
+}
+
+impl From<Number> for Complex {
+	#[inline]
+	fn from(num: Number) -> Self {
+		Self::new(num.into(), 0.0)
+	}
+}
+
+impl From<Complex64> for Complex {
+	#[inline]
+	fn from(complex: Complex64) -> Self {
+		Self(complex)
+	}
+}
+
+impl Complex {
+	/// The imaginary unit.
+	pub const I: Self = Self(Complex64::new(0.0, 1.0));
+
+	/// Create a new complex number.
+	#[inline]
+	pub const fn new(re: FloatType, im: FloatType) -> Self {
+		Self(Complex64::new(re, im))
+	}
+
+	/// Get the real part.
+	#[inline]
+	pub fn re(&self) -> FloatType {
+		self.0.re
+	}
+
+	/// Get the imaginary part.
+	#[inline]
+	pub fn im(&self) -> FloatType {
+		self.0.im
+	}
+
+	/// Get the complex conjugate.
+	#[inline]
+	pub fn conj(&self) -> Self {
+		Self(self.0.conj())
+	}
+
+	/// Get the absolute value (aka the magnitude).
+	#[inline]
+	pub fn abs(&self) -> FloatType {
+		self.0.norm()
+	}
+
+	/// Get the argument (aka the phase), in radians.
+	#[inline]
+	pub fn arg(&self) -> FloatType {
+		self.0.arg()
+	}
+
+	/// Raises `self` to the power of `rhs`.
+	///
+	/// Whole, real exponents use repeated multiplication, so that (eg) `I ** 2` is exactly `-1`.
+	pub fn pow(self, rhs: Self) -> Self {
+		if rhs.0.im == 0.0 && rhs.0.re.fract() == 0.0 && rhs.0.re.abs() <= i32::MAX as FloatType {
+			Self(self.0.powi(rhs.0.re as i32))
+		} else {
+			Self(self.0.powc(rhs.0))
+		}
+	}
+}
+
+macro_rules! impl_math_ops {
+	($($trait:ident $fn:ident)*) => {
+		$(
+			impl ops::$trait for Complex {
+				type Output = Self;
+
+				#[inline]
+				fn $fn(self, rhs: Self) -> Self {
+					Self(ops::$trait::$fn(self.0, rhs.0))
+				}
+			}
+		)*
+	};
+}
+
+impl_math_ops! { Add add Sub sub Mul mul Div div }
+
+impl ops::Neg for Complex {
+	type Output = Self;
+
+	#[inline]
+	fn neg(self) -> Self {
+		Self(-self.0)
+	}
+}
+
+/// Converts `obj` to a [`Complex`], promoting it from a [`Number`] if it isn't one already.
+fn operand(obj: &Object) -> crate::Result<Complex> {
+	if let Some(complex) = obj.downcast::<Complex>() {
+		Ok(*complex)
+	} else {
+		Ok(Complex::from(obj.call_downcast::<Number>()?.clone()))
+	}
+}
+
+/// Quest methods
+impl Complex {
+	/// Create a new complex number.
+	///
+	/// # Arguments
+	/// 1. (required, `@num`) The real part.
+	/// 2. (optional, `@num`) The imaginary part; defaults to zero.
+	#[instrument(name="Complex::()", level="trace", skip(args), fields(?args))]
+	pub fn qs_call(_: &Object, args: Args) -> crate::Result<Object> {
+		let re = args.try_arg(0)?.call_downcast::<Number>()?.clone();
+		let im =
+			if let Some(im) = args.arg(1) {
+				im.call_downcast::<Number>()?.clone()
+			} else {
+				Number::ZERO
+			};
+
+		Ok(Self::new(re.into(), im.into()).into())
+	}
+
+	/// Get the real part of `this`.
+	#[instrument(name="Complex::re", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_re(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.re().into())
+	}
+
+	/// Get the imaginary part of `this`.
+	#[instrument(name="Complex::im", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_im(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.im().into())
+	}
+
+	/// Get the complex conjugate of `this`.
+	#[instrument(name="Complex::conj", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_conj(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.conj().into())
+	}
+
+	/// Get the absolute value of `this`.
+	#[instrument(name="Complex::abs", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_abs(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.abs().into())
+	}
+
+	/// Get the argument of `this`, in radians.
+	#[instrument(name="Complex::arg", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_arg(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.arg().into())
+	}
+
+	/// Converts `this` to a [`Text`] of the form `re+imi`.
+	#[instrument(name="Complex::@text", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_at_text(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(Text::from(this.try_downcast::<Self>()?.to_string()).into())
+	}
+
+	/// Inspects `this`.
+	///
+	/// This is identical to [`qs_at_text`](#qs_at_text).
+	#[instrument(name="Complex::inspect", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_inspect(this: &Object, args: Args) -> crate::Result<Object> {
+		Self::qs_at_text(this, args)
+	}
+
+	/// Converts `this` to a [`Boolean`]; only zero is false.
+	#[instrument(name="Complex::@bool", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_at_bool(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(Boolean::from(*this.try_downcast::<Self>()? != Self::default()).into())
+	}
+
+	/// Hash `this`.
+	///
+	/// Complex numbers without an imaginary part hash the same as their equivalent [`Number`].
+	#[instrument(name="Complex::hash", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_hash(this: &Object, _: Args) -> crate::Result<Object> {
+		let this = *this.try_downcast::<Self>()?;
+
+		if this.im() == 0.0 {
+			Ok(crate::utils::hash(&Number::from(this.re())).into())
+		} else {
+			Ok(crate::utils::hash(&this).into())
+		}
+	}
+
+	/// See if `this` is equal to the first argument.
+	///
+	/// [`Number`]s are equal to complex numbers with the same real part and no imaginary part.
+	///
+	/// # Arguments
+	/// 1. (required) The other object to compare against.
+	#[instrument(name="Complex::==", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_eql(this: &Object, args: Args) -> crate::Result<Object> {
+		let this = *this.try_downcast::<Self>()?;
+		let rhs = args.try_arg(0)?;
+
+		let rhs =
+			if let Some(rhs) = rhs.downcast::<Self>() {
+				*rhs
+			} else if let Some(rhs) = rhs.downcast::<Number>() {
+				Self::from(rhs.clone())
+			} else {
+				return Ok(false.into());
+			};
+
+		Ok((this == rhs).into())
+	}
+
+	/// Negate `this`.
+	#[instrument(name="Complex::-@", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_neg(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok((-*this.try_downcast::<Self>()?).into())
+	}
+
+	/// Add `this` and the first argument.
+	///
+	/// # Arguments
+	/// 1. (required, `@num` or [`Complex`]) The addend.
+	#[instrument(name="Complex::+", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_add(this: &Object, args: Args) -> crate::Result<Object> {
+		let addend = operand(args.try_arg(0)?)?;
+
+		Ok((*this.try_downcast::<Self>()? + addend).into())
+	}
+
+	/// Subtract the first argument from `this`.
+	///
+	/// # Arguments
+	/// 1. (required, `@num` or [`Complex`]) The subtrahend.
+	#[instrument(name="Complex::-", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_sub(this: &Object, args: Args) -> crate::Result<Object> {
+		let subtrahend = operand(args.try_arg(0)?)?;
+
+		Ok((*this.try_downcast::<Self>()? - subtrahend).into())
+	}
+
+	/// Multiply `this` and the first argument.
+	///
+	/// # Arguments
+	/// 1. (required, `@num` or [`Complex`]) The multiplicand.
+	#[instrument(name="Complex::*", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_mul(this: &Object, args: Args) -> crate::Result<Object> {
+		let multiplicand = operand(args.try_arg(0)?)?;
+
+		Ok((*this.try_downcast::<Self>()? * multiplicand).into())
+	}
+
+	/// Divide `this` by the first argument.
+	///
+	/// # Arguments
+	/// 1. (required, `@num` or [`Complex`]) The divisor.
+	#[instrument(name="Complex::/", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_div(this: &Object, args: Args) -> crate::Result<Object> {
+		let divisor = operand(args.try_arg(0)?)?;
+
+		Ok((*this.try_downcast::<Self>()? / divisor).into())
+	}
+
+	/// Raises `this` to the power of the first argument.
+	///
+	/// # Arguments
+	/// 1. (required, `@num` or [`Complex`]) The exponent.
+	#[instrument(name="Complex::**", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_pow(this: &Object, args: Args) -> crate::Result<Object> {
+		let exponent = operand(args.try_arg(0)?)?;
+
+		Ok(this.try_downcast::<Self>()?.pow(exponent).into())
+	}
+}
+
+impl_object_type!{
+for Complex [(parents super::Basic)]:
+	"I" => const Self::I,
+	"()" => method Self::qs_call,
+	"re" => method Self::qs_re,
+	"im" => method Self::qs_im,
+	"conj" => method Self::qs_conj,
+	"abs" => method Self::qs_abs,
+	"arg" => method Self::qs_arg,
+
+	"@text" => method Self::qs_at_text,
+	"inspect" => method Self::qs_inspect,
+	"@bool" => method Self::qs_at_bool,
+	"hash" => method Self::qs_hash,
+	"==" => method Self::qs_eql,
+
+	"-@" => method Self::qs_neg,
+	"+" => method Self::qs_add,
+	"-" => method Self::qs_sub,
+	"*" => method Self::qs_mul,
+	"/" => method Self::qs_div,
+	"**" => method Self::qs_pow,
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn display() {
+		assert_eq!(Complex::new(1.0, 2.0).to_string(), "1+2i");
+		assert_eq!(Complex::new(1.5, -2.0).to_string(), "1.5-2i");
+		assert_eq!(Complex::new(0.0, 0.0).to_string(), "0+0i");
+	}
+
+	#[test]
+	fn arithmetic() {
+		crate::init();
+
+		let sum = Complex::qs_add(&Complex::new(1.0, 2.0).into(), args!(3)).unwrap();
+		assert_eq!(*sum.downcast::<Complex>().unwrap(), Complex::new(4.0, 2.0));
+
+		let square = Complex::I * Complex::I;
+		assert_eq!(square, Complex::new(-1.0, 0.0));
+		assert_eq!(Complex::new(3.0, 4.0).abs(), 5.0);
+		assert_eq!(Complex::new(3.0, 4.0).conj(), Complex::new(3.0, -4.0));
+	}
+}
@@ -0,0 +1,279 @@
+use crate::{Object, Args};
+use crate::error::ValueError;
+use crate::types::{Text, Number, Null, Duration, Time};
+use crate::types::time::{Zone, component, format};
+use chrono::{NaiveDate, Datelike};
+use std::convert::TryFrom;
+use std::fmt::{self, Display, Formatter};
+use tracing::instrument;
+
+/// A calendar date, without a time of day or time zone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date(NaiveDate);
+
+impl From<NaiveDate> for Date {
+	#[inline]
+	fn from(date: NaiveDate) -> Self {
+		Self(date)
+	}
+}
+
+impl From<Date> for NaiveDate {
+	#[inline]
+	fn from(date: Date) -> Self {
+		date.0
+	}
+}
+
+impl Display for Date {
+	/// Writes the date in the form `YYYY-MM-DD`.
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		Display::fmt(&self.0, f)
+	}
+}
+
+fn out_of_range() -> crate::Error {
+	ValueError::Messaged("date out of range".to_string()).into()
+}
+
+/// Quest methods
+impl Date {
+	/// Create a new date.
+	///
+	/// # Arguments
+	/// 1. (required, `@num`) The year.
+	/// 2. (optional, `@num`) The month, starting at 1; defaults to 1.
+	/// 3. (optional, `@num`) The day of the month, starting at 1; defaults to 1.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// assert(Date(2020, 2, 29).@text() == "2020-02-29");
+	/// ```
+	#[instrument(name="Date::()", level="trace", skip(args), fields(?args))]
+	pub fn qs_call(_: &Object, args: Args) -> crate::Result<Object> {
+		let year = i32::try_from(args.try_arg(0)?.call_downcast::<Number>()?.clone())?;
+		let month = component(&args, 1, 1)?;
+		let day = component(&args, 2, 1)?;
+
+		NaiveDate::from_ymd_opt(year, month, day)
+			.map(|date| Self(date).into())
+			.ok_or_else(|| ValueError::Messaged(format!("invalid date: {}-{:02}-{:02}", year, month, day)).into())
+	}
+
+	/// Get the current date.
+	///
+	/// # Arguments
+	/// 1. (optional) The time zone; see [`Time.now`](crate::types::Time::qs_now). Defaults to the
+	///    local zone.
+	#[instrument(name="Date::today", level="trace", skip(args), fields(?args))]
+	pub fn qs_today(_: &Object, args: Args) -> crate::Result<Object> {
+		Ok(Time::now(&Zone::from_arg(args.arg(0))?).date().into())
+	}
+
+	/// Parses a date from text.
+	///
+	/// # Arguments
+	/// 1. (required, `@text`) The text to parse.
+	/// 2. (optional, `@text`) The strftime-style format; defaults to `"%Y-%m-%d"`.
+	#[instrument(name="Date::parse", level="trace", skip(args), fields(?args))]
+	pub fn qs_parse(_: &Object, args: Args) -> crate::Result<Object> {
+		let text = args.try_arg(0)?.call_downcast::<Text>()?;
+		let fmt =
+			match args.arg(1) {
+				Some(fmt) if !fmt.is_a::<Null>() => fmt.call_downcast::<Text>()?.as_ref().to_string(),
+				_ => "%Y-%m-%d".to_string()
+			};
+
+		NaiveDate::parse_from_str(text.as_ref(), &fmt)
+			.map(|date| Self(date).into())
+			.map_err(|err| ValueError::Messaged(format!("cannot parse {:?} with {:?}: {}", text.as_ref(), fmt, err)).into())
+	}
+
+	/// Formats `this` with a strftime-style format.
+	///
+	/// # Arguments
+	/// 1. (required, `@text`) The format, such as `"%A, %B %d"`.
+	#[instrument(name="Date::format", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_format(this: &Object, args: Args) -> crate::Result<Object> {
+		let fmt = args.try_arg(0)?.call_downcast::<Text>()?;
+		let this = this.try_downcast::<Self>()?;
+
+		Ok(format(this.0.format(fmt.as_ref()), fmt.as_ref())?.into())
+	}
+
+	/// Get the [`Time`] at the given time of day on `this`.
+	///
+	/// # Arguments
+	/// 1. (optional, `@num`) The hour; defaults to 0.
+	/// 2. (optional, `@num`) The minute; defaults to 0.
+	/// 3. (optional, `@num`) The second, which may be fractional; defaults to 0.
+	/// 4. (optional) The time zone; see [`Time.now`](crate::types::Time::qs_now). Defaults to the
+	///    local zone.
+	#[instrument(name="Date::at", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_at(this: &Object, args: Args) -> crate::Result<Object> {
+		let hour = component(&args, 0, 0)?;
+		let minute = component(&args, 1, 0)?;
+		let second =
+			if let Some(second) = args.arg(2) {
+				second.call_downcast::<Number>()?.clone()
+			} else {
+				Number::ZERO
+			};
+		let zone = Zone::from_arg(args.arg(3))?;
+		let this = *this.try_downcast::<Self>()?;
+
+		Ok(Time::from_parts(this.0, hour, minute, second, &zone)?.into())
+	}
+
+	/// Converts `this` to a [`Text`] of the form `YYYY-MM-DD`.
+	#[instrument(name="Date::@text", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_at_text(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(Text::from(this.try_downcast::<Self>()?.to_string()).into())
+	}
+
+	/// Inspects `this`.
+	#[instrument(name="Date::inspect", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_inspect(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(Text::from(format!("Date({})", *this.try_downcast::<Self>()?)).into())
+	}
+
+	/// Get the year of `this`.
+	#[instrument(name="Date::year", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_year(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.0.year().into())
+	}
+
+	/// Get the month of `this`, starting at 1.
+	#[instrument(name="Date::month", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_month(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.0.month().into())
+	}
+
+	/// Get the day of the month of `this`, starting at 1.
+	#[instrument(name="Date::day", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_day(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.0.day().into())
+	}
+
+	/// Get the day of the week of `this`, from 1 (Monday) to 7 (Sunday).
+	#[instrument(name="Date::weekday", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_weekday(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.0.weekday().number_from_monday().into())
+	}
+
+	/// Get the day of the year of `this`, starting at 1.
+	#[instrument(name="Date::yday", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_yday(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.0.ordinal().into())
+	}
+
+	/// Hash `this`.
+	#[instrument(name="Date::hash", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_hash(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(crate::utils::hash(&*this.try_downcast::<Self>()?).into())
+	}
+
+	/// See if `this` is equal to the first argument.
+	///
+	/// # Arguments
+	/// 1. (required) The other object to compare against.
+	#[instrument(name="Date::==", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_eql(this: &Object, args: Args) -> crate::Result<Object> {
+		let this = *this.try_downcast::<Self>()?;
+		let rhs = args.try_arg(0)?.downcast::<Self>();
+
+		Ok(rhs.map(|rhs| *rhs == this).unwrap_or(false).into())
+	}
+
+	/// Compares `this` to the first argument.
+	///
+	/// # Arguments
+	/// 1. (required) The date to compare against.
+	#[instrument(name="Date::<=>", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_cmp(this: &Object, args: Args) -> crate::Result<Object> {
+		let this = *this.try_downcast::<Self>()?;
+		let rhs = args.try_arg(0)?.downcast::<Self>();
+
+		Ok(rhs.map(|rhs| this.cmp(&*rhs).into()).unwrap_or_default())
+	}
+
+	/// Add a [`Duration`] to `this`; any partial day is ignored.
+	///
+	/// # Arguments
+	/// 1. (required, [`Duration`]) The duration to add.
+	#[instrument(name="Date::+", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_add(this: &Object, args: Args) -> crate::Result<Object> {
+		let duration = *args.try_arg(0)?.try_downcast::<Duration>()?;
+		let this = this.try_downcast::<Self>()?;
+
+		this.0.checked_add_signed(duration.into()).map(|date| Self(date).into()).ok_or_else(out_of_range)
+	}
+
+	/// Subtract the first argument from `this`.
+	///
+	/// Subtracting a [`Duration`] returns a new [`Date`] (ignoring any partial day), and subtracting a
+	/// [`Date`] returns the [`Duration`] between the two.
+	///
+	/// # Arguments
+	/// 1. (required, [`Duration`] or [`Date`]) The value to subtract.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// assert((Date(2021, 1, 1) - Date(2020, 1, 1)).days() == 366);
+	/// ```
+	#[instrument(name="Date::-", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_sub(this: &Object, args: Args) -> crate::Result<Object> {
+		let rhs = args.try_arg(0)?;
+		let this = this.try_downcast::<Self>()?;
+
+		if let Some(rhs) = rhs.downcast::<Self>() {
+			return Ok(Duration::from(this.0.signed_duration_since(rhs.0)).into());
+		}
+
+		let duration = *rhs.try_downcast::<Duration>()?;
+
+		this.0.checked_sub_signed(duration.into()).map(|date| Self(date).into()).ok_or_else(out_of_range)
+	}
+}
+
+impl_object_type!{
+for Date [(init_parent super::Basic super::Comparable) (parents super::Basic)]:
+	"()" => method Self::qs_call,
+	"today" => method Self::qs_today,
+	"parse" => method Self::qs_parse,
+
+	"format" => method Self::qs_format,
+	"at" => method Self::qs_at,
+	"@text" => method Self::qs_at_text,
+	"inspect" => method Self::qs_inspect,
+	"year" => method Self::qs_year,
+	"month" => method Self::qs_month,
+	"day" => method Self::qs_day,
+	"weekday" => method Self::qs_weekday,
+	"yday" => method Self::qs_yday,
+
+	"hash" => method Self::qs_hash,
+	"==" => method Self::qs_eql,
+	"<=>" => method Self::qs_cmp,
+	"+" => method Self::qs_add,
+	"-" => method Self::qs_sub,
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn arithmetic() {
+		crate::init();
+
+		let leap = Object::from(Date(NaiveDate::from_ymd(2020, 2, 28)));
+		let day = Object::from(Duration::from(chrono::Duration::days(1)));
+
+		let next = Date::qs_add(&leap, args!(day.clone())).unwrap();
+		assert_eq!(next.downcast::<Date>().unwrap().to_string(), "2020-02-29");
+
+		let diff = Date::qs_sub(&next, args!(leap)).unwrap();
+		assert!(diff.eq_obj(&day).unwrap());
+	}
+}
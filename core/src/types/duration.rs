@@ -0,0 +1,415 @@
+use crate::{Object, Args};
+use crate::error::ValueError;
+use crate::types::{Text, Boolean, Number};
+use crate::types::number::FloatType;
+use std::convert::TryFrom;
+use std::fmt::{self, Display, Formatter};
+use std::hash::{Hash, Hasher};
+use tracing::instrument;
+
+/// A signed span of time, with nanosecond precision.
+///
+/// Durations are the result of subtracting two [`Time`](crate::types::Time)s or
+/// [`Date`](crate::types::Date)s, and can be added to either of them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Duration(chrono::Duration);
+
+// `chrono::Duration` only implements `Hash` when chrono's `oldtime` feature is enabled.
+impl Hash for Duration {
+	fn hash<H: Hasher>(&self, h: &mut H) {
+		let secs = self.0.num_seconds();
+		let nanos = (self.0 - chrono::Duration::seconds(secs)).num_nanoseconds();
+
+		(secs, nanos).hash(h);
+	}
+}
+
+impl From<chrono::Duration> for Duration {
+	#[inline]
+	fn from(duration: chrono::Duration) -> Self {
+		Self(duration)
+	}
+}
+
+impl From<Duration> for chrono::Duration {
+	#[inline]
+	fn from(duration: Duration) -> Self {
+		duration.0
+	}
+}
+
+impl From<std::time::Duration> for Duration {
+	fn from(duration: std::time::Duration) -> Self {
+		Self(chrono::Duration::from_std(duration).unwrap_or_else(|_| chrono::Duration::max_value()))
+	}
+}
+
+impl TryFrom<Duration> for std::time::Duration {
+	type Error = ValueError;
+
+	fn try_from(duration: Duration) -> Result<Self, Self::Error> {
+		duration.0.to_std()
+			.map_err(|_| ValueError::Messaged(format!("duration is negative: {}", duration)))
+	}
+}
+
+impl Display for Duration {
+	/// Writes the duration in the form `1d2h3m4.5s`, omitting any zero components.
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		if self.0.is_zero() {
+			return f.write_str("0s");
+		}
+
+		if self.0 < chrono::Duration::zero() {
+			f.write_str("-")?;
+		}
+
+		let mut secs = self.0.num_seconds().unsigned_abs();
+		let nanos = self.subsec_nanos().unsigned_abs();
+
+		for &(suffix, len) in &[("d", 86_400), ("h", 3_600), ("m", 60)] {
+			if secs >= len {
+				write!(f, "{}{}", secs / len, suffix)?;
+				secs %= len;
+			}
+		}
+
+		match (secs, nanos) {
+			(0, 0) => Ok(()),
+			(secs, 0) => write!(f, "{}s", secs),
+			(secs, nanos) => write!(f, "{}.{}s", secs, format!("{:09}", nanos).trim_end_matches('0'))
+		}
+	}
+}
+
+impl Duration {
+	/// A duration of zero length.
+	#[inline]
+	pub fn zero() -> Self {
+		Self(chrono::Duration::zero())
+	}
+
+	/// Create a duration from the given amount of seconds, which may be fractional.
+	pub fn from_secs(secs: Number) -> crate::Result<Self> {
+		const MAX_SECS: i64 = i64::MAX / 1_000;
+
+		let duration =
+			match i64::try_from(secs.clone()) {
+				Ok(secs) if (-MAX_SECS..=MAX_SECS).contains(&secs) => Some(chrono::Duration::seconds(secs)),
+				Ok(_) => None,
+				Err(_) => {
+					let nanos = FloatType::from(secs.clone()) * 1e9;
+
+					if nanos.is_finite() && nanos.abs() < i64::MAX as FloatType {
+						Some(chrono::Duration::nanoseconds(nanos.round() as i64))
+					} else {
+						None
+					}
+				}
+			};
+
+		duration
+			.map(Self)
+			.ok_or_else(|| ValueError::Messaged(format!("duration out of range: {} seconds", secs)).into())
+	}
+
+	/// Get the nanoseconds that aren't part of a whole second; this has the same sign as `self`.
+	fn subsec_nanos(&self) -> i64 {
+		(self.0 - chrono::Duration::seconds(self.0.num_seconds())).num_nanoseconds().unwrap_or(0)
+	}
+
+	/// Get the total amount of seconds, including fractional seconds.
+	pub fn as_secs(&self) -> Number {
+		let nanos = self.subsec_nanos();
+
+		if nanos == 0 {
+			self.0.num_seconds().into()
+		} else {
+			(self.0.num_seconds() as FloatType + nanos as FloatType / 1e9).into()
+		}
+	}
+
+	/// Multiply `self` by `factor`, returning `None` if the result is out of bounds.
+	pub fn checked_mul(&self, factor: Number) -> Option<Self> {
+		if let (Ok(factor), Some(nanos)) = (i64::try_from(factor.clone()), self.0.num_nanoseconds()) {
+			return nanos.checked_mul(factor).map(|nanos| Self(chrono::Duration::nanoseconds(nanos)));
+		}
+
+		Self::from_secs((FloatType::from(self.as_secs()) * FloatType::from(factor)).into()).ok()
+	}
+}
+
+/// Converts `obj` to a [`std::time::Duration`]: [`Duration`]s are used as-is, and anything else is
+/// converted to a [`Number`] of seconds.
+pub(crate) fn to_std(obj: &Object) -> crate::Result<std::time::Duration> {
+	let duration =
+		if let Some(duration) = obj.downcast::<Duration>() {
+			*duration
+		} else {
+			Duration::from_secs(obj.call_downcast::<Number>()?.clone())?
+		};
+
+	Ok(std::time::Duration::try_from(duration)?)
+}
+
+/// Converts `obj` to a [`Duration`], raising a `TypeError` if it isn't one.
+fn operand(obj: &Object) -> crate::Result<Duration> {
+	Ok(*obj.try_downcast::<Duration>()?)
+}
+
+fn out_of_range() -> crate::Error {
+	ValueError::Messaged("duration out of range".to_string()).into()
+}
+
+/// Quest methods
+impl Duration {
+	/// Create a new duration.
+	///
+	/// # Arguments
+	/// 1. (optional, `@num`) The length of the duration in seconds, which may be fractional; defaults
+	///    to zero.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// assert(Duration(90) == Duration.MINUTE + Duration.SECOND * 30);
+	/// assert(Duration(1.5).millis() == 1500);
+	/// ```
+	#[instrument(name="Duration::()", level="trace", skip(args), fields(?args))]
+	pub fn qs_call(_: &Object, args: Args) -> crate::Result<Object> {
+		if let Some(secs) = args.arg(0) {
+			Ok(Self::from_secs(secs.call_downcast::<Number>()?.clone())?.into())
+		} else {
+			Ok(Self::zero().into())
+		}
+	}
+
+	/// Get the length of `this` in seconds, including fractional seconds.
+	#[instrument(name="Duration::seconds", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_seconds(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.as_secs().into())
+	}
+
+	/// Get the amount of whole milliseconds in `this`.
+	#[instrument(name="Duration::millis", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_millis(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.0.num_milliseconds().into())
+	}
+
+	/// Get the amount of whole minutes in `this`.
+	#[instrument(name="Duration::minutes", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_minutes(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.0.num_minutes().into())
+	}
+
+	/// Get the amount of whole hours in `this`.
+	#[instrument(name="Duration::hours", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_hours(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.0.num_hours().into())
+	}
+
+	/// Get the amount of whole days in `this`.
+	#[instrument(name="Duration::days", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_days(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.0.num_days().into())
+	}
+
+	/// Converts `this` to a [`Number`] of seconds.
+	///
+	/// This is identical to [`qs_seconds`](#qs_seconds).
+	#[instrument(name="Duration::@num", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_at_num(this: &Object, args: Args) -> crate::Result<Object> {
+		Self::qs_seconds(this, args)
+	}
+
+	/// Converts `this` to a [`Text`] of the form `1d2h3m4.5s`.
+	#[instrument(name="Duration::@text", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_at_text(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(Text::from(this.try_downcast::<Self>()?.to_string()).into())
+	}
+
+	/// Inspects `this`.
+	#[instrument(name="Duration::inspect", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_inspect(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(Text::from(format!("Duration({})", *this.try_downcast::<Self>()?)).into())
+	}
+
+	/// Converts `this` to a [`Boolean`]; only zero-length durations are false.
+	#[instrument(name="Duration::@bool", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_at_bool(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(Boolean::from(!this.try_downcast::<Self>()?.0.is_zero()).into())
+	}
+
+	/// Hash `this`.
+	#[instrument(name="Duration::hash", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_hash(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(crate::utils::hash(&*this.try_downcast::<Self>()?).into())
+	}
+
+	/// See if `this` is equal to the first argument.
+	///
+	/// # Arguments
+	/// 1. (required) The other object to compare against.
+	#[instrument(name="Duration::==", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_eql(this: &Object, args: Args) -> crate::Result<Object> {
+		let this = *this.try_downcast::<Self>()?;
+		let rhs = args.try_arg(0)?.downcast::<Self>();
+
+		Ok(rhs.map(|rhs| *rhs == this).unwrap_or(false).into())
+	}
+
+	/// Compares `this` to the first argument.
+	///
+	/// # Arguments
+	/// 1. (required) The duration to compare against.
+	#[instrument(name="Duration::<=>", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_cmp(this: &Object, args: Args) -> crate::Result<Object> {
+		let this = *this.try_downcast::<Self>()?;
+		let rhs = args.try_arg(0)?.downcast::<Self>();
+
+		Ok(rhs.map(|rhs| this.cmp(&*rhs).into()).unwrap_or_default())
+	}
+
+	/// Negate `this`.
+	#[instrument(name="Duration::-@", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_neg(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(Self(-this.try_downcast::<Self>()?.0).into())
+	}
+
+	/// Get the absolute value of `this`.
+	#[instrument(name="Duration::abs", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_abs(this: &Object, _: Args) -> crate::Result<Object> {
+		let this = *this.try_downcast::<Self>()?;
+
+		if this.0 < chrono::Duration::zero() {
+			Ok(Self(-this.0).into())
+		} else {
+			Ok(this.into())
+		}
+	}
+
+	/// Add `this` and the first argument.
+	///
+	/// # Arguments
+	/// 1. (required, [`Duration`]) The addend.
+	#[instrument(name="Duration::+", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_add(this: &Object, args: Args) -> crate::Result<Object> {
+		let addend = operand(args.try_arg(0)?)?;
+		let this = *this.try_downcast::<Self>()?;
+
+		this.0.checked_add(&addend.0).map(|sum| Self(sum).into()).ok_or_else(out_of_range)
+	}
+
+	/// Subtract the first argument from `this`.
+	///
+	/// # Arguments
+	/// 1. (required, [`Duration`]) The subtrahend.
+	#[instrument(name="Duration::-", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_sub(this: &Object, args: Args) -> crate::Result<Object> {
+		let subtrahend = operand(args.try_arg(0)?)?;
+		let this = *this.try_downcast::<Self>()?;
+
+		this.0.checked_sub(&subtrahend.0).map(|diff| Self(diff).into()).ok_or_else(out_of_range)
+	}
+
+	/// Multiply `this` by the first argument.
+	///
+	/// # Arguments
+	/// 1. (required, `@num`) The factor; it may be fractional.
+	#[instrument(name="Duration::*", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_mul(this: &Object, args: Args) -> crate::Result<Object> {
+		let factor = args.try_arg(0)?.call_downcast::<Number>()?.clone();
+
+		this.try_downcast::<Self>()?.checked_mul(factor).map(Object::from).ok_or_else(out_of_range)
+	}
+
+	/// Divide `this` by the first argument.
+	///
+	/// Dividing by a [`Duration`] returns the ratio between the two as a [`Number`], and dividing by
+	/// anything else returns a new [`Duration`].
+	///
+	/// # Arguments
+	/// 1. (required, [`Duration`] or `@num`) The divisor.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// assert((Duration.HOUR / Duration.MINUTE) == 60);
+	/// assert((Duration.MINUTE / 4) == Duration(15));
+	/// ```
+	#[instrument(name="Duration::/", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_div(this: &Object, args: Args) -> crate::Result<Object> {
+		let this = *this.try_downcast::<Self>()?;
+		let divisor = args.try_arg(0)?;
+
+		if let Some(divisor) = divisor.downcast::<Self>() {
+			if divisor.0.is_zero() {
+				return Err(ValueError::Messaged("division by a zero-length duration".to_string()).into());
+			}
+
+			return Ok(match (this.0.num_nanoseconds(), divisor.0.num_nanoseconds()) {
+				(Some(lhs), Some(rhs)) if lhs % rhs == 0 => Number::from(lhs / rhs),
+				_ => Number::from(FloatType::from(this.as_secs()) / FloatType::from(divisor.as_secs()))
+			}.into());
+		}
+
+		let divisor = divisor.call_downcast::<Number>()?.clone();
+
+		if divisor == 0 {
+			return Err(ValueError::Messaged("division by zero".to_string()).into());
+		}
+
+		Self::from_secs((FloatType::from(this.as_secs()) / FloatType::from(divisor)).into())
+			.map(Object::from)
+	}
+}
+
+impl_object_type!{
+for Duration [(init_parent super::Basic super::Comparable) (parents super::Basic)]:
+	"MILLISECOND" => const Self(chrono::Duration::milliseconds(1)),
+	"SECOND" => const Self(chrono::Duration::seconds(1)),
+	"MINUTE" => const Self(chrono::Duration::minutes(1)),
+	"HOUR" => const Self(chrono::Duration::hours(1)),
+	"DAY" => const Self(chrono::Duration::days(1)),
+	"()" => method Self::qs_call,
+
+	"seconds" => method Self::qs_seconds,
+	"millis" => method Self::qs_millis,
+	"minutes" => method Self::qs_minutes,
+	"hours" => method Self::qs_hours,
+	"days" => method Self::qs_days,
+
+	"@num" => method Self::qs_at_num,
+	"@text" => method Self::qs_at_text,
+	"inspect" => method Self::qs_inspect,
+	"@bool" => method Self::qs_at_bool,
+	"hash" => method Self::qs_hash,
+	"==" => method Self::qs_eql,
+	"<=>" => method Self::qs_cmp,
+
+	"-@" => method Self::qs_neg,
+	"abs" => method Self::qs_abs,
+	"+" => method Self::qs_add,
+	"-" => method Self::qs_sub,
+	"*" => method Self::qs_mul,
+	"/" => method Self::qs_div,
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn display() {
+		assert_eq!(Duration::zero().to_string(), "0s");
+		assert_eq!(Duration(chrono::Duration::seconds(3723)).to_string(), "1h2m3s");
+		assert_eq!(Duration(chrono::Duration::milliseconds(90_500)).to_string(), "1m30.5s");
+		assert_eq!(Duration(chrono::Duration::days(-2)).to_string(), "-2d");
+	}
+
+	#[test]
+	fn from_secs() {
+		assert_eq!(Duration::from_secs(Number::from(90)).unwrap(), Duration(chrono::Duration::seconds(90)));
+		assert_eq!(Duration::from_secs(Number::from(1.25)).unwrap(), Duration(chrono::Duration::milliseconds(1250)));
+		assert_eq!(Duration::from_secs(Number::from(1.25)).unwrap().as_secs(), 1.25);
+		assert!(Duration::from_secs(Number::INF).is_err());
+	}
+}
@@ -0,0 +1,164 @@
+use crate::{Object, Args, Error, Binding};
+use crate::types::Text;
+use std::fmt::{self, Display, Formatter};
+use tracing::instrument;
+
+/// A native error that's been caught by [`Kernel::try`](crate::types::Kernel::qs_try), and
+/// surfaced to Quest.
+#[derive(Debug, Clone)]
+pub struct Exception {
+	/// The kind of error, e.g. `"KeyError"`.
+	kind: String,
+	/// The message associated with the error.
+	message: String,
+	/// The stackframe that the error originated in.
+	frame: Object
+}
+
+impl Display for Exception {
+	#[inline]
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		Display::fmt(&self.message, f)
+	}
+}
+
+impl Exception {
+	/// Create a new [`Exception`].
+	pub fn new(kind: impl Into<String>, message: String, frame: Object) -> Self {
+		Self { kind: kind.into(), message, frame }
+	}
+
+	/// Get the name of the kind of exception this is.
+	#[inline]
+	pub fn kind(&self) -> &str {
+		&self.kind
+	}
+
+	/// Get the message associated with this exception.
+	#[inline]
+	pub fn message(&self) -> &str {
+		&self.message
+	}
+
+	/// Convert an [`Error`] into a Quest object that can be handled by a `catch` block.
+	///
+	/// If the error was [thrown](Error::Thrown), the thrown object is returned as-is. If the error
+	/// doesn't have a [backtrace](Error::backtrace), `frame` is used as the originating frame.
+	///
+	/// # Panics
+	/// Panics if given an [`Error::Return`], as those aren't exceptions.
+	pub fn from_error(err: Error, frame: Binding) -> Object {
+		let kind =
+			match err {
+				Error::Raised { error, mut stack } if !stack.is_empty() =>
+					return Self::from_error(*error, stack.swap_remove(0)),
+				Error::Raised { error, .. } => return Self::from_error(*error, frame),
+				Error::Thrown(obj) => return obj,
+				Error::Return { .. } => unreachable!("returns shouldn't be converted to exceptions"),
+				Error::Messaged(_) => "Error",
+				Error::IoError(_) => "IoError",
+				Error::KeyError(_) => "KeyError",
+				Error::ArgumentError(_) => "ArgumentError",
+				Error::TypeError(_) => "TypeError",
+				Error::ValueError(_) => "ValueError",
+				Error::AssertionFailed(_) => "AssertionFailed",
+				Error::Boxed(_) => "Error",
+			};
+
+		Self::new(kind, err.to_string(), frame.into()).into()
+	}
+}
+
+/// Quest methods
+impl Exception {
+	/// Create a new exception with the given message and an optional kind.
+	///
+	/// The originating frame is the frame that the exception was created in.
+	///
+	/// # Arguments
+	/// 1. (required, `@text`) The message.
+	/// 2. (optional, `@text`) The kind; defaults to `"Error"`.
+	#[instrument(name="Exception::()", level="trace", skip(args), fields(?args))]
+	pub fn qs_call(_: &Object, args: Args) -> crate::Result<Object> {
+		let message = args.try_arg(0)?.call_downcast::<Text>()?.to_string();
+		let kind =
+			if let Some(kind) = args.arg(1) {
+				kind.call_downcast::<Text>()?.to_string()
+			} else {
+				"Error".to_string()
+			};
+
+		Ok(Self::new(kind, message, Binding::instance().into()).into())
+	}
+
+	/// Get the message of the exception.
+	#[instrument(name="Exception::message", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_message(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.message.clone().into())
+	}
+
+	/// Get the kind of the exception, e.g. `"TypeError"`.
+	#[instrument(name="Exception::kind", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_kind(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.kind.clone().into())
+	}
+
+	/// Get the stackframe that the exception originated in.
+	#[instrument(name="Exception::frame", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_frame(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.frame.clone())
+	}
+
+	/// Converts the exception into a [`Text`] of the form `"kind: message"`.
+	#[instrument(name="Exception::@text", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_at_text(this: &Object, _: Args) -> crate::Result<Object> {
+		let this = this.try_downcast::<Self>()?;
+
+		Ok(format!("{}: {}", this.kind, this.message).into())
+	}
+
+	/// Inspects the exception.
+	#[instrument(name="Exception::inspect", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_inspect(this: &Object, _: Args) -> crate::Result<Object> {
+		let this = this.try_downcast::<Self>()?;
+
+		Ok(format!("<{}: {:?}>", this.kind, this.message).into())
+	}
+
+	/// Throws `this`.
+	#[instrument(name="Exception::throw", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_throw(this: &Object, _: Args) -> crate::Result<Object> {
+		Err(Error::Thrown(this.clone()))
+	}
+}
+
+impl_object_type!{
+for Exception [(parents super::Basic)]:
+	"()" => method Self::qs_call,
+	"message" => method Self::qs_message,
+	"kind" => method Self::qs_kind,
+	"frame" => method Self::qs_frame,
+	"@text" => method Self::qs_at_text,
+	"inspect" => method Self::qs_inspect,
+	"throw" => method Self::qs_throw,
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::error::TypeError;
+
+	#[test]
+	fn from_error() {
+		crate::init();
+
+		let frame = Binding::instance();
+		let exc = Exception::from_error(TypeError::Messaged("oops".into()).into(), frame.clone());
+		let exc = exc.downcast::<Exception>().unwrap();
+		assert_eq!(exc.kind(), "TypeError");
+		assert!(exc.frame.is_identical(&frame));
+
+		let thrown = Object::from(12);
+		assert!(Exception::from_error(Error::Thrown(thrown.clone()), frame).is_identical(&thrown));
+	}
+}
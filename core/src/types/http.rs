@@ -0,0 +1,604 @@
+//! Making HTTP requests.
+
+use crate::{Object, Args};
+use crate::error::ValueError;
+use crate::types::{Text, Bytes, Boolean, Number, Null, Map};
+use std::convert::TryFrom;
+use std::io::{self, Read};
+use std::time::Duration;
+use tracing::instrument;
+
+/// The namespace for the HTTP functions.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Http;
+
+/// An HTTP request that hasn't been sent yet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Request {
+	/// The method to use, such as `"GET"`.
+	pub method: String,
+
+	/// The url to send the request to.
+	pub url: String,
+
+	/// The headers to send, in order.
+	pub headers: Vec<(String, String)>,
+
+	/// The body of the request, if there is one.
+	pub body: Option<Vec<u8>>,
+
+	/// How long to wait for the entire request before giving up. If `None`, waits forever.
+	pub timeout: Option<Duration>,
+
+	/// Whether the body of the response should be [`Bytes`] instead of [`Text`].
+	pub binary: bool
+}
+
+/// The response to an HTTP [`Request`].
+///
+/// Responses with error statuses (such as `404`) are still responses; only failing to get a response
+/// at all (such as the connection being refused) is an error.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+	status: u16,
+	reason: String,
+	url: String,
+	headers: Vec<(String, String)>,
+	body: Bytes,
+	binary: bool
+}
+
+impl Request {
+	/// Creates a new request with no headers, body, or timeout.
+	pub fn new(method: impl Into<String>, url: impl Into<String>) -> Self {
+		Self {
+			method: method.into(),
+			url: url.into(),
+			headers: Vec::new(),
+			body: None,
+			timeout: None,
+			binary: false
+		}
+	}
+
+	/// Sends the request, and reads the entire response.
+	pub fn send(&self) -> crate::Result<Response> {
+		let mut request = ureq::request(&self.method, &self.url);
+
+		for (name, value) in &self.headers {
+			request = request.set(name, value);
+		}
+
+		if let Some(timeout) = self.timeout {
+			request = request.timeout(timeout);
+		}
+
+		let result =
+			match self.body {
+				Some(ref body) => request.send_bytes(body),
+				None => request.call()
+			};
+
+		match result {
+			Ok(response) | Err(ureq::Error::Status(_, response)) => Ok(Response::read(response, self.binary)?),
+			Err(ureq::Error::Transport(err)) => Err(transport_error(err))
+		}
+	}
+}
+
+/// Converts an error that occurred while sending a request to an [`IoError`](crate::Error::IoError),
+/// or a [`ValueError`] if the url itself is invalid.
+fn transport_error(err: ureq::Transport) -> crate::Error {
+	use ureq::ErrorKind;
+
+	let source_kind = std::error::Error::source(&err)
+		.and_then(|source| source.downcast_ref::<io::Error>())
+		.map(io::Error::kind);
+
+	let kind =
+		match (err.kind(), source_kind) {
+			(ErrorKind::InvalidUrl, _) | (ErrorKind::UnknownScheme, _) | (ErrorKind::InvalidProxyUrl, _) =>
+				return ValueError::Messaged(err.to_string()).into(),
+			(_, Some(kind)) => kind,
+			(ErrorKind::Dns, None) => io::ErrorKind::NotFound,
+			(ErrorKind::ConnectionFailed, None) => io::ErrorKind::ConnectionRefused,
+			(ErrorKind::BadStatus, None) | (ErrorKind::BadHeader, None) => io::ErrorKind::InvalidData,
+			(_, None) => io::ErrorKind::Other
+		};
+
+	io::Error::new(kind, err.to_string()).into()
+}
+
+/// Gets the standard reason phrase for `status`, such as `"Not Found"` for `404`.
+///
+/// An empty string is returned for unknown statuses.
+pub fn reason_phrase(status: u16) -> &'static str {
+	match status {
+		100 => "Continue",
+		101 => "Switching Protocols",
+		200 => "OK",
+		201 => "Created",
+		202 => "Accepted",
+		204 => "No Content",
+		206 => "Partial Content",
+		301 => "Moved Permanently",
+		302 => "Found",
+		303 => "See Other",
+		304 => "Not Modified",
+		307 => "Temporary Redirect",
+		308 => "Permanent Redirect",
+		400 => "Bad Request",
+		401 => "Unauthorized",
+		403 => "Forbidden",
+		404 => "Not Found",
+		405 => "Method Not Allowed",
+		408 => "Request Timeout",
+		409 => "Conflict",
+		410 => "Gone",
+		411 => "Length Required",
+		413 => "Payload Too Large",
+		414 => "URI Too Long",
+		415 => "Unsupported Media Type",
+		422 => "Unprocessable Entity",
+		429 => "Too Many Requests",
+		431 => "Request Header Fields Too Large",
+		500 => "Internal Server Error",
+		501 => "Not Implemented",
+		502 => "Bad Gateway",
+		503 => "Service Unavailable",
+		504 => "Gateway Timeout",
+		505 => "HTTP Version Not Supported",
+		_ => ""
+	}
+}
+
+impl Response {
+	/// Creates a new response, such as for an [`HttpServer`](super::HttpServer) to send.
+	///
+	/// The reason is the [standard one](reason_phrase) for `status`. If `body` is binary, the
+	/// response's `body` attribute returns [`Bytes`].
+	pub fn new(status: u16, headers: Vec<(String, String)>, body: Vec<u8>, binary: bool) -> Self {
+		Self {
+			status,
+			reason: reason_phrase(status).to_string(),
+			url: String::new(),
+			headers,
+			body: body.into(),
+			binary
+		}
+	}
+
+	fn read(response: ureq::Response, binary: bool) -> io::Result<Self> {
+		let mut headers = Vec::<(String, String)>::new();
+
+		// `headers_names` has one entry per header line, so names that were sent multiple times show
+		// up more than once.
+		for name in response.headers_names() {
+			if !headers.iter().any(|(seen, _)| *seen == name) {
+				let value = response.all(&name).join(", ");
+				headers.push((name, value));
+			}
+		}
+
+		let status = response.status();
+		let reason = response.status_text().to_string();
+		let url = response.get_url().to_string();
+		let mut body = Vec::new();
+		response.into_reader().read_to_end(&mut body)?;
+
+		Ok(Self { status, reason, url, headers, body: body.into(), binary })
+	}
+
+	/// The status code, such as `200`.
+	#[inline]
+	pub fn status(&self) -> u16 {
+		self.status
+	}
+
+	/// The reason that was sent along with the status, such as `"Not Found"`.
+	#[inline]
+	pub fn reason(&self) -> &str {
+		&self.reason
+	}
+
+	/// All the headers, in the order they were received.
+	#[inline]
+	pub fn headers(&self) -> &[(String, String)] {
+		&self.headers
+	}
+
+	/// Checks to see if the status is a success (ie in the `2xx` range).
+	#[inline]
+	pub fn is_ok(&self) -> bool {
+		(200..300).contains(&self.status)
+	}
+
+	/// Gets the value of the header `name` (which is case-insensitive), if it was sent.
+	///
+	/// Headers that were sent multiple times have their values joined with `", "`.
+	pub fn header(&self, name: &str) -> Option<&str> {
+		self.headers.iter()
+			.find(|(key, _)| key.eq_ignore_ascii_case(name))
+			.map(|(_, value)| value.as_str())
+	}
+
+	/// Gets the body of the response.
+	#[inline]
+	pub fn body(&self) -> &Bytes {
+		&self.body
+	}
+
+	/// Checks to see if `body` returns [`Bytes`] instead of [`Text`] to Quest.
+	#[inline]
+	pub fn is_binary(&self) -> bool {
+		self.binary
+	}
+}
+
+/// Gets the entries of `map`, so that it isn't locked while they're converted.
+fn map_entries(map: &Object) -> crate::Result<Vec<(Object, Object)>> {
+	Ok(map.call_downcast::<Map>()?
+		.iter()
+		.map(|(key, value)| (key.clone(), value.clone()))
+		.collect())
+}
+
+/// Applies the options given to one of the [`Http`] functions to `request`.
+fn apply_options(request: &mut Request, options: Option<&Object>) -> crate::Result<()> {
+	let options =
+		match options {
+			Some(options) if !options.is_a::<Null>() => map_entries(options)?,
+			_ => return Ok(())
+		};
+
+	for (key, value) in options {
+		match key.call_downcast::<Text>()?.as_ref() {
+			"headers" =>
+				for (name, value) in map_entries(&value)? {
+					let name = name.call_downcast::<Text>()?.to_string();
+					let value = value.call_downcast::<Text>()?.to_string();
+					request.headers.push((name, value));
+				},
+			"body" => request.body = Some(super::bytes::to_write(&value)?),
+			"timeout" => request.timeout = Some(super::duration::to_std(&value)?),
+			"binary" => request.binary = bool::from(*value.call_downcast::<Boolean>()?),
+			other => return Err(ValueError::Messaged(format!("unknown http option {:?}", other)).into())
+		}
+	}
+
+	Ok(())
+}
+
+/// Quest methods
+impl Http {
+	/// Sends an HTTP request, returning its [`Response`].
+	///
+	/// Responses with error statuses are returned normally; use `ok?` on the response to check them.
+	/// If no response could be gotten (such as when the connection is refused or the timeout expires),
+	/// an `IoError` is raised.
+	///
+	/// # Arguments
+	/// 1. (required, `@text`) The method, such as `"PATCH"`.
+	/// 2. (required, `@text`) The url.
+	/// 3. (optional, [`Map`]) Options for the request:
+	///    - `"headers"`: A [`Map`] of header names to values.
+	///    - `"body"`: The body to send, as [`Bytes`] or `@text`.
+	///    - `"timeout"`: How long to wait for the response, as a [`Duration`](super::Duration) or
+	///      seconds. Defaults to waiting forever.
+	///    - `"binary"`: If true, the response's `body` is [`Bytes`] instead of [`Text`].
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// $headers = Map([["Content-Type", "application/json"]]);
+	/// $resp = Http.request("PATCH", "http://localhost:8080/users/1", Map([
+	///     ["headers", headers],
+	///     ["body", Json.dump(Map([["name", "sam"]]))],
+	///     ["timeout", 5]
+	/// ]));
+	/// assert(resp.ok?());
+	/// ```
+	#[instrument(name="Http::request", level="trace", skip(args), fields(?args))]
+	pub fn qs_request(_: &Object, args: Args) -> crate::Result<Object> {
+		let method = args.try_arg(0)?.call_downcast::<Text>()?.as_ref().to_uppercase();
+		let url = args.try_arg(1)?.call_downcast::<Text>()?.as_ref().to_string();
+		let mut request = Request::new(method, url);
+
+		apply_options(&mut request, args.arg(2))?;
+
+		Ok(request.send()?.into())
+	}
+
+	/// Sends a request with the given method and no body; `args` are the url and options.
+	fn send_without_body(method: &str, args: Args) -> crate::Result<Object> {
+		let url = args.try_arg(0)?.call_downcast::<Text>()?.as_ref().to_string();
+		let mut request = Request::new(method, url);
+
+		apply_options(&mut request, args.arg(1))?;
+
+		Ok(request.send()?.into())
+	}
+
+	/// Sends a request with the given method; `args` are the url, body, and options.
+	fn send_with_body(method: &str, args: Args) -> crate::Result<Object> {
+		let url = args.try_arg(0)?.call_downcast::<Text>()?.as_ref().to_string();
+		let mut request = Request::new(method, url);
+		request.body = Some(super::bytes::to_write(args.try_arg(1)?)?);
+
+		apply_options(&mut request, args.arg(2))?;
+
+		Ok(request.send()?.into())
+	}
+
+	/// Sends a `GET` request.
+	///
+	/// # Arguments
+	/// 1. (required, `@text`) The url.
+	/// 2. (optional, [`Map`]) Options for the request; see [`request`](#method.qs_request).
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// $resp = Http.get("http://localhost:8080/", Map([["timeout", 5]]));
+	/// disp(resp.status(), resp.header("content-type"), resp.body());
+	/// ```
+	#[instrument(name="Http::get", level="trace", skip(args), fields(?args))]
+	pub fn qs_get(_: &Object, args: Args) -> crate::Result<Object> {
+		Self::send_without_body("GET", args)
+	}
+
+	/// Sends a `DELETE` request.
+	///
+	/// # Arguments
+	/// 1. (required, `@text`) The url.
+	/// 2. (optional, [`Map`]) Options for the request; see [`request`](#method.qs_request).
+	#[instrument(name="Http::delete", level="trace", skip(args), fields(?args))]
+	pub fn qs_delete(_: &Object, args: Args) -> crate::Result<Object> {
+		Self::send_without_body("DELETE", args)
+	}
+
+	/// Sends a `POST` request.
+	///
+	/// # Arguments
+	/// 1. (required, `@text`) The url.
+	/// 2. (required, [`Bytes`] or `@text`) The body.
+	/// 3. (optional, [`Map`]) Options for the request; see [`request`](#method.qs_request).
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// $body = Json.dump(Map([["name", "sam"]]));
+	/// $resp = Http.post("http://localhost:8080/users", body);
+	/// assert(resp.status() == 201);
+	/// ```
+	#[instrument(name="Http::post", level="trace", skip(args), fields(?args))]
+	pub fn qs_post(_: &Object, args: Args) -> crate::Result<Object> {
+		Self::send_with_body("POST", args)
+	}
+
+	/// Sends a `PUT` request.
+	///
+	/// # Arguments
+	/// 1. (required, `@text`) The url.
+	/// 2. (required, [`Bytes`] or `@text`) The body.
+	/// 3. (optional, [`Map`]) Options for the request; see [`request`](#method.qs_request).
+	#[instrument(name="Http::put", level="trace", skip(args), fields(?args))]
+	pub fn qs_put(_: &Object, args: Args) -> crate::Result<Object> {
+		Self::send_with_body("PUT", args)
+	}
+}
+
+impl_object_type!{
+for Http [(parents super::Basic)]:
+	"Response" => const Response::mapping().clone(),
+	"request" => method Self::qs_request,
+	"get" => method Self::qs_get,
+	"post" => method Self::qs_post,
+	"put" => method Self::qs_put,
+	"delete" => method Self::qs_delete,
+}
+
+/// Quest methods
+impl Response {
+	/// Creates a new response, usually to return from an [`HttpServer`](super::HttpServer) route.
+	///
+	/// # Arguments
+	/// 1. (required, `@num`) The status code.
+	/// 2. (optional, [`Bytes`] or `@text`) The body; defaults to nothing.
+	/// 3. (optional, [`Map`]) The headers.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// $resp = Http.Response(404, "no such user");
+	/// assert(resp.reason() == "Not Found");
+	/// assert(resp.body() == "no such user");
+	/// ```
+	#[instrument(name="Http::Response::()", level="trace", skip(args), fields(?args))]
+	pub fn qs_call(_: &Object, args: Args) -> crate::Result<Object> {
+		let status = args.try_arg(0)?.call_downcast::<Number>()?.truncate();
+		let status = u16::try_from(status)
+			.ok()
+			.filter(|status| (100..1000).contains(status))
+			.ok_or_else(|| ValueError::Messaged(format!("invalid http status {}", status)))?;
+
+		let (body, binary) =
+			match args.arg(1) {
+				Some(body) if body.is_a::<Bytes>() => (super::bytes::to_write(body)?, true),
+				Some(body) if !body.is_a::<Null>() => (super::bytes::to_write(body)?, false),
+				_ => (Vec::new(), false)
+			};
+
+		let mut headers = Vec::new();
+
+		if let Some(map) = args.arg(2) {
+			for (name, value) in map_entries(map)? {
+				headers.push((name.call_downcast::<Text>()?.to_string(), value.call_downcast::<Text>()?.to_string()));
+			}
+		}
+
+		Ok(Self::new(status, headers, body, binary).into())
+	}
+
+	/// Gets the status code, such as `200`.
+	#[instrument(name="Http::Response::status", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_status(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.status.into())
+	}
+
+	/// Gets the reason that was sent along with the status, such as `"Not Found"`.
+	#[instrument(name="Http::Response::reason", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_reason(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(Text::from(this.try_downcast::<Self>()?.reason.clone()).into())
+	}
+
+	/// Checks to see if the status is a success (ie in the `2xx` range).
+	#[instrument(name="Http::Response::ok?", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_ok_q(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.is_ok().into())
+	}
+
+	/// Gets the url the response came from, which differs from the requested url if there were
+	/// redirects.
+	#[instrument(name="Http::Response::url", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_url(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(Text::from(this.try_downcast::<Self>()?.url.clone()).into())
+	}
+
+	/// Gets a [`Map`] of the headers, with lowercase names.
+	///
+	/// Headers that were sent multiple times have their values joined with `", "`.
+	#[instrument(name="Http::Response::headers", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_headers(this: &Object, _: Args) -> crate::Result<Object> {
+		let mut map = Map::new();
+
+		for (name, value) in &this.try_downcast::<Self>()?.headers {
+			// inserting `Text` keys can't fail, as hashing and comparing them never calls quest code.
+			map.insert(Text::from(name.clone()).into(), Text::from(value.clone()).into())
+				.expect("inserting a text key failed");
+		}
+
+		Ok(map.into())
+	}
+
+	/// Gets the value of a header, or [`Null`] if it wasn't sent.
+	///
+	/// # Arguments
+	/// 1. (required, `@text`) The header's name, which is case-insensitive.
+	#[instrument(name="Http::Response::header", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_header(this: &Object, args: Args) -> crate::Result<Object> {
+		let name = args.try_arg(0)?.call_downcast::<Text>()?;
+
+		Ok(this.try_downcast::<Self>()?
+			.header(name.as_ref())
+			.map(|value| Text::from(value.to_string()).into())
+			.unwrap_or_default())
+	}
+
+	/// Gets the body of the response.
+	///
+	/// If the request was made with the `"binary"` option, this is [`Bytes`]. Otherwise, it's
+	/// [`Text`], and the body must be valid UTF-8.
+	#[instrument(name="Http::Response::body", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_body(this: &Object, _: Args) -> crate::Result<Object> {
+		let this = this.try_downcast::<Self>()?;
+
+		if this.binary {
+			Ok(this.body.clone().into())
+		} else {
+			Ok(Text::try_from(&this.body)?.into())
+		}
+	}
+
+	/// Gets a debugging representation of `this`, such as `Http::Response(404 Not Found)`.
+	#[instrument(name="Http::Response::inspect", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_inspect(this: &Object, _: Args) -> crate::Result<Object> {
+		let this = this.try_downcast::<Self>()?;
+
+		Ok(Text::from(format!("Http::Response({} {})", this.status, this.reason)).into())
+	}
+}
+
+impl_object_type!{
+for Response [(parents super::Basic)]:
+	"()" => method Self::qs_call,
+	"status" => method Self::qs_status,
+	"reason" => method Self::qs_reason,
+	"ok?" => method Self::qs_ok_q,
+	"url" => method Self::qs_url,
+	"headers" => method Self::qs_headers,
+	"header" => method Self::qs_header,
+	"body" => method Self::qs_body,
+	"inspect" => method Self::qs_inspect,
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::io::{BufRead, BufReader, Write};
+	use std::net::TcpListener;
+
+	/// Serves a single request with `response`, returning the address and a handle that gives back
+	/// the request line and body that were received.
+	fn serve_once(response: &'static str) -> (String, std::thread::JoinHandle<(String, String)>) {
+		let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+		let addr = listener.local_addr().unwrap().to_string();
+
+		let handle = std::thread::spawn(move || {
+			let (stream, _) = listener.accept().unwrap();
+			let mut reader = BufReader::new(stream.try_clone().unwrap());
+			let mut request_line = String::new();
+			let mut content_length = 0;
+
+			reader.read_line(&mut request_line).unwrap();
+
+			loop {
+				let mut line = String::new();
+				reader.read_line(&mut line).unwrap();
+
+				if line.trim().is_empty() {
+					break;
+				}
+
+				if let Some(len) = line.to_lowercase().strip_prefix("content-length:") {
+					content_length = len.trim().parse().unwrap();
+				}
+			}
+
+			let mut body = vec![0; content_length];
+			reader.read_exact(&mut body).unwrap();
+			(&stream).write_all(response.as_bytes()).unwrap();
+
+			(request_line.trim_end().to_string(), String::from_utf8(body).unwrap())
+		});
+
+		(addr, handle)
+	}
+
+	#[test]
+	fn send_and_receive() {
+		let (addr, handle) = serve_once(
+			"HTTP/1.1 404 Not Found\r\nX-Test: a\r\nx-test: b\r\nContent-Length: 5\r\n\r\nnope!");
+
+		let mut request = Request::new("POST", format!("http://{}/path?q=1", addr));
+		request.body = Some(b"hello".to_vec());
+		request.timeout = Some(Duration::from_secs(5));
+
+		let response = request.send().unwrap();
+		assert_eq!(handle.join().unwrap(), ("POST /path?q=1 HTTP/1.1".to_string(), "hello".to_string()));
+
+		assert_eq!(response.status(), 404);
+		assert!(!response.is_ok());
+		assert_eq!(response.header("X-TEST"), Some("a, b"));
+		assert_eq!(response.body(), &Bytes::from(b"nope!".to_vec()));
+	}
+
+	#[test]
+	fn connection_refused() {
+		// bind and immediately drop a listener so that nothing's listening on the port.
+		let addr = TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap();
+
+		match Request::new("GET", format!("http://{}/", addr)).send() {
+			Err(crate::Error::IoError(err)) => assert_eq!(err.kind(), io::ErrorKind::ConnectionRefused),
+			other => panic!("expected an io error, got {:?}", other)
+		}
+
+		assert!(matches!(Request::new("GET", "not a url").send(), Err(crate::Error::ValueError(_))));
+	}
+}
@@ -0,0 +1,897 @@
+use crate::{Object, Args, Literal, Result};
+use crate::error::ValueError;
+use crate::types::{ObjectType, Text, Bytes, Null, Map, Kernel, RustClosure, TcpServer};
+use crate::types::http::Response;
+
+use parking_lot::{Mutex, RwLock};
+use std::convert::TryFrom;
+use std::fmt::{self, Display, Formatter};
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpStream};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{instrument, error};
+
+/// The longest a request line or header line can be.
+const MAX_LINE_LEN: usize = 8 * 1024;
+
+/// The most headers a request can have.
+const MAX_HEADERS: usize = 100;
+
+/// The largest a request's body can be.
+const MAX_BODY_LEN: usize = 16 * 1024 * 1024;
+
+/// How long to wait for a client to send its request before giving up on it.
+const READ_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// A minimal HTTP/1.1 server, which routes requests to Quest blocks.
+///
+/// Each connection is served on its own thread, via `Kernel::spawn`, and is closed after a single
+/// response is sent.
+#[derive(Debug, Clone)]
+pub struct HttpServer(Arc<Inner>);
+
+#[derive(Debug)]
+struct Inner {
+	server: TcpServer,
+	routes: RwLock<Vec<Route>>,
+	binary: bool
+}
+
+#[derive(Debug, Clone)]
+struct Route {
+	// `None` matches any method.
+	method: Option<String>,
+	pattern: Pattern,
+	handler: Object
+}
+
+/// A pattern that request paths are matched against, such as `/users/:id/*rest`.
+///
+/// Each segment is either matched literally, or is a parameter: `:name` matches any non-empty
+/// segment, and `*name` (which must be last) matches the rest of the path, even if it's empty.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pattern(Vec<Segment>);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+	Literal(String),
+	Param(String),
+	Rest(String)
+}
+
+impl Pattern {
+	/// Parses a pattern, which must start with a `/`.
+	pub fn parse(pattern: &str) -> std::result::Result<Self, ValueError> {
+		if !pattern.starts_with('/') {
+			return Err(ValueError::Messaged(format!("route patterns must start with '/': {:?}", pattern)));
+		}
+
+		let mut segments = pattern[1..].split('/').peekable();
+		let mut parsed = Vec::new();
+
+		while let Some(segment) = segments.next() {
+			if let Some(name) = segment.strip_prefix(':') {
+				parsed.push(Segment::Param(name.to_string()));
+			} else if let Some(name) = segment.strip_prefix('*') {
+				if segments.peek().is_some() {
+					return Err(ValueError::Messaged(format!("'*' must be the last segment: {:?}", pattern)));
+				}
+
+				parsed.push(Segment::Rest(name.to_string()));
+			} else {
+				parsed.push(Segment::Literal(percent_decode(segment, false)));
+			}
+		}
+
+		Ok(Self(parsed))
+	}
+
+	/// Matches `path` (which hasn't been percent-decoded yet) against `self`, returning the decoded
+	/// parameters if it matches.
+	pub fn matches(&self, path: &str) -> Option<Vec<(String, String)>> {
+		let mut segments = path.strip_prefix('/')?.split('/');
+		let mut params = Vec::new();
+
+		for pattern in &self.0 {
+			match pattern {
+				Segment::Rest(name) => {
+					let rest = segments.by_ref().collect::<Vec<_>>().join("/");
+					params.push((name.clone(), percent_decode(&rest, false)));
+				},
+				Segment::Param(name) => match segments.next() {
+					Some(segment) if !segment.is_empty() => params.push((name.clone(), percent_decode(segment, false))),
+					_ => return None
+				},
+				Segment::Literal(literal) =>
+					if segments.next().map(|segment| percent_decode(segment, false)).as_ref() != Some(literal) {
+						return None
+					}
+			}
+		}
+
+		if segments.next().is_none() {
+			Some(params)
+		} else {
+			None
+		}
+	}
+}
+
+/// Decodes `%XX` escapes in `input` (and `+`s if `plus_as_space` is set); invalid UTF-8 is replaced.
+fn percent_decode(input: &str, plus_as_space: bool) -> String {
+	let input = input.as_bytes();
+	let mut decoded = Vec::with_capacity(input.len());
+	let mut idx = 0;
+
+	while idx < input.len() {
+		let hex = input.get(idx + 1..idx + 3)
+			.and_then(|hex| std::str::from_utf8(hex).ok())
+			.and_then(|hex| u8::from_str_radix(hex, 16).ok());
+
+		match (input[idx], hex) {
+			(b'%', Some(hex)) => {
+				decoded.push(hex);
+				idx += 2;
+			},
+			(b'+', _) if plus_as_space => decoded.push(b' '),
+			(other, _) => decoded.push(other)
+		}
+
+		idx += 1;
+	}
+
+	String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// A request that was sent to an [`HttpServer`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Request {
+	method: String,
+	// the path, before it's been percent-decoded.
+	raw_path: String,
+	query: Vec<(String, String)>,
+	headers: Vec<(String, String)>,
+	body: Vec<u8>,
+	params: Vec<(String, String)>,
+	binary: bool
+}
+
+/// Why a request couldn't be read.
+#[derive(Debug)]
+enum ReadError {
+	/// The connection failed.
+	Io(io::Error),
+
+	/// The request was malformed, and the given status should be sent back.
+	Invalid(u16)
+}
+
+impl From<io::Error> for ReadError {
+	#[inline]
+	fn from(err: io::Error) -> Self {
+		Self::Io(err)
+	}
+}
+
+/// Reads a single line, without its line ending.
+///
+/// `Ok(None)` is returned if the stream ended before anything was read.
+fn read_line<R: BufRead>(reader: &mut R, too_long: u16) -> std::result::Result<Option<String>, ReadError> {
+	let mut line = Vec::new();
+
+	reader.take(MAX_LINE_LEN as u64 + 1).read_until(b'\n', &mut line)?;
+
+	if line.is_empty() {
+		return Ok(None);
+	} else if line.pop() != Some(b'\n') {
+		// either the line is too long or the stream ended partway through it.
+		return Err(if line.len() >= MAX_LINE_LEN { ReadError::Invalid(too_long) } else { ReadError::Invalid(400) });
+	}
+
+	if line.last() == Some(&b'\r') {
+		line.pop();
+	}
+
+	String::from_utf8(line).map(Some).map_err(|_| ReadError::Invalid(400))
+}
+
+impl Request {
+	/// Reads a request from `reader`.
+	///
+	/// If the client expects a `100 Continue` before sending the body, it's written to `writer`.
+	/// `Ok(None)` is returned if the connection was closed without sending anything.
+	fn read<R: BufRead, W: Write>(reader: &mut R, writer: &mut W, binary: bool)
+		-> std::result::Result<Option<Self>, ReadError>
+	{
+		let request_line =
+			match read_line(reader, 414)? {
+				Some(line) => line,
+				None => return Ok(None)
+			};
+
+		let mut parts = request_line.split(' ');
+		let (method, target, version) =
+			match (parts.next(), parts.next(), parts.next(), parts.next()) {
+				(Some(method), Some(target), Some(version), None) if !method.is_empty() => (method, target, version),
+				_ => return Err(ReadError::Invalid(400))
+			};
+
+		if !version.starts_with("HTTP/1.") {
+			return Err(ReadError::Invalid(505));
+		}
+
+		let (raw_path, query) =
+			match target.find('?') {
+				Some(idx) => (&target[..idx], parse_query(&target[idx + 1..])),
+				None => (target, Vec::new())
+			};
+
+		if !raw_path.starts_with('/') {
+			return Err(ReadError::Invalid(400));
+		}
+
+		let mut headers = Vec::new();
+
+		loop {
+			let line = read_line(reader, 431)?.ok_or(ReadError::Invalid(400))?;
+
+			if line.is_empty() {
+				break;
+			} else if headers.len() == MAX_HEADERS {
+				return Err(ReadError::Invalid(431));
+			}
+
+			let idx = line.find(':').ok_or(ReadError::Invalid(400))?;
+			headers.push((line[..idx].trim().to_ascii_lowercase(), line[idx + 1..].trim().to_string()));
+		}
+
+		let mut request = Self {
+			method: method.to_string(),
+			raw_path: raw_path.to_string(),
+			query,
+			headers,
+			body: Vec::new(),
+			params: Vec::new(),
+			binary
+		};
+
+		let chunked = matches!(request.header("transfer-encoding"),
+			Some(encoding) if encoding.to_ascii_lowercase().contains("chunked"));
+		let content_length = request.header("content-length")
+			.map(|len| len.parse::<usize>().map_err(|_| ReadError::Invalid(400)))
+			.transpose()?;
+		let expects_continue = matches!(request.header("expect"),
+			Some(expect) if expect.eq_ignore_ascii_case("100-continue"));
+
+		if expects_continue && (chunked || content_length.unwrap_or(0) != 0) {
+			writer.write_all(b"HTTP/1.1 100 Continue\r\n\r\n")?;
+			writer.flush()?;
+		}
+
+		if chunked {
+			request.body = read_chunked(reader)?;
+		} else if let Some(len) = content_length {
+			if len > MAX_BODY_LEN {
+				return Err(ReadError::Invalid(413));
+			}
+
+			request.body = vec![0; len];
+			reader.read_exact(&mut request.body)?;
+		}
+
+		Ok(Some(request))
+	}
+
+	/// Gets the value of the header `name` (which is case-insensitive), if it was sent.
+	pub fn header(&self, name: &str) -> Option<&str> {
+		self.headers.iter()
+			.find(|(key, _)| key.eq_ignore_ascii_case(name))
+			.map(|(_, value)| value.as_str())
+	}
+
+	/// Gets the request's method, such as `"GET"`.
+	#[inline]
+	pub fn method(&self) -> &str {
+		&self.method
+	}
+
+	/// Gets the request's path, percent-decoded.
+	#[inline]
+	pub fn path(&self) -> String {
+		percent_decode(&self.raw_path, false)
+	}
+
+	/// Gets the request's body.
+	#[inline]
+	pub fn body(&self) -> &[u8] {
+		&self.body
+	}
+}
+
+/// Reads a body sent with `Transfer-Encoding: chunked`, ignoring any trailers.
+fn read_chunked<R: BufRead>(reader: &mut R) -> std::result::Result<Vec<u8>, ReadError> {
+	let mut body = Vec::new();
+
+	loop {
+		let line = read_line(reader, 400)?.ok_or(ReadError::Invalid(400))?;
+		let size = line.split(';').next().unwrap_or_default().trim();
+		let size = usize::from_str_radix(size, 16).map_err(|_| ReadError::Invalid(400))?;
+
+		if size == 0 {
+			break;
+		} else if body.len() + size > MAX_BODY_LEN {
+			return Err(ReadError::Invalid(413));
+		}
+
+		let start = body.len();
+		body.resize(start + size, 0);
+		reader.read_exact(&mut body[start..])?;
+
+		if read_line(reader, 400)? != Some(String::new()) {
+			return Err(ReadError::Invalid(400));
+		}
+	}
+
+	while !read_line(reader, 431)?.ok_or(ReadError::Invalid(400))?.is_empty() {
+		// skip trailers
+	}
+
+	Ok(body)
+}
+
+/// Parses a query string such as `a=1&b=x+y` into its decoded pairs.
+fn parse_query(query: &str) -> Vec<(String, String)> {
+	query.split('&')
+		.filter(|pair| !pair.is_empty())
+		.map(|pair| {
+			let mut parts = pair.splitn(2, '=');
+			let key = parts.next().unwrap_or_default();
+			let value = parts.next().unwrap_or_default();
+
+			(percent_decode(key, true), percent_decode(value, true))
+		})
+		.collect()
+}
+
+impl Display for Request {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		write!(f, "{} {}", self.method, self.raw_path)
+	}
+}
+
+/// Converts `pairs` to a [`Map`] of [`Text`] to [`Text`].
+fn pairs_to_map(pairs: &[(String, String)]) -> Object {
+	let mut map = Map::new();
+
+	for (key, value) in pairs {
+		// inserting `Text` keys can't fail, as hashing and comparing them never calls quest code.
+		map.insert(Text::from(key.clone()).into(), Text::from(value.clone()).into())
+			.expect("inserting a text key failed");
+	}
+
+	map.into()
+}
+
+/// Converts what a route's block returned into the response to send.
+///
+/// [`Response`](crate::types::http::Response)s are sent as-is, [`Null`] becomes a `204`, and
+/// anything else is sent as the body of a `200`.
+fn to_response(obj: &Object) -> Result<Response> {
+	if let Some(response) = obj.downcast::<Response>() {
+		Ok(response.clone())
+	} else if obj.is_a::<Null>() {
+		Ok(Response::new(204, Vec::new(), Vec::new(), false))
+	} else if let Some(bytes) = obj.downcast::<Bytes>() {
+		Ok(Response::new(200, Vec::new(), bytes.as_ref().to_vec(), true))
+	} else {
+		Ok(Response::new(200, Vec::new(), obj.call_downcast::<Text>()?.as_ref().as_bytes().to_vec(), false))
+	}
+}
+
+/// Writes `response` to `writer`, followed by the body if `include_body` is set.
+fn write_response<W: Write>(writer: &mut W, response: &Response, include_body: bool) -> io::Result<()> {
+	let mut head = format!("HTTP/1.1 {} {}\r\n", response.status(), response.reason());
+	let mut has_content_type = false;
+
+	for (name, value) in response.headers() {
+		// we always send these ourselves.
+		if ["content-length", "connection", "transfer-encoding"].iter().any(|h| name.eq_ignore_ascii_case(h)) {
+			continue;
+		}
+
+		has_content_type |= name.eq_ignore_ascii_case("content-type");
+		head.push_str(&format!("{}: {}\r\n", name, value));
+	}
+
+	if !has_content_type && !response.body().is_empty() {
+		head.push_str("Content-Type: ");
+		head.push_str(if response.is_binary() { "application/octet-stream" } else { "text/plain; charset=utf-8" });
+		head.push_str("\r\n");
+	}
+
+	head.push_str(&format!("Content-Length: {}\r\nConnection: close\r\n\r\n", response.body().len()));
+
+	writer.write_all(head.as_bytes())?;
+
+	if include_body {
+		writer.write_all(response.body().as_ref())?;
+	}
+
+	writer.flush()
+}
+
+impl HttpServer {
+	/// Start listening on `addr`. If `binary` is set, request bodies are [`Bytes`] instead of [`Text`].
+	pub fn bind<A: std::net::ToSocketAddrs>(addr: A, binary: bool) -> io::Result<Self> {
+		Ok(Self(Arc::new(Inner {
+			server: TcpServer::bind(addr, false)?,
+			routes: RwLock::new(Vec::new()),
+			binary
+		})))
+	}
+
+	/// Get the address the server is listening on.
+	#[inline]
+	pub fn local_addr(&self) -> SocketAddr {
+		self.0.server.local_addr()
+	}
+
+	/// Checks to see if the server has been shut down.
+	#[inline]
+	pub fn is_closed(&self) -> bool {
+		self.0.server.is_closed()
+	}
+
+	/// Stop accepting connections; connections that are already being served aren't affected.
+	#[inline]
+	pub fn shutdown(&self) {
+		self.0.server.shutdown()
+	}
+
+	/// Adds a route, which calls `handler` with requests that match `method` (or any method, if it's
+	/// `None`) and `pattern`.
+	///
+	/// Routes are checked in the order they're added.
+	pub fn route(&self, method: Option<&str>, pattern: Pattern, handler: Object) {
+		self.0.routes.write().push(Route {
+			method: method.map(str::to_ascii_uppercase),
+			pattern,
+			handler
+		});
+	}
+
+	/// Finds the route for `request`, and calls it.
+	fn respond(&self, mut request: Request) -> Result<Response> {
+		let mut allowed = Vec::new();
+		let mut found = None;
+
+		for route in self.0.routes.read().iter() {
+			let params =
+				match route.pattern.matches(&request.raw_path) {
+					Some(params) => params,
+					None => continue
+				};
+
+			let matches_method =
+				match route.method {
+					None => true,
+					Some(ref method) => *method == request.method || (method == "GET" && request.method == "HEAD")
+				};
+
+			if matches_method {
+				found = Some((route.handler.clone(), params));
+				break;
+			}
+
+			allowed.extend(route.method.clone());
+		}
+
+		match found {
+			Some((handler, params)) => {
+				request.params = params;
+				to_response(&handler.call_attr_lit(&Literal::CALL, &[&request.into()])?)
+			},
+			None if allowed.is_empty() => Ok(Response::new(404, Vec::new(), b"not found".to_vec(), false)),
+			None => {
+				allowed.sort();
+				allowed.dedup();
+				let headers = vec![("Allow".to_string(), allowed.join(", "))];
+
+				Ok(Response::new(405, headers, b"method not allowed".to_vec(), false))
+			}
+		}
+	}
+
+	/// Reads a single request from `stream`, and sends the response.
+	fn serve_connection(&self, stream: TcpStream) -> io::Result<()> {
+		stream.set_read_timeout(Some(READ_TIMEOUT))?;
+
+		let mut reader = BufReader::new(&stream);
+		let mut writer = &stream;
+
+		let request =
+			match Request::read(&mut reader, &mut writer, self.0.binary) {
+				Ok(Some(request)) => request,
+				Ok(None) => return Ok(()),
+				Err(ReadError::Invalid(status)) => {
+					let response = Response::new(status, Vec::new(), Vec::new(), false);
+					return write_response(&mut writer, &response, true);
+				},
+				Err(ReadError::Io(err)) => return Err(err)
+			};
+
+		let include_body = request.method != "HEAD";
+		let description = request.to_string();
+
+		let response = self.respond(request).unwrap_or_else(|err| {
+			error!(request=%description, %err, "error handling request");
+			Response::new(500, Vec::new(), b"internal server error".to_vec(), false)
+		});
+
+		write_response(&mut writer, &response, include_body)?;
+		stream.shutdown(std::net::Shutdown::Write).or_else(|err|
+			if err.kind() == io::ErrorKind::NotConnected { Ok(()) } else { Err(err) })
+	}
+
+	/// Accept connections until the server is shut down, serving each one on a new thread.
+	pub fn run(&self) -> Result<()> {
+		let spawn = Kernel::mapping().get_attr_lit("spawn")?;
+
+		while let Some(stream) = self.0.server.accept_stream(None)? {
+			let server = self.clone();
+			let stream = Mutex::new(Some(stream));
+
+			let serve: Object = RustClosure::new(move |_| {
+				if let Some(stream) = stream.lock().take() {
+					server.serve_connection(stream)?;
+				}
+
+				Ok(Null.into())
+			}).into();
+
+			spawn.call_attr_lit(&Literal::CALL, &[&serve])?;
+		}
+
+		Ok(())
+	}
+}
+
+/// Quest methods
+impl HttpServer {
+	/// Listen for HTTP requests on the address given by the first argument.
+	///
+	/// Routes are added with [`route`](#method.qs_route) (or `get`, `post`, `put`, and `delete`),
+	/// and then requests are served with [`run`](#method.qs_run). Each route's block is called with
+	/// an `HttpServer::Request`, and can return:
+	/// - an `Http::Response`, which is sent as-is;
+	/// - [`Null`], which sends a `204 No Content`;
+	/// - anything else, which is sent as the body of a `200 OK`.
+	///
+	/// Requests that don't match any route get a `404`, and errors raised by a route give a `500`.
+	///
+	/// # Arguments
+	/// 1. (required, `@text`) The address to listen on, such as `"127.0.0.1:8080"`. Use port `0` to
+	///    have one picked for you.
+	/// 2. (optional, `@text`) The mode; `"b"` means request bodies are [`Bytes`] instead of [`Text`].
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// $server = HttpServer("127.0.0.1:8080");
+	///
+	/// server.get("/hello/:name", {
+	///     "hello, " + _0.params().get("name")
+	/// });
+	///
+	/// server.post("/echo", {
+	///     $headers = Map([["Content-Type", "application/json"]]);
+	///     Http.Response(201, _0.body(), headers)
+	/// });
+	///
+	/// server.run();
+	/// ```
+	#[instrument(name="HttpServer::()", level="trace")]
+	pub fn qs_call(_: &Object, args: Args) -> Result<Object> {
+		let binary = super::tcp::parse_mode(args.arg(1))?;
+		let addr = args.try_arg(0)?.call_downcast::<Text>()?;
+
+		Ok(Self::bind(addr.as_ref(), binary)?.into())
+	}
+
+	/// Add a route, returning `this`.
+	///
+	/// # Arguments
+	/// 1. (required, `@text`) The method to match, such as `"PATCH"`; `"*"` matches any method.
+	///    Routes for `GET` also match `HEAD`.
+	/// 2. (required, `@text`) The path pattern to match. Segments like `:id` match any non-empty
+	///    segment, and a final `*rest` segment matches the rest of the path; both are passed to the
+	///    block as `params`.
+	/// 3. (required, callable) The block to call with each matching request.
+	#[instrument(name="HttpServer::route", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_route(this: &Object, args: Args) -> Result<Object> {
+		let method = args.try_arg(0)?.call_downcast::<Text>()?.to_string();
+		let pattern = Pattern::parse(args.try_arg(1)?.call_downcast::<Text>()?.as_ref())?;
+		let handler = args.try_arg(2)?.clone();
+
+		this.try_downcast::<Self>()?.route(Some(method.as_str()).filter(|&method| method != "*"), pattern, handler);
+
+		Ok(this.clone())
+	}
+
+	/// Add a route for the given method; `args` are the pattern and block.
+	fn route_method(this: &Object, method: &str, args: Args) -> Result<Object> {
+		let pattern = Pattern::parse(args.try_arg(0)?.call_downcast::<Text>()?.as_ref())?;
+		let handler = args.try_arg(1)?.clone();
+
+		this.try_downcast::<Self>()?.route(Some(method), pattern, handler);
+
+		Ok(this.clone())
+	}
+
+	/// Add a route for `GET` (and `HEAD`) requests; see [`route`](#method.qs_route).
+	#[instrument(name="HttpServer::get", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_get(this: &Object, args: Args) -> Result<Object> {
+		Self::route_method(this, "GET", args)
+	}
+
+	/// Add a route for `POST` requests; see [`route`](#method.qs_route).
+	#[instrument(name="HttpServer::post", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_post(this: &Object, args: Args) -> Result<Object> {
+		Self::route_method(this, "POST", args)
+	}
+
+	/// Add a route for `PUT` requests; see [`route`](#method.qs_route).
+	#[instrument(name="HttpServer::put", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_put(this: &Object, args: Args) -> Result<Object> {
+		Self::route_method(this, "PUT", args)
+	}
+
+	/// Add a route for `DELETE` requests; see [`route`](#method.qs_route).
+	#[instrument(name="HttpServer::delete", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_delete(this: &Object, args: Args) -> Result<Object> {
+		Self::route_method(this, "DELETE", args)
+	}
+
+	/// Serve requests until the server is shut down, returning `this`.
+	#[instrument(name="HttpServer::run", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_run(this: &Object, _: Args) -> Result<Object> {
+		this.try_downcast::<Self>()?.clone().run()?;
+
+		Ok(this.clone())
+	}
+
+	/// Get the address `this` is listening on, such as `"127.0.0.1:8080"`.
+	#[instrument(name="HttpServer::local_addr", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_local_addr(this: &Object, _: Args) -> Result<Object> {
+		Ok(Text::from(this.try_downcast::<Self>()?.local_addr().to_string()).into())
+	}
+
+	/// Get the port `this` is listening on.
+	#[instrument(name="HttpServer::port", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_port(this: &Object, _: Args) -> Result<Object> {
+		Ok(this.try_downcast::<Self>()?.local_addr().port().into())
+	}
+
+	/// Stop accepting connections, which makes [`run`](#method.qs_run) return.
+	#[instrument(name="HttpServer::shutdown", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_shutdown(this: &Object, _: Args) -> Result<Object> {
+		this.try_downcast::<Self>()?.shutdown();
+
+		Ok(this.clone())
+	}
+
+	/// Checks to see if `this` has been shut down.
+	#[instrument(name="HttpServer::closed?", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_closed_q(this: &Object, _: Args) -> Result<Object> {
+		Ok(this.try_downcast::<Self>()?.is_closed().into())
+	}
+}
+
+impl_object_type!{
+for HttpServer [(parents super::Basic)]:
+	"Request" => const Request::mapping().clone(),
+	"()" => method Self::qs_call,
+	"route" => method Self::qs_route,
+	"get" => method Self::qs_get,
+	"post" => method Self::qs_post,
+	"put" => method Self::qs_put,
+	"delete" => method Self::qs_delete,
+	"run" => method Self::qs_run,
+	"local_addr" => method Self::qs_local_addr,
+	"port" => method Self::qs_port,
+	"shutdown" => method Self::qs_shutdown,
+	"closed?" => method Self::qs_closed_q,
+}
+
+/// Quest methods
+impl Request {
+	/// Gets the method, such as `"GET"`.
+	#[instrument(name="HttpServer::Request::method", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_method(this: &Object, _: Args) -> Result<Object> {
+		Ok(Text::from(this.try_downcast::<Self>()?.method.clone()).into())
+	}
+
+	/// Gets the path, without the query string.
+	#[instrument(name="HttpServer::Request::path", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_path(this: &Object, _: Args) -> Result<Object> {
+		Ok(Text::from(this.try_downcast::<Self>()?.path()).into())
+	}
+
+	/// Gets a [`Map`] of the query string's parameters.
+	///
+	/// If a parameter is given more than once, the last value is used.
+	#[instrument(name="HttpServer::Request::query", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_query(this: &Object, _: Args) -> Result<Object> {
+		Ok(pairs_to_map(&this.try_downcast::<Self>()?.query))
+	}
+
+	/// Gets a [`Map`] of the parameters matched by the route's pattern.
+	#[instrument(name="HttpServer::Request::params", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_params(this: &Object, _: Args) -> Result<Object> {
+		Ok(pairs_to_map(&this.try_downcast::<Self>()?.params))
+	}
+
+	/// Gets a [`Map`] of the headers, with lowercase names.
+	///
+	/// If a header is sent more than once, the last value is used.
+	#[instrument(name="HttpServer::Request::headers", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_headers(this: &Object, _: Args) -> Result<Object> {
+		Ok(pairs_to_map(&this.try_downcast::<Self>()?.headers))
+	}
+
+	/// Gets the value of a header, or [`Null`] if it wasn't sent.
+	///
+	/// # Arguments
+	/// 1. (required, `@text`) The header's name, which is case-insensitive.
+	#[instrument(name="HttpServer::Request::header", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_header(this: &Object, args: Args) -> Result<Object> {
+		let name = args.try_arg(0)?.call_downcast::<Text>()?;
+
+		Ok(this.try_downcast::<Self>()?
+			.header(name.as_ref())
+			.map(|value| Text::from(value.to_string()).into())
+			.unwrap_or_default())
+	}
+
+	/// Gets the body of the request.
+	///
+	/// If the server is in binary mode, this is [`Bytes`]. Otherwise, it's [`Text`], and the body must
+	/// be valid UTF-8.
+	#[instrument(name="HttpServer::Request::body", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_body(this: &Object, _: Args) -> Result<Object> {
+		let this = this.try_downcast::<Self>()?;
+		let bytes = Bytes::from(this.body.clone());
+
+		if this.binary {
+			Ok(bytes.into())
+		} else {
+			Ok(Text::try_from(&bytes)?.into())
+		}
+	}
+
+	/// Gets a debugging representation of `this`, such as `HttpServer::Request(GET /)`.
+	#[instrument(name="HttpServer::Request::inspect", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_inspect(this: &Object, _: Args) -> Result<Object> {
+		Ok(Text::from(format!("HttpServer::Request({})", *this.try_downcast::<Self>()?)).into())
+	}
+}
+
+impl_object_type!{
+for Request [(parents super::Basic)]:
+	"method" => method Self::qs_method,
+	"path" => method Self::qs_path,
+	"query" => method Self::qs_query,
+	"params" => method Self::qs_params,
+	"headers" => method Self::qs_headers,
+	"header" => method Self::qs_header,
+	"body" => method Self::qs_body,
+	"inspect" => method Self::qs_inspect,
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn patterns() {
+		let pattern = Pattern::parse("/users/:id/files/*path").unwrap();
+
+		assert_eq!(pattern.matches("/users/12/files/a/b%20c"), Some(vec![
+			("id".to_string(), "12".to_string()),
+			("path".to_string(), "a/b c".to_string())
+		]));
+		assert_eq!(pattern.matches("/users/12/files/"), Some(vec![
+			("id".to_string(), "12".to_string()),
+			("path".to_string(), String::new())
+		]));
+		assert_eq!(pattern.matches("/users//files/x"), None);
+		assert_eq!(pattern.matches("/users/12/other/x"), None);
+
+		assert_eq!(Pattern::parse("/").unwrap().matches("/"), Some(vec![]));
+		assert_eq!(Pattern::parse("/a").unwrap().matches("/a/b"), None);
+		assert!(Pattern::parse("/*rest/x").is_err());
+		assert!(Pattern::parse("a").is_err());
+	}
+
+	#[test]
+	fn read_requests() {
+		let raw = b"POST /echo?a=1&b=x+y%21 HTTP/1.1\r\nHost: x\r\nTransfer-Encoding: chunked\r\n\
+			Expect: 100-continue\r\n\r\n3;ext\r\nabc\r\n2\r\nde\r\n0\r\nTrailer: 1\r\n\r\n";
+		let mut written = Vec::new();
+		let request = Request::read(&mut &raw[..], &mut written, false).unwrap().unwrap();
+
+		assert_eq!(written, b"HTTP/1.1 100 Continue\r\n\r\n");
+		assert_eq!(request.method(), "POST");
+		assert_eq!(request.path(), "/echo");
+		assert_eq!(request.query, vec![("a".to_string(), "1".to_string()), ("b".to_string(), "x y!".to_string())]);
+		assert_eq!(request.header("HOST"), Some("x"));
+		assert_eq!(request.body(), b"abcde");
+
+		let raw = b"GET / HTTP/1.0\r\nContent-Length: 2\r\n\r\nhi";
+		let request = Request::read(&mut &raw[..], &mut io::sink(), false).unwrap().unwrap();
+		assert_eq!(request.body(), b"hi");
+
+		assert!(Request::read(&mut &b""[..], &mut io::sink(), false).unwrap().is_none());
+		assert!(matches!(Request::read(&mut &b"GET /\r\n\r\n"[..], &mut io::sink(), false), Err(ReadError::Invalid(400))));
+		assert!(matches!(Request::read(&mut &b"GET / SPDY/3\r\n\r\n"[..], &mut io::sink(), false), Err(ReadError::Invalid(505))));
+	}
+
+	#[test]
+	fn serve_requests() {
+		crate::init();
+
+		let server = HttpServer::bind("127.0.0.1:0", false).unwrap();
+		let handler = RustClosure::new(|args| {
+			let request = args.try_arg(0)?.try_downcast::<Request>()?.clone();
+			let body = format!("{} {:?} {}", request.method, request.params, String::from_utf8_lossy(&request.body));
+
+			Ok(Response::new(201, vec![("X-Test".to_string(), "1".to_string())], body.into_bytes(), false).into())
+		});
+
+		server.route(Some("post"), Pattern::parse("/items/:id").unwrap(), handler.into());
+
+		let running = server.clone();
+		let runner = std::thread::spawn(move || running.run().unwrap());
+		let url = format!("http://{}", server.local_addr());
+
+		let mut request = crate::types::http::Request::new("POST", format!("{}/items/7", url));
+		request.body = Some(b"hi".to_vec());
+		let response = request.send().unwrap();
+
+		assert_eq!(response.status(), 201);
+		assert_eq!(response.header("x-test"), Some("1"));
+		assert_eq!(response.body().as_ref(), br#"POST [("id", "7")] hi"#);
+
+		let response = crate::types::http::Request::new("GET", format!("{}/items/7", url)).send().unwrap();
+		assert_eq!(response.status(), 405);
+		assert_eq!(response.header("allow"), Some("POST"));
+
+		let response = crate::types::http::Request::new("GET", format!("{}/nope", url)).send().unwrap();
+		assert_eq!(response.status(), 404);
+
+		server.shutdown();
+		runner.join().unwrap();
+	}
+
+	#[test]
+	fn method_not_allowed() {
+		crate::init();
+
+		let server = HttpServer::bind("127.0.0.1:0", false).unwrap();
+		let handler = Object::from(RustClosure::new(|_| Ok(Null.into())));
+
+		for method in &["put", "post", "put"] {
+			server.route(Some(method), Pattern::parse("/items").unwrap(), handler.clone());
+		}
+
+		let request = Request::read(&mut &b"DELETE /items HTTP/1.1\r\n\r\n"[..], &mut io::sink(), false)
+			.unwrap()
+			.unwrap();
+		let response = server.respond(request).unwrap();
+
+		assert_eq!(response.status(), 405);
+		assert_eq!(response.header("allow"), Some("POST, PUT"));
+
+		server.shutdown();
+	}
+}
@@ -1,6 +1,8 @@
 mod file;
+pub mod dir;
 
 pub use file::File;
+pub use dir::Dir;
 
 use crate::{Object, Args, Literal};
 use crate::types::Null;
@@ -10,17 +12,26 @@ use tracing::instrument;
 pub struct Io;
 
 impl Io {
+	/// Iterate over each line, or each chunk ending in a delimiter, in the io.
+	///
+	/// Without a delimiter, this iterates through the [iteration protocol](crate::types::Iter::from_object),
+	/// so types that override `next` or `@iter` are iterated over the same way as everywhere else.
 	#[instrument(name="Io::each", level="trace", skip(this, args), fields(self=?this, args=?args))]
 	pub fn qs_each(this: &Object, args: Args) -> crate::Result<Object> {
-		let (delim, block) = 
-			if args.len() == 1 {
-				("\n".into(), args.arg(0).unwrap())
-			} else {
-				(args.try_arg(0)?.clone(), args.try_arg(1)?)
-			};
+		if args.len() == 1 {
+			let block = args.try_arg(0)?;
+
+			for line in crate::types::Iter::from_object(this)? {
+				block.call_attr_lit(&Literal::CALL, &[&line?])?;
+			}
+
+			return Ok(this.clone());
+		}
+
+		let (delim, block) = (args.try_arg(0)?, args.try_arg(1)?);
 
 		loop {
-			let line = this.call_attr_lit("read", &[&delim])?;
+			let line = this.call_attr_lit("read", &[delim])?;
 
 			if line.is_a::<Null>() {
 				break
@@ -31,13 +42,34 @@ impl Io {
 
 		Ok(this.clone())
 	}
+
+	/// Reads the next line, returning [`StopIteration`](crate::types::StopIteration) at the end.
+	#[instrument(name="Io::next", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_next(this: &Object, _: Args) -> crate::Result<Object> {
+		let line = this.call_attr_lit("read", &[&"\n".into()])?;
+
+		if line.is_a::<Null>() {
+			Ok(crate::types::StopIteration.into())
+		} else {
+			Ok(line)
+		}
+	}
+
+	/// Ios are their own iterators, as they respond to `next`.
+	#[instrument(name="Io::@iter", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_at_iter(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.clone())
+	}
 }
 
 impl_object_type!{
 for Io [(parents super::Iterable)]:
 	"File" => const file::File::mapping().clone(),
+	"Dir" => const dir::Dir::mapping().clone(),
 	"Stdin" => const file::File::from_fd(0, true, false).expect("cant create stdin"),
 	"Stdout" => const file::File::from_fd(1, false, true).expect("cant create stdout"),
 	"Stderr" => const file::File::from_fd(2, false, true).expect("cant create stdout"),
 	"each" => method Self::qs_each,
+	"next" => method Self::qs_next,
+	"@iter" => method Self::qs_at_iter,
 }
@@ -0,0 +1,723 @@
+//! Working with directories and the filesystem.
+
+use crate::{Object, Args, Literal};
+use crate::types::{Text, Number, List, Iter};
+use tracing::instrument;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// The namespace for directory and filesystem functions.
+///
+/// Paths can be given as either [`Path`](crate::types::Path)s or [`Text`]s, and are returned as
+/// [`Text`]s.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Dir;
+
+/// Information about a file, directory, or symlink.
+#[derive(Debug, Clone)]
+pub struct Metadata(fs::Metadata);
+
+impl From<fs::Metadata> for Metadata {
+	#[inline]
+	fn from(metadata: fs::Metadata) -> Self {
+		Self(metadata)
+	}
+}
+
+impl Metadata {
+	/// Get the metadata for `path`, without following symlinks.
+	#[inline]
+	pub fn of<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+		fs::symlink_metadata(path).map(Self)
+	}
+
+	/// Get the kind of file this is: `"file"`, `"dir"`, `"symlink"`, or `"other"`.
+	pub fn kind(&self) -> &'static str {
+		let file_type = self.0.file_type();
+
+		if file_type.is_symlink() {
+			"symlink"
+		} else if file_type.is_dir() {
+			"dir"
+		} else if file_type.is_file() {
+			"file"
+		} else {
+			"other"
+		}
+	}
+
+	/// Get the permission bits, such as `0o644`.
+	#[cfg(unix)]
+	pub fn mode(&self) -> u32 {
+		use std::os::unix::fs::PermissionsExt;
+
+		self.0.permissions().mode() & 0o7777
+	}
+
+	/// Get the permission bits. As only read-only-ness is known, this is either `0o444` or `0o644`.
+	#[cfg(not(unix))]
+	pub fn mode(&self) -> u32 {
+		if self.0.permissions().readonly() { 0o444 } else { 0o644 }
+	}
+}
+
+fn to_path(arg: &Object) -> crate::Result<PathBuf> {
+	Ok(arg.call_downcast::<crate::types::Path>()?.as_ref().to_path_buf())
+}
+
+fn path_to_object(path: &Path) -> Object {
+	Text::from(path.to_string_lossy().into_owned()).into()
+}
+
+/// Get the paths of the entries in `dir`, sorted by name.
+pub fn list<P: AsRef<Path>>(dir: P) -> io::Result<Vec<PathBuf>> {
+	let mut entries = fs::read_dir(dir)?
+		.map(|entry| entry.map(|entry| entry.path()))
+		.collect::<io::Result<Vec<_>>>()?;
+
+	entries.sort();
+
+	Ok(entries)
+}
+
+/// Get the paths of everything within `dir`, recursively. Directories come before their contents,
+/// and symlinks to directories aren't followed.
+pub fn walk<P: AsRef<Path>>(dir: P) -> io::Result<Vec<PathBuf>> {
+	fn walk_into(dir: &Path, paths: &mut Vec<PathBuf>) -> io::Result<()> {
+		for path in list(dir)? {
+			let is_dir = fs::symlink_metadata(&path)?.is_dir();
+			paths.push(path.clone());
+
+			if is_dir {
+				walk_into(&path, paths)?;
+			}
+		}
+
+		Ok(())
+	}
+
+	let mut paths = Vec::new();
+	walk_into(dir.as_ref(), &mut paths)?;
+	Ok(paths)
+}
+
+/// Removes `path`, along with everything in it if it's a directory.
+pub fn remove<P: AsRef<Path>>(path: P) -> io::Result<()> {
+	if fs::symlink_metadata(&path)?.is_dir() {
+		fs::remove_dir_all(path)
+	} else {
+		fs::remove_file(path)
+	}
+}
+
+/// Canonicalizes `path`, which may not exist yet, by canonicalizing its closest existing ancestor.
+fn canonicalize_partial(path: &Path) -> io::Result<PathBuf> {
+	let mut missing = Vec::new();
+	let mut existing = if path.as_os_str().is_empty() { Path::new(".") } else { path };
+
+	while !existing.exists() {
+		missing.push(existing.file_name().unwrap_or_default().to_owned());
+		existing = match existing.parent() {
+			Some(parent) if !parent.as_os_str().is_empty() => parent,
+			_ => Path::new(".")
+		};
+	}
+
+	let mut canonical = existing.canonicalize()?;
+	canonical.extend(missing.iter().rev());
+	Ok(canonical)
+}
+
+/// Copies `from` to `to`, along with everything in it if it's a directory.
+///
+/// Symlinks are copied as links, rather than what they point to. Copying a directory into itself is
+/// an error, and nothing is created if so.
+pub fn copy<P: AsRef<Path>, Q: AsRef<Path>>(from: P, to: Q) -> io::Result<()> {
+	let (from, to) = (from.as_ref(), to.as_ref());
+
+	if fs::symlink_metadata(from)?.is_dir() && canonicalize_partial(to)?.starts_with(from.canonicalize()?) {
+		return Err(io::Error::new(io::ErrorKind::InvalidInput,
+			format!("can't copy {} into itself ({})", from.display(), to.display())));
+	}
+
+	copy_unchecked(from, to)
+}
+
+fn copy_unchecked(from: &Path, to: &Path) -> io::Result<()> {
+	let file_type = fs::symlink_metadata(from)?.file_type();
+
+	if file_type.is_symlink() {
+		return copy_symlink(from, to);
+	} else if !file_type.is_dir() {
+		return fs::copy(from, to).map(|_| ());
+	}
+
+	fs::create_dir_all(to)?;
+
+	for entry in fs::read_dir(from)? {
+		let entry = entry?;
+		copy_unchecked(&entry.path(), &to.join(entry.file_name()))?;
+	}
+
+	Ok(())
+}
+
+#[cfg(unix)]
+fn copy_symlink(from: &Path, to: &Path) -> io::Result<()> {
+	std::os::unix::fs::symlink(fs::read_link(from)?, to)
+}
+
+// symlinks need special permissions to create on other platforms, so they're skipped.
+#[cfg(not(unix))]
+fn copy_symlink(_: &Path, _: &Path) -> io::Result<()> {
+	Ok(())
+}
+
+/// Creates a new, empty directory in the system's temporary directory.
+pub fn temp() -> io::Result<PathBuf> {
+	loop {
+		let path = std::env::temp_dir().join(format!("quest-{:016x}", rand::random::<u64>()));
+
+		match fs::create_dir(&path) {
+			Ok(()) => return Ok(path),
+			Err(err) if err.kind() == io::ErrorKind::AlreadyExists => continue,
+			Err(err) => return Err(err)
+		}
+	}
+}
+
+/// Checks to see if `name` matches the glob `pattern`.
+///
+/// Within a pattern, `*` matches any amount of characters, `?` matches any single character, and
+/// `[...]` matches a single character within the brackets, such as `[abc]` or `[a-z]`; if the
+/// brackets start with `!`, the set is negated. Unlike [`glob`], `*` also matches `/`.
+pub fn matches(pattern: &str, name: &str) -> bool {
+	fn match_class(class: &[char], chr: char) -> Option<(bool, usize)> {
+		let (negated, start) = if class.first() == Some(&'!') { (true, 1) } else { (false, 0) };
+		// a `]` right after the opening bracket is a literal `]`.
+		let end = start + 1 + class.get(start + 1..)?.iter().position(|&c| c == ']')?;
+		let set = &class[start..end];
+
+		let mut found = false;
+		let mut i = 0;
+		while i < set.len() {
+			if i + 2 < set.len() && set[i + 1] == '-' {
+				found |= set[i] <= chr && chr <= set[i + 2];
+				i += 3;
+			} else {
+				found |= set[i] == chr;
+				i += 1;
+			}
+		}
+
+		Some((found != negated, end + 1))
+	}
+
+	fn match_from(pattern: &[char], name: &[char]) -> bool {
+		match pattern.first() {
+			None => name.is_empty(),
+			Some('*') => (0..=name.len()).any(|i| match_from(&pattern[1..], &name[i..])),
+			Some(_) if name.is_empty() => false,
+			Some('?') => match_from(&pattern[1..], &name[1..]),
+			Some('[') =>
+				match match_class(&pattern[1..], name[0]) {
+					Some((true, len)) => match_from(&pattern[1 + len..], &name[1..]),
+					Some((false, _)) => false,
+					// an unclosed bracket is just a literal `[`.
+					None => name[0] == '[' && match_from(&pattern[1..], &name[1..])
+				},
+			Some(&chr) => name[0] == chr && match_from(&pattern[1..], &name[1..])
+		}
+	}
+
+	let pattern = pattern.chars().collect::<Vec<_>>();
+	let name = name.chars().collect::<Vec<_>>();
+
+	match_from(&pattern, &name)
+}
+
+/// Finds all the paths that match `pattern`, sorted.
+///
+/// Each component of the pattern is [matched](matches) against the names in a directory, with
+/// the addition of `**`, which matches any amount of nested directories. Hidden files are only
+/// matched by components starting with `.`.
+pub fn glob(pattern: &str) -> io::Result<Vec<PathBuf>> {
+	fn is_literal(component: &str) -> bool {
+		!component.contains(&['*', '?', '['][..])
+	}
+
+	fn expand(dir: &Path, components: &[&str], paths: &mut Vec<PathBuf>) -> io::Result<()> {
+		let (component, rest) =
+			match components.split_first() {
+				Some(split) => split,
+				// a lone `**` would otherwise match the empty path.
+				None if dir.as_os_str().is_empty() => return Ok(()),
+				None => { paths.push(dir.to_path_buf()); return Ok(()) }
+			};
+
+		if component.is_empty() {
+			return expand(dir, rest, paths);
+		}
+
+		if is_literal(component) {
+			let path = dir.join(component);
+
+			if fs::symlink_metadata(&path).is_ok() {
+				expand(&path, rest, paths)?;
+			}
+
+			return Ok(());
+		}
+
+		// we don't care if `dir` doesn't exist or isn't a directory; it just doesn't match anything.
+		let entries =
+			match list(if dir.as_os_str().is_empty() { Path::new(".") } else { dir }) {
+				Ok(entries) => entries,
+				Err(_) => return Ok(())
+			};
+
+		if *component == "**" {
+			expand(dir, rest, paths)?;
+		}
+
+		for entry in entries {
+			let name = entry.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default();
+
+			if name.starts_with('.') && !component.starts_with('.') {
+				continue;
+			}
+
+			let path = dir.join(&name);
+
+			if *component == "**" {
+				if fs::symlink_metadata(&path)?.is_dir() {
+					expand(&path, components, paths)?;
+				}
+			} else if matches(component, &name) {
+				expand(&path, rest, paths)?;
+			}
+		}
+
+		Ok(())
+	}
+
+	let components = pattern.split('/').collect::<Vec<_>>();
+	let root = if pattern.starts_with('/') { Path::new("/") } else { Path::new("") };
+
+	let mut paths = Vec::new();
+	expand(root, &components, &mut paths)?;
+	paths.sort();
+	paths.dedup();
+
+	Ok(paths)
+}
+
+/// Quest methods
+impl Dir {
+	/// Get an [`Iter`] of the paths of the entries in a directory, sorted by name.
+	///
+	/// # Arguments
+	/// 1. (optional, `@path`) The directory; defaults to the current directory.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// Io.Dir.temp({
+	/// 	$dir = _0;
+	/// 	Io.File(dir + "/b.txt", "n").write("b");
+	/// 	Io.Dir.mkdir(dir + "/a");
+	///
+	/// 	$expected = [dir + "/a", dir + "/b.txt"];
+	/// 	assert(Io.Dir.list(dir).@list() == expected);
+	/// });
+	/// ```
+	#[instrument(name="Dir::list", level="trace", skip(args), fields(?args))]
+	pub fn qs_list(_: &Object, args: Args) -> crate::Result<Object> {
+		let dir = args.arg(0).map(to_path).transpose()?.unwrap_or_else(|| PathBuf::from("."));
+		let entries = list(dir)?;
+
+		Ok(Iter::with_objects(entries.iter().map(|path| path_to_object(path)).collect::<Vec<_>>()).into())
+	}
+
+	/// Get an [`Iter`] of the paths of everything within a directory, recursively.
+	///
+	/// Directories come before their contents, and symlinks to directories aren't followed.
+	///
+	/// # Arguments
+	/// 1. (required, `@path`) The directory.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// Io.Dir.temp({
+	/// 	$dir = _0;
+	/// 	Io.Dir.mkdir(dir + "/a/b");
+	/// 	Io.File(dir + "/a/b/c.txt", "n").write("c");
+	///
+	/// 	$expected = [dir + "/a", dir + "/a/b", dir + "/a/b/c.txt"];
+	/// 	assert(Io.Dir.walk(dir).@list() == expected);
+	/// });
+	/// ```
+	#[instrument(name="Dir::walk", level="trace", skip(args), fields(?args))]
+	pub fn qs_walk(_: &Object, args: Args) -> crate::Result<Object> {
+		let paths = walk(to_path(args.try_arg(0)?)?)?;
+
+		Ok(Iter::with_objects(paths.iter().map(|path| path_to_object(path)).collect::<Vec<_>>()).into())
+	}
+
+	/// Create a directory, along with any missing parents. It's not an error if it already exists.
+	///
+	/// # Arguments
+	/// 1. (required, `@path`) The directory to create.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// Io.Dir.temp({
+	/// 	$path = _0 + "/a/b/c";
+	/// 	Io.Dir.mkdir(path);
+	/// 	assert(Io.Dir.dir?(path));
+	/// });
+	/// ```
+	#[instrument(name="Dir::mkdir", level="trace", skip(args), fields(?args))]
+	pub fn qs_mkdir(_: &Object, args: Args) -> crate::Result<Object> {
+		let path = args.try_arg(0)?;
+
+		fs::create_dir_all(to_path(path)?)?;
+
+		Ok(path.clone())
+	}
+
+	/// Remove a file or symlink, or a directory and everything in it.
+	///
+	/// # Arguments
+	/// 1. (required, `@path`) The path to remove.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// Io.Dir.temp({
+	/// 	$path = _0 + "/a";
+	/// 	Io.Dir.mkdir(path + "/b");
+	/// 	Io.Dir.remove(path);
+	/// 	assert(!Io.Dir.exists?(path));
+	/// });
+	/// ```
+	#[instrument(name="Dir::remove", level="trace", skip(args), fields(?args))]
+	pub fn qs_remove(_: &Object, args: Args) -> crate::Result<Object> {
+		remove(to_path(args.try_arg(0)?)?)?;
+
+		Ok(Object::default())
+	}
+
+	/// Rename (or move) a path, replacing the destination if it's a file.
+	///
+	/// # Arguments
+	/// 1. (required, `@path`) The path to rename.
+	/// 2. (required, `@path`) The new path.
+	#[instrument(name="Dir::rename", level="trace", skip(args), fields(?args))]
+	pub fn qs_rename(_: &Object, args: Args) -> crate::Result<Object> {
+		let to = args.try_arg(1)?;
+
+		fs::rename(to_path(args.try_arg(0)?)?, to_path(to)?)?;
+
+		Ok(to.clone())
+	}
+
+	/// Copy a file, or a directory and everything in it.
+	///
+	/// # Arguments
+	/// 1. (required, `@path`) The path to copy.
+	/// 2. (required, `@path`) Where to copy it to.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// Io.Dir.temp({
+	/// 	$dir = _0;
+	/// 	Io.Dir.mkdir(dir + "/a");
+	/// 	Io.File(dir + "/a/x.txt", "n").write("x");
+	/// 	Io.Dir.copy(dir + "/a", dir + "/b");
+	/// 	assert(Io.File(dir + "/b/x.txt").read() == "x");
+	/// });
+	/// ```
+	#[instrument(name="Dir::copy", level="trace", skip(args), fields(?args))]
+	pub fn qs_copy(_: &Object, args: Args) -> crate::Result<Object> {
+		let to = args.try_arg(1)?;
+
+		copy(to_path(args.try_arg(0)?)?, to_path(to)?)?;
+
+		Ok(to.clone())
+	}
+
+	/// Get the [`Metadata`] for a path, without following symlinks.
+	///
+	/// # Arguments
+	/// 1. (required, `@path`) The path.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// Io.Dir.temp({
+	/// 	$path = _0 + "/x.txt";
+	/// 	Io.File(path, "n").write("hello");
+	///
+	/// 	$metadata = Io.Dir.metadata(path);
+	/// 	assert(metadata.size() == 5);
+	/// 	assert(metadata.type() == "file");
+	/// });
+	/// ```
+	#[instrument(name="Dir::metadata", level="trace", skip(args), fields(?args))]
+	pub fn qs_metadata(_: &Object, args: Args) -> crate::Result<Object> {
+		Ok(Metadata::of(to_path(args.try_arg(0)?)?)?.into())
+	}
+
+	/// Checks to see if a path exists. Broken symlinks don't exist.
+	///
+	/// # Arguments
+	/// 1. (required, `@path`) The path.
+	#[instrument(name="Dir::exists?", level="trace", skip(args), fields(?args))]
+	pub fn qs_exists_q(_: &Object, args: Args) -> crate::Result<Object> {
+		Ok(to_path(args.try_arg(0)?)?.exists().into())
+	}
+
+	/// Checks to see if a path is a directory, following symlinks.
+	///
+	/// # Arguments
+	/// 1. (required, `@path`) The path.
+	#[instrument(name="Dir::dir?", level="trace", skip(args), fields(?args))]
+	pub fn qs_dir_q(_: &Object, args: Args) -> crate::Result<Object> {
+		Ok(to_path(args.try_arg(0)?)?.is_dir().into())
+	}
+
+	/// Checks to see if a path is a regular file, following symlinks.
+	///
+	/// # Arguments
+	/// 1. (required, `@path`) The path.
+	#[instrument(name="Dir::file?", level="trace", skip(args), fields(?args))]
+	pub fn qs_file_q(_: &Object, args: Args) -> crate::Result<Object> {
+		Ok(to_path(args.try_arg(0)?)?.is_file().into())
+	}
+
+	/// Create a new, empty temporary directory.
+	///
+	/// If a block is given, it's called with the directory's path, and the directory (and
+	/// everything in it) is removed afterwards, even if the block throws an error; the block's
+	/// result is returned. Otherwise, the path is returned and removing it is up to the caller.
+	///
+	/// # Arguments
+	/// 1. (optional) The block to call with the path.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// $path = Io.Dir.temp({ _0 });
+	/// assert(!Io.Dir.exists?(path));
+	/// ```
+	#[instrument(name="Dir::temp", level="trace", skip(args), fields(?args))]
+	pub fn qs_temp(_: &Object, args: Args) -> crate::Result<Object> {
+		let path = temp()?;
+		let path_obj = path_to_object(&path);
+
+		let block =
+			if let Some(block) = args.arg(0) {
+				block
+			} else {
+				return Ok(path_obj);
+			};
+
+		let result = block.call_attr_lit(&Literal::CALL, &[&path_obj]);
+
+		match (result, remove(&path)) {
+			(Err(err), _) => Err(err),
+			(Ok(_), Err(err)) => Err(err.into()),
+			(Ok(result), Ok(())) => Ok(result)
+		}
+	}
+
+	/// Get a [`List`] of the paths matching a glob pattern, sorted.
+	///
+	/// Within each component of the pattern, `*` matches any amount of characters, `?` matches any
+	/// single character, and `[...]` matches any character in the brackets (`[!...]` negates it).
+	/// A `**` component matches any amount of nested directories. Hidden files are only matched if
+	/// the component starts with a `.`.
+	///
+	/// # Arguments
+	/// 1. (required, `@path`) The pattern.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// Io.Dir.temp({
+	/// 	$dir = _0;
+	/// 	Io.Dir.mkdir(dir + "/src/bin");
+	/// 	Io.File(dir + "/src/lib.rs", "n").write("");
+	/// 	Io.File(dir + "/src/bin/main.rs", "n").write("");
+	/// 	Io.File(dir + "/README.md", "n").write("");
+	///
+	/// 	$expected = [dir + "/src/bin/main.rs", dir + "/src/lib.rs"];
+	/// 	assert(Io.Dir.glob(dir + "/**/*.rs") == expected);
+	/// });
+	/// ```
+	#[instrument(name="Dir::glob", level="trace", skip(args), fields(?args))]
+	pub fn qs_glob(_: &Object, args: Args) -> crate::Result<Object> {
+		let paths = glob(args.try_arg(0)?.call_downcast::<Text>()?.as_ref())?;
+
+		Ok(List::from(paths.iter().map(|path| path_to_object(path)).collect::<Vec<_>>()).into())
+	}
+
+	/// Checks to see if a name matches a glob pattern. Unlike [`glob`](#method.qs_glob), `*` also
+	/// matches `/`.
+	///
+	/// # Arguments
+	/// 1. (required, `@path`) The pattern.
+	/// 2. (required, `@text`) The name to check.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// assert(Io.Dir.matches?("*.[ch]", "main.c"));
+	/// assert(!Io.Dir.matches?("*.[ch]", "main.rs"));
+	/// ```
+	#[instrument(name="Dir::matches?", level="trace", skip(args), fields(?args))]
+	pub fn qs_matches_q(_: &Object, args: Args) -> crate::Result<Object> {
+		let pattern = args.try_arg(0)?.call_downcast::<Text>()?;
+		let name = args.try_arg(1)?.call_downcast::<Text>()?;
+
+		Ok(matches(pattern.as_ref(), name.as_ref()).into())
+	}
+}
+
+impl_object_type!{
+for Dir [(parents crate::types::Basic)]:
+	"Metadata" => const Metadata::mapping().clone(),
+	"list" => method Self::qs_list,
+	"walk" => method Self::qs_walk,
+	"mkdir" => method Self::qs_mkdir,
+	"remove" => method Self::qs_remove,
+	"rename" => method Self::qs_rename,
+	"copy" => method Self::qs_copy,
+	"metadata" => method Self::qs_metadata,
+	"exists?" => method Self::qs_exists_q,
+	"dir?" => method Self::qs_dir_q,
+	"file?" => method Self::qs_file_q,
+	"temp" => method Self::qs_temp,
+	"glob" => method Self::qs_glob,
+	"matches?" => method Self::qs_matches_q,
+}
+
+/// Quest methods
+impl Metadata {
+	/// Get the size, in bytes.
+	#[instrument(name="Dir::Metadata::size", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_size(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(Number::from(this.try_downcast::<Self>()?.0.len()).into())
+	}
+
+	/// Get when it was last modified, as a [`Time`](crate::types::Time) in the local time zone.
+	#[instrument(name="Dir::Metadata::mtime", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_mtime(this: &Object, _: Args) -> crate::Result<Object> {
+		let modified = this.try_downcast::<Self>()?.0.modified()?;
+
+		Ok(crate::types::time::Zone::local().at(modified.into()).into())
+	}
+
+	/// Get the permission bits as a [`Number`], such as `0o644`.
+	#[instrument(name="Dir::Metadata::mode", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_mode(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(Number::from(this.try_downcast::<Self>()?.mode()).into())
+	}
+
+	/// Checks to see if it's read-only.
+	#[instrument(name="Dir::Metadata::readonly?", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_readonly_q(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.0.permissions().readonly().into())
+	}
+
+	/// Get the kind of file: `"file"`, `"dir"`, `"symlink"`, or `"other"`.
+	#[instrument(name="Dir::Metadata::type", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_type(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(Text::from(this.try_downcast::<Self>()?.kind()).into())
+	}
+
+	#[instrument(name="Dir::Metadata::inspect", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_inspect(this: &Object, _: Args) -> crate::Result<Object> {
+		let this = this.try_downcast::<Self>()?;
+
+		Ok(Text::from(format!("Metadata({}, {} bytes, {:#o})", this.kind(), this.0.len(), this.mode())).into())
+	}
+}
+
+impl_object_type!{
+for Metadata [(parents crate::types::Basic)]:
+	"size" => method Self::qs_size,
+	"mtime" => method Self::qs_mtime,
+	"mode" => method Self::qs_mode,
+	"readonly?" => method Self::qs_readonly_q,
+	"type" => method Self::qs_type,
+	"inspect" => method Self::qs_inspect,
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn glob_matching() {
+		assert!(matches("*.rs", "main.rs"));
+		assert!(matches("*", ""));
+		assert!(matches("a?c", "abc"));
+		assert!(!matches("a?c", "ac"));
+		assert!(matches("[a-c]x", "bx"));
+		assert!(!matches("[!a-c]x", "bx"));
+		assert!(matches("[]]", "]"));
+		assert!(matches("[ab", "[ab"));
+		assert!(!matches("*.rs", "main.rs.bak"));
+	}
+
+	#[test]
+	fn filesystem_operations() {
+		let dir = temp().unwrap();
+
+		fs::create_dir_all(dir.join("a/b")).unwrap();
+		fs::write(dir.join("a/b/c.rs"), "c").unwrap();
+		fs::write(dir.join("a/d.rs"), "dd").unwrap();
+		fs::write(dir.join("a/.e.rs"), "").unwrap();
+
+		assert_eq!(list(dir.join("a")).unwrap(), vec![dir.join("a/.e.rs"), dir.join("a/b"), dir.join("a/d.rs")]);
+		assert_eq!(walk(&dir).unwrap().len(), 5);
+
+		let pattern = format!("{}/**/*.rs", dir.display());
+		assert_eq!(glob(&pattern).unwrap(), vec![dir.join("a/b/c.rs"), dir.join("a/d.rs")]);
+
+		copy(dir.join("a"), dir.join("x")).unwrap();
+		assert_eq!(fs::read_to_string(dir.join("x/b/c.rs")).unwrap(), "c");
+		assert_eq!(Metadata::of(dir.join("x/d.rs")).unwrap().0.len(), 2);
+		assert_eq!(Metadata::of(dir.join("x/b")).unwrap().kind(), "dir");
+
+		remove(&dir).unwrap();
+		assert!(!dir.exists());
+	}
+
+	#[test]
+	fn copy_into_itself() {
+		let dir = temp().unwrap();
+		fs::create_dir_all(dir.join("a/b")).unwrap();
+
+		assert!(copy(dir.join("a"), dir.join("a/inner")).is_err());
+		assert!(copy(dir.join("a"), dir.join("a/b/c/d")).is_err());
+		assert!(copy(dir.join("a"), dir.join("a")).is_err());
+		assert!(!dir.join("a/inner").exists() && !dir.join("a/b/c").exists());
+
+		// a sibling whose name starts the same isn't inside it.
+		copy(dir.join("a"), dir.join("ab")).unwrap();
+		assert!(dir.join("ab/b").is_dir());
+
+		remove(&dir).unwrap();
+	}
+
+	#[test]
+	#[cfg(unix)]
+	fn copy_symlinks() {
+		let dir = temp().unwrap();
+		fs::create_dir_all(dir.join("a")).unwrap();
+		std::os::unix::fs::symlink(dir.join("a"), dir.join("a/loop")).unwrap();
+
+		copy(dir.join("a"), dir.join("b")).unwrap();
+		assert_eq!(fs::read_link(dir.join("b/loop")).unwrap(), dir.join("a"));
+
+		remove(&dir).unwrap();
+	}
+}
@@ -1,7 +1,7 @@
 #![allow(unused)]
 use crate::{Object, Args, Literal};
 use crate::error::ValueError;
-use crate::types::{Text, Number, Null, Regex};
+use crate::types::{Text, Number, Null, Regex, Bytes};
 use tracing::instrument;
 use parking_lot::Mutex;
 use std::convert::TryFrom;
@@ -14,7 +14,9 @@ use std::io::{self, Read, Write, Seek, SeekFrom, BufReader, BufRead};
 // is an `Arc<Mutex>` really the best way to do this
 #[derive(Debug)]
 pub struct File {
-	file: Option<BufReader<fs::File>>
+	file: Option<BufReader<fs::File>>,
+	/// Whether the file reads [`Bytes`] instead of [`Text`].
+	binary: bool
 }
 
 impl Clone for File {
@@ -22,7 +24,8 @@ impl Clone for File {
 		Self { 
 			file: self.file.as_ref()
 				.map(|x| x.get_ref().try_clone().expect("unable to clone file"))
-				.map(BufReader::new)
+				.map(BufReader::new),
+			binary: self.binary
 		}
 	}
 }
@@ -36,10 +39,22 @@ impl File {
 		OpenOptions::new().read(read).write(write).open_fd(fd)
 	}
 
+	/// Wraps an already-open file, which reads [`Bytes`] instead of [`Text`] if `binary` is set.
+	#[inline]
+	pub fn from_std(file: fs::File, binary: bool) -> Self {
+		Self { binary, ..Self::from(file) }
+	}
+
 	pub fn close(&mut self) {
 		self.file.take();
 	}
 
+	/// Checks to see if the file was opened in binary mode.
+	#[inline]
+	pub fn is_binary(&self) -> bool {
+		self.binary
+	}
+
 	pub fn write(&mut self, what: &[u8]) -> io::Result<()> {
 		if let Some(ref mut file) = self.file {
 			file.get_mut().write(what);
@@ -78,6 +93,79 @@ impl File {
 		Ok(Some(String::from_utf8_lossy(&buf).into_owned()))
 	}
 
+	pub fn read_all_bytes(&mut self) -> io::Result<Option<Vec<u8>>> {
+		let file = 
+			if let Some(ref mut file) = self.file {
+				file
+			} else {
+				return Ok(None);
+			};
+
+		let mut buf = Vec::with_capacity(file.buffer().len());
+
+		file.read_to_end(&mut buf)?;
+
+		Ok(Some(buf))
+	}
+
+	// unlike `read_amnt`, fewer than `amnt` bytes are returned if EOF is encountered.
+	pub fn read_amnt_bytes(&mut self, amnt: usize) -> io::Result<Option<Vec<u8>>> {
+		let file = 
+			if let Some(ref mut file) = self.file {
+				file
+			} else {
+				return Ok(None);
+			};
+
+		let mut buf = Vec::with_capacity(amnt);
+
+		file.take(amnt as u64).read_to_end(&mut buf)?;
+
+		Ok(Some(buf))
+	}
+
+	// note that if EOF is encountered before the sentinel is hit, we just return everything.
+	pub fn read_until_bytes(&mut self, sentinel: &[u8]) -> io::Result<Option<Vec<u8>>> {
+		if sentinel.is_empty() {
+			return self.read_all_bytes();
+		}
+
+		let file =
+			if let Some(ref mut file) = self.file {
+				file
+			} else {
+				return Ok(None)
+			};
+
+		let mut buf = Vec::with_capacity(sentinel.len());
+
+		// we search what we've read so far, so sentinels that span multiple reads are found.
+		while !buf.ends_with(sentinel) {
+			let file_buf = file.fill_buf()?;
+
+			if file_buf.is_empty() {
+				break;
+			}
+
+			let start = buf.len().saturating_sub(sentinel.len() - 1);
+			buf.extend_from_slice(file_buf);
+
+			let consumed =
+				if let Some(i) = buf[start..].windows(sentinel.len()).position(|x| x == sentinel) {
+					let end = start + i + sentinel.len();
+					let consumed = file_buf.len() - (buf.len() - end);
+					buf.truncate(end);
+					consumed
+				} else {
+					file_buf.len()
+				};
+
+			file.consume(consumed);
+		}
+
+		Ok(Some(buf))
+	}
+
 	// note that if EOF is encountered before the sentinel is hit, we just return everything.
 	pub fn read_until_sentinel(&mut self, sentinel: &str) -> io::Result<Option<String>> {
 		if sentinel.is_empty() {
@@ -163,7 +251,7 @@ impl File {
 impl From<fs::File> for File {
 	#[inline]
 	fn from(file: fs::File) -> Self {
-		Self { file: Some(BufReader::new(file)) }
+		Self { file: Some(BufReader::new(file)), binary: false }
 	}
 }
 
@@ -176,9 +264,10 @@ impl From<fs::File> for Object {
 
 /// A class that's used to specify options when opening files.
 ///
-/// For the time being, this is a wrapper around [`std::fs::OpenOptions`]	
+/// For the time being, this is a wrapper around [`std::fs::OpenOptions`], along with whether the
+/// file should be opened in binary mode.
 #[derive(Debug, Clone)]
-pub struct OpenOptions(fs::OpenOptions);
+pub struct OpenOptions(fs::OpenOptions, bool);
 
 impl Default for OpenOptions {
 	/// By default, files are read.
@@ -192,7 +281,7 @@ impl Default for OpenOptions {
 impl From<fs::OpenOptions> for OpenOptions {
 	#[inline]
 	fn from(open_opts: fs::OpenOptions) -> Self {
-		Self(open_opts)
+		Self(open_opts, false)
 	}
 }
 
@@ -211,7 +300,14 @@ macro_rules! forward_fn {
 impl OpenOptions {
 	#[inline]
 	pub fn new() -> Self {
-		Self(fs::OpenOptions::new())
+		Self(fs::OpenOptions::new(), false)
+	}
+
+	/// Sets whether the file reads [`Bytes`] instead of [`Text`].
+	#[inline]
+	pub fn binary(&mut self, binary: bool) -> &mut Self {
+		self.1 = binary;
+		self
 	}
 
 	#[inline]
@@ -254,7 +350,7 @@ impl OpenOptions {
 
 	#[inline]
 	pub fn open<P: AsRef<Path>>(&self, path: P) -> io::Result<File> {
-		self.0.open(path).map(File::from)
+		self.0.open(path).map(|file| File { binary: self.1, ..File::from(file) })
 	}
 
 	pub fn open_fd(&self, fd: i32) -> io::Result<File> {
@@ -284,6 +380,7 @@ impl TryFrom<&str> for OpenOptions {
 	/// - `a` Sets [`append`](OpenOptions::append).
 	/// - `t` Sets [`truncate`](OpenOptions::truncate).
 	/// - `n` Sets [`create_new`](OpenOptions::create_new).
+	/// - `b` Sets [`binary`](OpenOptions::binary).
 	/// Any other character will yield an [`InvalidOptionChar`].
 	///
 	/// If an empty string is supplied, [the default](OpenOptions::default) is returned.
@@ -297,6 +394,7 @@ impl TryFrom<&str> for OpenOptions {
 				't' | 'T' => opts.truncate(opt == 't'),
 				'r' | 'R' => opts.read(opt == 'r'),
 				'n' | 'N' => opts.create_new(opt == 'n'),
+				'b' | 'B' => opts.binary(opt == 'b'),
 				invalid => return Err(InvalidOptionChar(invalid))
 			};
 		}
@@ -319,9 +417,9 @@ impl File {
 
 		let file = 
 			if let Some(fd) = filename.downcast::<Number>() {
-				openopts.open_fd(i32::try_from(*fd)?)?
+				openopts.open_fd(i32::try_from(fd.clone())?)?
 			} else {
-				openopts.open(filename.call_downcast::<Text>()?.as_ref())?
+				openopts.open(&*filename.call_downcast::<crate::types::Path>()?)?
 			};
 
 		Ok(file.into())
@@ -331,6 +429,10 @@ impl File {
 	pub fn qs_read(this: &Object, args: Args) -> crate::Result<Object> {
 		let mut this = this.try_downcast_mut::<Self>()?;
 
+		if this.binary {
+			return this.read_binary(args.arg(0));
+		}
+
 		let arg = args.arg(0);
 
 		let read =
@@ -364,17 +466,51 @@ impl File {
 		})
 	}
 
+	/// Reads [`Bytes`] from a binary file.
+	///
+	/// Binary files can only be read in their entirety, by an amount, or until a [`Bytes`] (or
+	/// [`Text`]) sentinel.
+	fn read_binary(&mut self, arg: Option<&Object>) -> crate::Result<Object> {
+		let read =
+			if let Some(amnt) = arg.and_then(Object::downcast::<Number>) {
+				self.read_amnt_bytes(amnt.truncate() as usize)?
+			} else if let Some(end) = arg.and_then(Object::downcast::<Bytes>) {
+				self.read_until_bytes(end.as_ref())?
+			} else if let Some(end) = arg.and_then(Object::downcast::<Text>) {
+				self.read_until_bytes(end.as_ref().as_bytes())?
+			} else if arg.map_or(true, Object::is_a::<Null>) {
+				self.read_all_bytes()?
+			} else {
+				return Err(crate::error::TypeError::Messaged(
+					"binary files can only be read by amount or until a sentinel".into()).into());
+			};
+
+		Ok(match read {
+			Some(read) if !read.is_empty() => Bytes::from(read).into(),
+			_ => Object::default()
+		})
+	}
+
+	/// Writes the first argument to the file.
+	///
+	/// [`Bytes`] are written as-is, and everything else is converted to [`Text`].
 	#[instrument(name="File::write", level="trace", skip(this, args), fields(self=?this, args=?args))]
 	pub fn qs_write(this: &Object, args: Args) -> crate::Result<Object> {
-		let to_write = args.try_arg(0)?.call_downcast::<Text>()?;
+		let to_write = crate::types::bytes::to_write(args.try_arg(0)?)?;
 
 		if let Some(ref mut file) = this.try_downcast_mut::<Self>()?.file {
-			file.get_mut().write_all(to_write.as_ref().as_ref())?;
+			file.get_mut().write_all(&to_write)?;
 		}
 
 		Ok(this.clone())
 	}
 
+	/// Checks to see if the file was opened in binary mode.
+	#[instrument(name="File::binary?", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_binary_q(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.binary.into())
+	}
+
 	#[instrument(name="File::close", level="trace", skip(this), fields(self=?this))]
 	pub fn qs_close(this: &Object, _: Args) -> crate::Result<Object> {
 		this.try_downcast_mut::<Self>()?.close();
@@ -394,5 +530,6 @@ for File [(parents super::Io)]:
 	},
 	"write" => method Self::qs_write,
 	"close" => method Self::qs_close,
+	"binary?" => method Self::qs_binary_q,
 	// "close" => method Self::qs_close
 }
@@ -1,5 +1,7 @@
 mod iter;
+mod generator;
 pub use iter::{Iter, StopIteration};
+pub use generator::yield_value;
 
 use tracing::instrument;
 use crate::error::ArgumentError;
@@ -15,17 +17,23 @@ impl Iterable {
 	/// Finishes the iterable and converts it to a [`List`].
 	#[instrument(name="Iterable::@list", level="trace", skip(this), fields(self=?this))]
 	pub fn qs_at_list(this: &Object, _: Args) -> crate::Result<Object> {
-		this.call_downcast::<Iter>()?
-			.clone()
+		Iter::from_object(this)?
 			.try_into_list()
 			.map(Object::from)
 	}
 
+	/// Finishes the iterable and converts it to a [`Set`](crate::types::Set), removing duplicates.
+	#[instrument(name="Iterable::@set", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_at_set(this: &Object, _: Args) -> crate::Result<Object> {
+		let iter = Iter::from_object(this)?;
+
+		crate::types::Set::try_from_iter(iter).map(Object::from)
+	}
+
 	/// Finishes the iterable and converts it to a [`List`].
 	#[instrument(name="Iterable::@list", level="trace", skip(this), fields(self=?this))]
 	pub fn qs_run(this: &Object, _: Args) -> crate::Result<Object> {
-		this.call_downcast::<Iter>()?
-			.clone()
+		Iter::from_object(this)?
 			.run()
 			.map(|_| Object::default())
 	}
@@ -33,7 +41,7 @@ impl Iterable {
 	/// Enumerates the iterable by returning `[ele, idx]`
 	#[instrument(name="Iterable::enumerate", level="trace", skip(this, args), fields(self=?this, ?args))]
 	pub fn qs_enumerate(this: &Object, args: Args) -> crate::Result<Object> {
-		let iter = this.call_downcast::<Iter>()?.clone();
+		let iter = Iter::from_object(this)?;
 
 		Ok(iter.enumerate().into())
 	}
@@ -41,7 +49,7 @@ impl Iterable {
 	// Returns a new [`Iter`], where each element is `args[0](this.next())`
 	#[instrument(name="Iterable::map", level="trace", skip(this, args), fields(self=?this, ?args))]
 	pub fn qs_map(this: &Object, args: Args) -> crate::Result<Object> {
-		let iter = this.call_downcast::<Iter>()?.clone();
+		let iter = Iter::from_object(this)?;
 		let block = args.try_arg(0)?.clone();
 
 		Ok(iter.map(move |obj| block.call_attr_lit(&Literal::CALL, &[&obj])).into())
@@ -58,7 +66,7 @@ impl Iterable {
 	// this version is lazy, and doesn't actually evaluate it.
 	#[instrument(name="Iterable::eachl", level="trace", skip(this, args), fields(self=?this, ?args))]
 	pub fn qs_eachl(this: &Object, args: Args) -> crate::Result<Object> {
-		let iter = this.call_downcast::<Iter>()?.clone();
+		let iter = Iter::from_object(this)?;
 		let block = args.try_arg(0)?.clone();
 
 		Ok(iter.each(move |obj| block.call_attr_lit(&Literal::CALL, &[obj]).and(Ok(()))).into())
@@ -66,7 +74,7 @@ impl Iterable {
 
 	#[instrument(name="Iterable::select", level="trace", skip(this, args), fields(self=?this, ?args))]
 	pub fn qs_select(this: &Object, args: Args) -> crate::Result<Object> {
-		let iter = this.call_downcast::<Iter>()?.clone();
+		let iter = Iter::from_object(this)?;
 		let block = args.try_arg(0)?.clone();
 
 		Ok(iter.select(move |obj| 
@@ -78,7 +86,7 @@ impl Iterable {
 
 	#[instrument(name="Iterable::reject", level="trace", skip(this, args), fields(self=?this, ?args))]
 	pub fn qs_reject(this: &Object, args: Args) -> crate::Result<Object> {
-		let iter = this.call_downcast::<Iter>()?.clone();
+		let iter = Iter::from_object(this)?;
 		let block = args.try_arg(0)?.clone();
 
 		Ok(iter.select(move |obj| 
@@ -90,7 +98,7 @@ impl Iterable {
 
 	#[instrument(name="Iterable::reduce", level="trace", skip(this, args), fields(self=?this, ?args))]
 	pub fn qs_reduce(this: &Object, args: Args) -> crate::Result<Object> {
-		let mut iter = this.call_downcast::<Iter>()?.clone();
+		let mut iter = Iter::from_object(this)?;
 
 		let start;
 		let block;
@@ -116,12 +124,12 @@ impl Iterable {
 			return Ok(this.clone());
 		}
 
-		let this = this.call_downcast::<Iter>()?.clone();
+		let this = Iter::from_object(this)?;
 
 		let zippers =
 			args.as_ref()
 				.iter()
-				.map(|arg| arg.call_downcast::<Iter>().map(|iter| iter.clone()))
+				.map(|arg| Iter::from_object(arg))
 				.collect::<crate::Result<Vec<_>>>()?;
 
 		Ok(this.zip(zippers).into())
@@ -130,7 +138,7 @@ impl Iterable {
 	/// Only return a maximum of `n` elements, where `n` is the first argument.
 	#[instrument(name="Iterable::take", level="trace", skip(this, args), fields(self=?this, ?args))]
 	pub fn qs_take(this: &Object, args: Args) -> crate::Result<Object> {
-		let this = this.call_downcast::<Iter>()?.clone();
+		let this = Iter::from_object(this)?;
 		let amnt = args.try_arg(0)?.call_downcast::<Number>()?.truncate();
 
 		if amnt < 0 {
@@ -143,7 +151,7 @@ impl Iterable {
 	/// Only take elements while the give block (ie first arg) evaluates to true.
 	#[instrument(name="Iterable::take_while", level="trace", skip(this, args), fields(self=?this, ?args))]
 	pub fn qs_take_while(this: &Object, args: Args) -> crate::Result<Object> {
-		let this = this.call_downcast::<Iter>()?.clone();
+		let this = Iter::from_object(this)?;
 		let block = args.try_arg(0)?.clone();
 
 		Ok(this.take_while(move |obj| {
@@ -156,7 +164,7 @@ impl Iterable {
 	/// Only take elements while the give block (ie first arg) evaluates to false.
 	#[instrument(name="Iterable::take_until", level="trace", skip(this, args), fields(self=?this, ?args))]
 	pub fn qs_take_until(this: &Object, args: Args) -> crate::Result<Object> {
-		let this = this.call_downcast::<Iter>()?.clone();
+		let this = Iter::from_object(this)?;
 		let block = args.try_arg(0)?.clone();
 
 		Ok(this.take_while(move |obj| {
@@ -169,7 +177,7 @@ impl Iterable {
 	/// Ignore the first `n` elements (ie the first argument)
 	#[instrument(name="Iterable::drop", level="trace", skip(this, args), fields(self=?this, ?args))]
 	pub fn qs_drop(this: &Object, args: Args) -> crate::Result<Object> {
-		let this = this.call_downcast::<Iter>()?.clone();
+		let this = Iter::from_object(this)?;
 		let amnt = args.try_arg(0)?.call_downcast::<Number>()?.truncate();
 
 		if amnt < 0 {
@@ -182,7 +190,7 @@ impl Iterable {
 	/// Ignore elements while the give block (ie first arg) evaluates to true.
 	#[instrument(name="Iterable::drop_while", level="trace", skip(this, args), fields(self=?this, ?args))]
 	pub fn qs_drop_while(this: &Object, args: Args) -> crate::Result<Object> {
-		let this = this.call_downcast::<Iter>()?.clone();
+		let this = Iter::from_object(this)?;
 		let block = args.try_arg(0)?.clone();
 
 		Ok(this.drop_while(move |obj| {
@@ -195,7 +203,7 @@ impl Iterable {
 	/// Ignore elements while the given block (ie first arg) evaluates to false
 	#[instrument(name="Iterable::drop_until", level="trace", skip(this, args), fields(self=?this, ?args))]
 	pub fn qs_drop_until(this: &Object, args: Args) -> crate::Result<Object> {
-		let this = this.call_downcast::<Iter>()?.clone();
+		let this = Iter::from_object(this)?;
 		let block = args.try_arg(0)?.clone();
 
 		Ok(this.drop_while(move |obj| {
@@ -208,7 +216,7 @@ impl Iterable {
 	/// Group every `n` (ie the first argument) into an array.
 	#[instrument(name="Iterable::chunk", level="trace", skip(this, args), fields(self=?this, ?args))]
 	pub fn qs_chunk(this: &Object, args: Args) -> crate::Result<Object> {
-		let this = this.call_downcast::<Iter>()?.clone();
+		let this = Iter::from_object(this)?;
 		let amnt = args.try_arg(0)?.call_downcast::<Number>()?.truncate();
 
 		if amnt < 0 {
@@ -221,7 +229,7 @@ impl Iterable {
 	/// Chunk elements while the given block (ie first argument) evaluates to truncate.
 	#[instrument(name="Iterable::chunk_while", level="trace", skip(this, args), fields(self=?this, ?args))]
 	pub fn qs_chunk_while(this: &Object, args: Args) -> crate::Result<Object> {
-		let this = this.call_downcast::<Iter>()?.clone();
+		let this = Iter::from_object(this)?;
 		let block = args.try_arg(0)?.clone();
 
 		Ok(this.chunk_while(move |obj| {
@@ -234,7 +242,7 @@ impl Iterable {
 	/// Chunk elements while the given block (ie first argument) evaluates to false.
 	#[instrument(name="Iterable::chunk_until", level="trace", skip(this, args), fields(self=?this, ?args))]
 	pub fn qs_chunk_until(this: &Object, args: Args) -> crate::Result<Object> {
-		let this = this.call_downcast::<Iter>()?.clone();
+		let this = Iter::from_object(this)?;
 		let block = args.try_arg(0)?.clone();
 
 		Ok(this.chunk_while(move |obj| {
@@ -247,7 +255,7 @@ impl Iterable {
 
 	#[instrument(name="Iterable::cycle", level="trace", skip(this, args), fields(self=?this, ?args))]
 	pub fn qs_cycle(this: &Object, args: Args) -> crate::Result<Object> {
-		let this = this.call_downcast::<Iter>()?.clone();
+		let this = Iter::from_object(this)?;
 
 		Ok(this.cycle(
 			if let Some(arg) = args.arg(0) {
@@ -288,7 +296,7 @@ impl Iterable {
 
 	#[instrument(name="Iterable::sum", level="trace", skip(this), fields(self=?this))]
 	pub fn qs_sum(this: &Object, _: Args) -> crate::Result<Object> {
-		let mut this = this.call_downcast::<Iter>()?.clone();
+		let mut this = Iter::from_object(this)?;
 
 		if let Some(init) = this.next() {
 			this.reduce(init?, |acc, new| acc.call_attr_lit("+", &[&new]))
@@ -299,7 +307,7 @@ impl Iterable {
 
 	#[instrument(name="Iterable::prod", level="trace", skip(this), fields(self=?this))]
 	pub fn qs_prod(this: &Object, _: Args) -> crate::Result<Object> {
-		let mut this = this.call_downcast::<Iter>()?.clone();
+		let mut this = Iter::from_object(this)?;
 
 		if let Some(init) = this.next() {
 			this.reduce(init?, |acc, new| acc.call_attr_lit("*", &[&new]))
@@ -313,7 +321,7 @@ impl Iterable {
 	pub fn qs_unique(this: &Object, args: Args) -> crate::Result<Object> {
 		if args.len() != 0 { panic!("todo: nonzero args."); }
 
-		let this = this.call_downcast::<Iter>()?.clone();
+		let this = Iter::from_object(this)?;
 
 		let mut unique = Vec::<Object>::new();
 
@@ -379,7 +387,7 @@ impl Iterable {
 	/// Gets the amount of elements in this iterator.
 	#[instrument(name="Iterable::len", level="trace", skip(this), fields(self=?this))]
 	pub fn qs_len(this: &Object, _: Args) -> crate::Result<Object> {
-		let this = this.call_downcast::<Iter>()?.clone();
+		let this = Iter::from_object(this)?;
 		let mut i = 0;
 
 		for ele in this {
@@ -411,7 +419,7 @@ impl Iterable {
 			return Self::qs_take(this, args);
 		}
 
-		let this = this.call_downcast::<Iter>()?.clone();
+		let this = Iter::from_object(this)?;
 		Ok(this.take(1).into())
 	}
 
@@ -428,7 +436,7 @@ impl Iterable {
 
 	#[instrument(name="Iterable::all?", level="trace", skip(this, args), fields(self=?this, ?args))]
 	pub fn qs_all_q(this: &Object, args: Args) -> crate::Result<Object> {
-		let mut this = this.call_downcast::<Iter>()?.clone();
+		let mut this = Iter::from_object(this)?;
 
 		if let Some(block) = args.arg(0) {
 			let block = block.clone();
@@ -443,7 +451,7 @@ impl Iterable {
 
 	#[instrument(name="Iterable::any?", level="trace", skip(this, args), fields(self=?this, ?args))]
 	pub fn qs_any_q(this: &Object, args: Args) -> crate::Result<Object> {
-		let mut this = this.call_downcast::<Iter>()?.clone();
+		let mut this = Iter::from_object(this)?;
 
 		if let Some(block) = args.arg(0) {
 			let block = block.clone();
@@ -458,7 +466,7 @@ impl Iterable {
 
 	#[instrument(name="Iterable::one?", level="trace", skip(this, args), fields(self=?this, ?args))]
 	pub fn qs_one_q(this: &Object, args: Args) -> crate::Result<Object> {
-		let mut this = this.call_downcast::<Iter>()?.clone();
+		let mut this = Iter::from_object(this)?;
 
 		if let Some(block) = args.arg(0) {
 			let block = block.clone();
@@ -473,7 +481,7 @@ impl Iterable {
 
 	#[instrument(name="Iterable::none?", level="trace", skip(this, args), fields(self=?this, ?args))]
 	pub fn qs_none_q(this: &Object, args: Args) -> crate::Result<Object> {
-		let mut this = this.call_downcast::<Iter>()?.clone();
+		let mut this = Iter::from_object(this)?;
 
 		if let Some(block) = args.arg(0) {
 			let block = block.clone();
@@ -488,7 +496,7 @@ impl Iterable {
 
 	#[instrument(name="Iterable::include?", level="trace", skip(this, args), fields(self=?this, ?args))]
 	pub fn qs_include_q(this: &Object, args: Args) -> crate::Result<Object> {
-		let mut this = this.call_downcast::<Iter>()?.clone();
+		let mut this = Iter::from_object(this)?;
 		let to_find = args.try_arg(0)?.clone();
 
 		this.any(move |obj|
@@ -501,6 +509,7 @@ impl Iterable {
 
 impl_object_type! { for Iterable [(parents super::Class)]:
 	"@list"       => method Self::qs_at_list,
+	"@set"        => method Self::qs_at_set,
 	"run"         => method Self::qs_run,
 	"enumerate"   => method Self::qs_enumerate,
 	"map"         => method Self::qs_map,
@@ -0,0 +1,166 @@
+//! Lazily iterating over the values a block [`yield`](yield_value)s.
+//!
+//! As blocks can't be suspended partway through, each generator's block runs on its own thread,
+//! which waits until the next value is asked for before continuing.
+
+use super::Iter;
+use crate::{Object, Binding, Error, Literal, Result};
+use crate::types::Scope;
+use parking_lot::Mutex;
+use std::cell::RefCell;
+use std::sync::Arc;
+use std::sync::mpsc::{self, Receiver, SyncSender};
+use std::thread;
+
+/// What the generator's thread sends back each time it's resumed.
+type Step = Result<Option<Object>>;
+
+/// The consumer's half of a generator.
+///
+/// When the last clone of the iterator is dropped, `resume` is dropped too, which stops the block
+/// the next time it yields.
+#[derive(Debug)]
+struct Consumer {
+	resume: SyncSender<()>,
+	steps: Receiver<Step>,
+	finished: bool
+}
+
+/// The generator's half, which is stored in a thread local while the block runs.
+struct Producer {
+	resume: Receiver<()>,
+	steps: SyncSender<Step>,
+	/// The binding that's "returned" to when the consumer goes away, which unwinds the block.
+	cancel: Binding
+}
+
+thread_local! {
+	static PRODUCER: RefCell<Option<Producer>> = const { RefCell::new(None) };
+}
+
+impl Consumer {
+	fn next(&mut self) -> Option<Result<Object>> {
+		if self.finished {
+			return None;
+		}
+
+		let step = self.resume.send(()).ok().and_then(|()| self.steps.recv().ok());
+
+		match step {
+			Some(Ok(Some(value))) => Some(Ok(value)),
+			Some(Err(err)) => { self.finished = true; Some(Err(err)) },
+			Some(Ok(None)) | None => { self.finished = true; None }
+		}
+	}
+}
+
+impl Iter {
+	/// Creates an iterator over the values that `block` [yields](yield_value).
+	///
+	/// The block isn't started until the first value is asked for, and is paused after each yield
+	/// until the next one is. If the iterator is dropped before the block finishes, the block is
+	/// unwound the next time it yields, which runs any `ensure` blocks it's in.
+	///
+	/// Clones of the iterator share the same block, so advancing one advances all of them.
+	pub fn generator(block: Object) -> Self {
+		let (resume_tx, resume_rx) = mpsc::sync_channel(0);
+		let (steps_tx, steps_rx) = mpsc::sync_channel(0);
+
+		thread::spawn(move || {
+			// if the iterator was dropped before it was ever used, don't run the block at all.
+			if resume_rx.recv().is_err() {
+				return;
+			}
+
+			let cancel = Binding::from(Object::new(Scope));
+			let steps = steps_tx.clone();
+
+			PRODUCER.with(|producer| {
+				*producer.borrow_mut() = Some(Producer { resume: resume_rx, steps: steps_tx, cancel: cancel.clone() });
+			});
+
+			let step =
+				match block.call_attr_lit(&Literal::CALL, &[]) {
+					Ok(_) => Ok(None),
+					Err(Error::Return { to, .. }) if to.is_identical(&cancel) => return,
+					Err(err) => Err(err)
+				};
+
+			let _ = steps.send(step);
+		});
+
+		let consumer = Arc::new(Mutex::new(Consumer { resume: resume_tx, steps: steps_rx, finished: false }));
+
+		Self::from_fn(move || consumer.lock().next())
+	}
+}
+
+/// Gives `value` to the generator this thread is running, and waits until the next value is asked
+/// for.
+///
+/// If the generator's iterator has been dropped, an error is returned that unwinds the block. It
+/// can't be caught, but `ensure` blocks are still run.
+pub fn yield_value(value: Object) -> Result<()> {
+	PRODUCER.with(|producer| {
+		let producer = producer.borrow();
+		let producer = producer.as_ref()
+			.ok_or_else(|| Error::Messaged("yield called outside of a generator".into()))?;
+
+		let cancelled = Error::Return { to: producer.cancel.clone(), obj: Object::default() };
+
+		if producer.steps.send(Ok(Some(value))).is_err() || producer.resume.recv().is_err() {
+			return Err(cancelled);
+		}
+
+		Ok(())
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::types::{Number, RustClosure};
+	use std::sync::atomic::{AtomicBool, Ordering};
+
+	fn counter(limit: i64, finished: Arc<AtomicBool>) -> Iter {
+		Iter::generator(RustClosure::new(move |_| {
+			let result = (0..limit).try_for_each(|i| yield_value(i.into()));
+			finished.store(true, Ordering::SeqCst);
+			result.map(|()| Object::default())
+		}).into())
+	}
+
+	#[test]
+	fn yields_lazily() {
+		crate::init();
+
+		let finished = Arc::new(AtomicBool::new(false));
+		let values = counter(3, finished.clone()).collect::<Result<Vec<_>>>().unwrap();
+		let values = values.iter().map(|value| value.downcast::<Number>().unwrap().clone()).collect::<Vec<_>>();
+
+		assert_eq!(values, vec![Number::from(0), Number::from(1), Number::from(2)]);
+		assert!(finished.load(Ordering::SeqCst));
+	}
+
+	#[test]
+	fn dropping_stops_the_block() {
+		crate::init();
+
+		let finished = Arc::new(AtomicBool::new(false));
+		let mut iter = counter(i64::MAX, finished.clone());
+
+		assert!(iter.next().is_some());
+		drop(iter);
+
+		let start = std::time::Instant::now();
+		while !finished.load(Ordering::SeqCst) {
+			assert!(start.elapsed() < std::time::Duration::from_secs(5), "the block never stopped");
+			thread::sleep(std::time::Duration::from_millis(1));
+		}
+	}
+
+	#[test]
+	fn yield_outside_of_generator() {
+		assert!(yield_value(Object::default()).is_err());
+	}
+}
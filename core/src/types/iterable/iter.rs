@@ -81,15 +81,46 @@ impl Iter {
 		Self::new(iter.into_iter().map(Ok))
 	}
 
-	/// Creates an iterator from an object. The object will be repeatedly called until it returns
-	/// [`StopIteration`].
+	/// Creates an iterator from an object. The object's `next` method, or the object itself if it
+	/// doesn't have one, will be repeatedly called until it returns or throws [`StopIteration`].
 	pub fn from_callable(obj: Object) -> Self {
+		let func = if matches!(obj.has_attr_lit("next"), Ok(true)) { "next" } else { "()" };
+
 		Self::new(std::iter::from_fn(move ||
-			obj.call_attr_lit("()", &[])
-				.map(|result| if result.is_a::<StopIteration>() { None } else { Some(result) })
-				.transpose()
-			)
-		)
+			match obj.call_attr_lit(func, &[]) {
+				Ok(result) if StopIteration::is_stop(&result) => None,
+				Err(err) if matches!(err.thrown(), Some(thrown) if StopIteration::is_stop(thrown)) => None,
+				other => Some(other)
+			}
+		))
+	}
+
+	/// Gets the iterator for an object.
+	///
+	/// If `obj` isn't an [`Iter`] already, its `@iter` is called, which may either return an
+	/// [`Iter`] or any object that responds to `next` (see [`Iter::from_callable`]).
+	pub fn from_object(obj: &Object) -> Result<Self> {
+		use crate::types::Convertible;
+
+		if let Some(iter) = obj.downcast::<Self>() {
+			return Ok(Clone::clone(&*iter));
+		}
+
+		let iter = obj.call_attr_lit(&Self::CONVERT_FUNC, &[])?;
+
+		let native = iter.downcast::<Self>().map(|native| Clone::clone(&*native));
+
+		if let Some(native) = native {
+			Ok(native)
+		} else if iter.has_attr_lit("next")? {
+			Ok(Self::from_callable(iter))
+		} else {
+			Err(crate::error::TypeError::ConversionReturnedBadType {
+				func: Self::CONVERT_FUNC,
+				expected: std::any::type_name::<Self>(),
+				got: iter.typename()
+			}.into())
+		}
 	}
 }
 
@@ -417,6 +448,7 @@ impl Iter {
 
 impl_object_type! { for Iter [(parents super::Iterable) (convert "@iter")]:
 	"()" => method Self::qs_call,
+	"next" => method Self::qs_call,
 	"@iter" => method Self::qs_at_iter,
 	"run" => method Self::qs_run,
 }
@@ -427,4 +459,12 @@ impl_object_type! { for Iter [(parents super::Iterable) (convert "@iter")]:
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
 pub struct StopIteration;
 
+impl StopIteration {
+	/// Checks to see if `obj` signals the end of iteration, which is either an instance of
+	/// [`StopIteration`] or the `StopIteration` class itself.
+	pub fn is_stop(obj: &Object) -> bool {
+		obj.is_a::<Self>() || obj.is_identical(<Self as crate::types::ObjectType>::mapping())
+	}
+}
+
 impl_object_type!(for StopIteration [(parents crate::types::Basic)]:);
@@ -0,0 +1,580 @@
+//! Reading and writing JSON.
+
+use crate::{Object, Args};
+use crate::error::{ValueError, TypeError};
+use crate::types::{Text, Number, Boolean, Null, List, Map};
+use crate::types::number::FloatType;
+use std::convert::TryFrom;
+use std::fmt::{self, Display, Formatter, Write};
+use tracing::instrument;
+
+/// The namespace for the JSON functions.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Json;
+
+/// How deeply arrays and objects can be nested before we give up, so that malicious input can't
+/// overflow the stack.
+const MAX_DEPTH: usize = 512;
+
+/// An error that occurred while parsing JSON.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+	/// The line the error occurred on, starting at 1.
+	pub line: usize,
+
+	/// The column the error occurred on, starting at 1.
+	pub column: usize,
+
+	/// What went wrong.
+	pub message: String
+}
+
+impl Display for ParseError {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		write!(f, "{} at line {}, column {}", self.message, self.line, self.column)
+	}
+}
+
+impl std::error::Error for ParseError {}
+
+impl From<ParseError> for crate::Error {
+	fn from(err: ParseError) -> Self {
+		ValueError::Messaged(format!("invalid json: {}", err)).into()
+	}
+}
+
+struct Parser<'a> {
+	src: &'a str,
+	pos: usize,
+	line: usize,
+	column: usize,
+	depth: usize
+}
+
+impl<'a> Parser<'a> {
+	fn new(src: &'a str) -> Self {
+		Self { src, pos: 0, line: 1, column: 1, depth: 0 }
+	}
+
+	fn error(&self, message: impl Into<String>) -> ParseError {
+		ParseError { line: self.line, column: self.column, message: message.into() }
+	}
+
+	fn unexpected(&self) -> ParseError {
+		match self.peek() {
+			Some(chr) => self.error(format!("unexpected character {:?}", chr)),
+			None => self.error("unexpected end of input")
+		}
+	}
+
+	fn peek(&self) -> Option<char> {
+		self.src[self.pos..].chars().next()
+	}
+
+	fn advance(&mut self) -> Option<char> {
+		let chr = self.peek()?;
+		self.pos += chr.len_utf8();
+
+		if chr == '\n' {
+			self.line += 1;
+			self.column = 1;
+		} else {
+			self.column += 1;
+		}
+
+		Some(chr)
+	}
+
+	fn expect(&mut self, expected: char) -> Result<(), ParseError> {
+		if self.peek() == Some(expected) {
+			self.advance();
+			Ok(())
+		} else {
+			Err(self.unexpected())
+		}
+	}
+
+	fn skip_whitespace(&mut self) {
+		while let Some(' ') | Some('\t') | Some('\n') | Some('\r') = self.peek() {
+			self.advance();
+		}
+	}
+
+	fn parse_document(&mut self) -> Result<Object, ParseError> {
+		let value = self.parse_value()?;
+		self.skip_whitespace();
+
+		if self.peek().is_some() {
+			Err(self.unexpected())
+		} else {
+			Ok(value)
+		}
+	}
+
+	fn parse_value(&mut self) -> Result<Object, ParseError> {
+		self.skip_whitespace();
+
+		match self.peek() {
+			Some('{') => self.nested(Self::parse_object),
+			Some('[') => self.nested(Self::parse_array),
+			Some('"') => self.parse_string().map(Object::from),
+			Some('t') => self.parse_keyword("true", true.into()),
+			Some('f') => self.parse_keyword("false", false.into()),
+			Some('n') => self.parse_keyword("null", Null.into()),
+			Some('-') | Some('0'..='9') => self.parse_number(),
+			_ => Err(self.unexpected())
+		}
+	}
+
+	fn nested(&mut self, parse: fn(&mut Self) -> Result<Object, ParseError>) -> Result<Object, ParseError> {
+		if self.depth == MAX_DEPTH {
+			return Err(self.error("nesting is too deep"));
+		}
+
+		self.depth += 1;
+		let result = parse(self);
+		self.depth -= 1;
+		result
+	}
+
+	fn parse_keyword(&mut self, keyword: &str, value: Object) -> Result<Object, ParseError> {
+		for expected in keyword.chars() {
+			self.expect(expected)?;
+		}
+
+		Ok(value)
+	}
+
+	fn parse_number(&mut self) -> Result<Object, ParseError> {
+		let (line, column, start) = (self.line, self.column, self.pos);
+
+		macro_rules! digits {
+			() => {{
+				if !matches!(self.peek(), Some('0'..='9')) {
+					return Err(self.unexpected());
+				}
+
+				while let Some('0'..='9') = self.peek() {
+					self.advance();
+				}
+			}};
+		}
+
+		if self.peek() == Some('-') {
+			self.advance();
+		}
+
+		if self.peek() == Some('0') {
+			self.advance();
+		} else {
+			digits!();
+		}
+
+		if self.peek() == Some('.') {
+			self.advance();
+			digits!();
+		}
+
+		if let Some('e') | Some('E') = self.peek() {
+			self.advance();
+
+			if let Some('+') | Some('-') = self.peek() {
+				self.advance();
+			}
+
+			digits!();
+		}
+
+		// integers that don't fit are parsed as big integers, just like number literals are.
+		Number::try_from(&self.src[start..self.pos])
+			.map(Object::from)
+			.map_err(|err| ParseError { line, column, message: err.to_string() })
+	}
+
+	fn parse_hex_escape(&mut self) -> Result<u32, ParseError> {
+		let mut code = 0;
+
+		for _ in 0..4 {
+			let digit = self.peek()
+				.and_then(|chr| chr.to_digit(16))
+				.ok_or_else(|| self.error("invalid unicode escape"))?;
+
+			self.advance();
+			code = code * 16 + digit;
+		}
+
+		Ok(code)
+	}
+
+	fn parse_string(&mut self) -> Result<Text, ParseError> {
+		self.expect('"')?;
+
+		let mut text = String::new();
+
+		loop {
+			match self.peek() {
+				Some('"') => {
+					self.advance();
+					return Ok(text.into());
+				},
+				Some('\\') => {
+					self.advance();
+
+					let escaped =
+						match self.advance() {
+							Some('"') => '"',
+							Some('\\') => '\\',
+							Some('/') => '/',
+							Some('b') => '\u{8}',
+							Some('f') => '\u{c}',
+							Some('n') => '\n',
+							Some('r') => '\r',
+							Some('t') => '\t',
+							Some('u') => self.parse_unicode_escape()?,
+							_ => return Err(self.error("invalid escape sequence"))
+						};
+
+					text.push(escaped);
+				},
+				Some(chr) if chr < ' ' => return Err(self.error("control character in string")),
+				Some(chr) => {
+					self.advance();
+					text.push(chr);
+				},
+				None => return Err(self.error("unterminated string"))
+			}
+		}
+	}
+
+	fn parse_unicode_escape(&mut self) -> Result<char, ParseError> {
+		let high = self.parse_hex_escape()?;
+
+		let code =
+			if (0xd800..0xdc00).contains(&high) {
+				if self.advance() != Some('\\') || self.advance() != Some('u') {
+					return Err(self.error("unpaired surrogate in unicode escape"));
+				}
+
+				let low = self.parse_hex_escape()?;
+
+				if !(0xdc00..0xe000).contains(&low) {
+					return Err(self.error("unpaired surrogate in unicode escape"));
+				}
+
+				0x10000 + ((high - 0xd800) << 10) + (low - 0xdc00)
+			} else {
+				high
+			};
+
+		std::char::from_u32(code).ok_or_else(|| self.error("unpaired surrogate in unicode escape"))
+	}
+
+	fn parse_array(&mut self) -> Result<Object, ParseError> {
+		self.expect('[')?;
+		self.skip_whitespace();
+
+		let mut list = Vec::new();
+
+		if self.peek() == Some(']') {
+			self.advance();
+			return Ok(list.into());
+		}
+
+		loop {
+			list.push(self.parse_value()?);
+			self.skip_whitespace();
+
+			match self.advance() {
+				Some(',') => continue,
+				Some(']') => return Ok(list.into()),
+				_ => return Err(self.error("expected ',' or ']' after array element"))
+			}
+		}
+	}
+
+	fn parse_object(&mut self) -> Result<Object, ParseError> {
+		self.expect('{')?;
+		self.skip_whitespace();
+
+		let mut map = Map::new();
+
+		if self.peek() == Some('}') {
+			self.advance();
+			return Ok(map.into());
+		}
+
+		loop {
+			self.skip_whitespace();
+
+			if self.peek() != Some('"') {
+				return Err(self.error("expected a string key"));
+			}
+
+			let key = self.parse_string()?;
+			self.skip_whitespace();
+			self.expect(':')?;
+			let value = self.parse_value()?;
+
+			// inserting `Text` keys can't fail, as hashing and comparing them never calls quest code.
+			map.insert(key.into(), value).expect("inserting a text key failed");
+			self.skip_whitespace();
+
+			match self.advance() {
+				Some(',') => continue,
+				Some('}') => return Ok(map.into()),
+				_ => return Err(self.error("expected ',' or '}' after object member"))
+			}
+		}
+	}
+}
+
+/// Parses a JSON document.
+///
+/// Objects become [`Map`]s with [`Text`] keys, and arrays become [`List`]s.
+pub fn parse(input: &str) -> Result<Object, ParseError> {
+	Parser::new(input).parse_document()
+}
+
+struct Dumper {
+	out: String,
+	pretty: bool,
+	stack: Vec<Object>
+}
+
+impl Dumper {
+	fn newline(&mut self) {
+		if self.pretty {
+			self.out.push('\n');
+
+			for _ in 0..self.stack.len() {
+				self.out.push_str("  ");
+			}
+		}
+	}
+
+	fn dump_text(&mut self, text: &str) {
+		self.out.push('"');
+
+		for chr in text.chars() {
+			match chr {
+				'"' => self.out.push_str("\\\""),
+				'\\' => self.out.push_str("\\\\"),
+				'\n' => self.out.push_str("\\n"),
+				'\r' => self.out.push_str("\\r"),
+				'\t' => self.out.push_str("\\t"),
+				'\u{8}' => self.out.push_str("\\b"),
+				'\u{c}' => self.out.push_str("\\f"),
+				chr if chr < ' ' => { let _ = write!(self.out, "\\u{:04x}", chr as u32); },
+				chr => self.out.push(chr)
+			}
+		}
+
+		self.out.push('"');
+	}
+
+	fn dump_number(&mut self, num: &Number) -> crate::Result<()> {
+		// integers are written exactly, no matter how large they are.
+		if num.try_to_big().is_ok() {
+			let _ = write!(self.out, "{}", num);
+			return Ok(());
+		}
+
+		let float = FloatType::from(num.clone());
+
+		if !float.is_finite() {
+			return Err(ValueError::Messaged(format!("{} cannot be represented in json", num)).into());
+		}
+
+		let _ = write!(self.out, "{:?}", float);
+		Ok(())
+	}
+
+	/// Writes each element of `elements`, surrounded by `open` and `close`.
+	fn dump_container<T>(&mut self, open: char, close: char, elements: Vec<T>,
+		mut dump: impl FnMut(&mut Self, T) -> crate::Result<()>) -> crate::Result<()>
+	{
+		self.out.push(open);
+
+		if !elements.is_empty() {
+			for (idx, element) in elements.into_iter().enumerate() {
+				if idx != 0 {
+					self.out.push(',');
+				}
+
+				self.newline();
+				dump(self, element)?;
+			}
+
+			self.stack.pop();
+			self.newline();
+			self.out.push(close);
+			return Ok(());
+		}
+
+		self.stack.pop();
+		self.out.push(close);
+		Ok(())
+	}
+
+	fn dump(&mut self, obj: &Object) -> crate::Result<()> {
+		if obj.is_a::<Null>() {
+			self.out.push_str("null");
+			return Ok(());
+		} else if let Some(boolean) = obj.downcast::<Boolean>() {
+			self.out.push_str(if bool::from(*boolean) { "true" } else { "false" });
+			return Ok(());
+		} else if let Some(num) = obj.downcast::<Number>() {
+			return self.dump_number(&num);
+		} else if let Some(text) = obj.downcast::<Text>() {
+			self.dump_text(text.as_ref());
+			return Ok(());
+		}
+
+		if self.stack.iter().any(|parent| parent.is_identical(obj)) {
+			return Err(ValueError::Messaged("cannot convert a recursive structure to json".to_string()).into());
+		}
+
+		// we copy the elements out so that we don't hold a lock on `obj` while dumping them.
+		if let Some(list) = obj.downcast::<List>().map(|list| list.iter().cloned().collect::<Vec<_>>()) {
+			self.stack.push(obj.clone());
+			self.dump_container('[', ']', list, |this, element| this.dump(&element))
+		} else if let Some(map) = obj.downcast::<Map>()
+			.map(|map| map.iter().map(|(k, v)| (k.clone(), v.clone())).collect::<Vec<_>>())
+		{
+			self.stack.push(obj.clone());
+			self.dump_container('{', '}', map, |this, (key, value)| {
+				this.dump_text(key.call_downcast::<Text>()?.as_ref());
+				this.out.push_str(if this.pretty { ": " } else { ":" });
+				this.dump(&value)
+			})
+		} else if obj.has_attr_lit("@json")? {
+			let json = obj.call_attr_lit("@json", &[])?;
+
+			self.stack.push(obj.clone());
+			let result = self.dump(&json);
+			self.stack.pop();
+			result
+		} else {
+			Err(TypeError::Messaged(format!("cannot convert a {} to json", obj.typename())).into())
+		}
+	}
+}
+
+/// Converts `obj` to JSON.
+///
+/// [`Null`], [`Boolean`], [`Number`], [`Text`], [`List`], and [`Map`] are converted directly (map keys
+/// are converted to [`Text`]), and any other object is converted by calling its `@json` attribute
+/// and converting the result. If `pretty` is set, the output is indented by two spaces per level.
+pub fn dump(obj: &Object, pretty: bool) -> crate::Result<String> {
+	let mut dumper = Dumper { out: String::new(), pretty, stack: Vec::new() };
+
+	dumper.dump(obj)?;
+
+	Ok(dumper.out)
+}
+
+/// Quest methods
+impl Json {
+	/// Parses a JSON document.
+	///
+	/// Objects become [`Map`]s with [`Text`] keys, and integers too large for a regular [`Number`]
+	/// become big integers.
+	///
+	/// # Arguments
+	/// 1. (required, `@text`) The JSON to parse.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// $data = Json.parse('{"name": "quest", "tags": [1, 2.5, null]}');
+	/// assert((data["name"]) == "quest");
+	/// $tags = data["tags"];
+	/// assert(tags == [1, 2.5, null]);
+	/// ```
+	#[instrument(name="Json::parse", level="trace", skip(args), fields(?args))]
+	pub fn qs_parse(_: &Object, args: Args) -> crate::Result<Object> {
+		let text = args.try_arg(0)?.call_downcast::<Text>()?;
+
+		Ok(parse(text.as_ref())?)
+	}
+
+	/// Converts an object to JSON.
+	///
+	/// See [`dump`] for how objects are converted. Recursive structures raise an error.
+	///
+	/// # Arguments
+	/// 1. (required) The object to convert.
+	/// 2. (optional, `@bool`) Whether to pretty-print the output; defaults to `false`.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// assert(Json.dump([1, "two", true]) == '[1,"two",true]');
+	/// ```
+	#[instrument(name="Json::dump", level="trace", skip(args), fields(?args))]
+	pub fn qs_dump(_: &Object, args: Args) -> crate::Result<Object> {
+		let obj = args.try_arg(0)?;
+		let pretty =
+			if let Some(pretty) = args.arg(1) {
+				bool::from(*pretty.call_downcast::<Boolean>()?)
+			} else {
+				false
+			};
+
+		Ok(Text::from(dump(obj, pretty)?).into())
+	}
+}
+
+impl_object_type!{
+for Json [(parents super::Basic)]:
+	"parse" => method Self::qs_parse,
+	"dump" => method Self::qs_dump,
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn round_trip() {
+		crate::init();
+
+		let input = r#"{"a":[1,-2.5,1e3,true,false,null],"b":"esc\"aped\né😀","c":{}}"#;
+		let parsed = parse(input).unwrap();
+
+		assert_eq!(dump(&parsed, false).unwrap(),
+			"{\"a\":[1,-2.5,1000,true,false,null],\"b\":\"esc\\\"aped\\n\u{e9}\u{1f600}\",\"c\":{}}");
+		assert_eq!(dump(&parse("[[], [1]]").unwrap(), true).unwrap(), "[\n  [],\n  [\n    1\n  ]\n]");
+	}
+
+	#[test]
+	fn big_numbers() {
+		crate::init();
+
+		let big = parse("123456789012345678901234567890").unwrap();
+		assert!(big.downcast::<Number>().unwrap().is_big());
+		assert_eq!(dump(&big, false).unwrap(), "123456789012345678901234567890");
+		assert!(dump(&Number::INF.into(), false).is_err());
+	}
+
+	#[test]
+	fn errors() {
+		assert_eq!(parse("[1,\n  2,,]").unwrap_err(),
+			ParseError { line: 2, column: 5, message: "unexpected character ','".to_string() });
+		assert_eq!(parse("\"abc").unwrap_err().message, "unterminated string");
+		assert_eq!(parse("[1] 2").unwrap_err().column, 5);
+		assert_eq!(parse(&"[".repeat(MAX_DEPTH + 1)).unwrap_err().message, "nesting is too deep");
+	}
+
+	#[test]
+	fn cycles() {
+		crate::init();
+
+		let list = Object::from(vec![Object::from(1)]);
+		list.downcast_mut::<List>().unwrap().push(list.clone());
+
+		assert!(dump(&list, false).is_err());
+
+		// the same object appearing twice isn't a cycle.
+		let inner = Object::from(vec![]);
+		assert_eq!(dump(&vec![inner.clone(), inner].into(), false).unwrap(), "[[],[]]");
+	}
+}
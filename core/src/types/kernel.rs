@@ -1,5 +1,5 @@
 use crate::{Args, Object, Error, Literal, Binding};
-use crate::types::{Boolean, Text, Null, Number};
+use crate::types::{Boolean, Text, Null, Number, Exception};
 use tracing::instrument;
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -30,6 +30,36 @@ fn is_object_truthy(object: &Object) -> crate::Result<bool> {
 	object.call_downcast::<Boolean>().map(|b| b.into_inner())
 }
 
+/// Calls `body`, converting any errors (other than [`Error::Return`]s) it raises into exceptions.
+///
+/// If `handler` is given, it's called with the exception and its result is returned; otherwise the
+/// exception itself is returned.
+fn call_catching(body: &Object, handler: Option<&Object>) -> crate::Result<Object> {
+	match body.call_attr_lit(&Literal::CALL, &[]) {
+		Err(err @ Error::Return { .. }) => Err(err),
+		Err(err) => {
+			let exception = Exception::from_error(err, Binding::instance());
+
+			tracing::debug!(?exception, "caught exception");
+
+			if let Some(handler) = handler {
+				handler.call_attr_lit(&Literal::CALL, &[&exception])
+			} else {
+				Ok(exception)
+			}
+		},
+		ok => ok
+	}
+}
+
+/// Calls `ensure` regardless of what `result` is, and then returns `result`.
+///
+/// If `ensure` itself fails, its error is returned instead.
+fn call_ensure(result: crate::Result<Object>, ensure: &Object) -> crate::Result<Object> {
+	ensure.call_attr_lit(&Literal::CALL, &[])?;
+	result
+}
+
 #[inline]
 fn object_to_string(object: &Object) -> crate::Result<String> {
 	object.call_downcast::<Text>().map(|t| t.to_string())
@@ -137,6 +167,59 @@ impl Kernel {
 		Ok(result.unwrap_or_default())
 	}
 
+	/// Matches a value against a series of patterns, returning the result of the first arm that
+	/// matches.
+	///
+	/// The block declares the arms with `case(pattern, body)` or `case(pattern, guard, body)`, and
+	/// can use `any`, `bind`, `rest` and `attrs` to build patterns. Variables bound by the pattern
+	/// are available in the guard and body. See [`Pattern`](super::Pattern) for how patterns are
+	/// matched.
+	///
+	/// An error is raised if no arm matches.
+	///
+	/// # Arguments
+	/// 1. (required, `this`) The value to match.
+	/// 2. (required) The block declaring the arms.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// $describe = {
+	/// 	match(_0) {
+	/// 		case(0, { "zero" });
+	/// 		case(bind("n", Number), { n < 0 }, { "negative" });
+	/// 		case(Number, { "positive" });
+	/// 		case([], { "empty" });
+	/// 		case([bind("head"), rest("tail")], { "list of " + (tail.len() + 1) });
+	/// 		case(attrs("name", bind("name", Text)), { "named " + name });
+	/// 		case(any, { "something else" });
+	/// 	}
+	/// };
+	///
+	/// assert(describe(0) == "zero");
+	/// assert(describe(-3) == "negative");
+	/// assert(describe([1, 2, 3]) == "list of 3");
+	/// assert(describe(object() { name = "sam"; }) == "named sam");
+	/// ```
+	#[instrument(name="Kernel::match", level="trace", skip(value, args), fields(?value, ?args))]
+	pub fn qs_match(value: &Object, args: Args) -> crate::Result<Object> {
+		super::pattern::run_match(value, args.try_arg(0)?)
+	}
+
+	/// Calls the block with each element of the iterable, returning the last result.
+	///
+	/// The iterable can be anything that follows the [iteration protocol](super::Iter::from_object).
+	#[instrument(name="Kernel::for", level="trace", skip(iterable, args), fields(?iterable, ?args))]
+	pub fn qs_for(iterable: &Object, args: Args) -> crate::Result<Object> {
+		let body = args.try_arg(0)?;
+		let mut result = None;
+
+		for ele in super::Iter::from_object(iterable)? {
+			result = Some(body.call_attr_lit(&Literal::CALL, &[&ele?])?);
+		}
+
+		Ok(result.unwrap_or_default())
+	}
+
 	#[instrument(name="Kernel::loop", level="trace")]
 	pub fn qs_loop(body: &Object, _: Args) -> crate::Result<Object> {
 		loop {
@@ -150,7 +233,7 @@ impl Kernel {
 
 		let code = 
 			if let Some(code) = args.arg(0) {
-				i32::try_from(*code.call_downcast::<Number>()?)?
+				i32::try_from(code.call_downcast::<Number>()?.clone())?
 			} else {
 				0
 			};
@@ -194,10 +277,10 @@ impl Kernel {
 		let mut end: FloatType = 1.0;
 
 		if let Some(start_num) = args.arg(0) {
-			start = (*start_num.call_downcast::<Number>()?).into();
+			start = start_num.call_downcast::<Number>()?.clone().into();
 
 			if let Some(end_num) = args.arg(1) {
-				end = (*end_num.call_downcast::<Number>()?).into();
+				end = end_num.call_downcast::<Number>()?.clone().into();
 			} else {
 				end = start;
 				start = 0.0;
@@ -256,26 +339,188 @@ impl Kernel {
 		Err(Error::AssertionFailed(msg))
 	}
 
-	#[instrument(name="Kernel::sleep", level="trace")]
-	pub fn qs_sleep(args: Args) -> crate::Result<Object> {
-		if let Some(arg) = args.arg(0) {
-			let dur: f64 = (*arg.call_downcast::<Number>()?).into();
-			std::thread::sleep(std::time::Duration::from_secs_f64(dur));
+	/// Calls `body`, and if it raises an exception, calls the `catch` block with it.
+	///
+	/// Regardless of what happens in `body` or `catch`---including `return`s---the `ensure` block is
+	/// always run afterwards. If no `catch` block is given (or it's `null`), exceptions propagate
+	/// after running `ensure`.
+	///
+	/// # Arguments
+	/// 1. (required) The body to run.
+	/// 2. (optional) The block to call with exceptions.
+	/// 3. (optional) The block to run afterwards.
+	#[instrument(name="Kernel::try", level="trace")]
+	pub fn qs_try(body: &Object, args: Args) -> crate::Result<Object> {
+		let handler = args.arg(0).filter(|handler| !handler.is_a::<Null>());
+
+		let result =
+			if let Some(handler) = handler {
+				call_catching(body, Some(handler))
+			} else {
+				body.call_attr_lit(&Literal::CALL, &[])
+			};
+
+		if let Some(ensure) = args.arg(1) {
+			call_ensure(result, ensure)
 		} else {
-			// technically doesn't sleep forever lol.
-			std::thread::sleep(std::time::Duration::from_secs_f64(f64::MAX));
+			result
 		}
+	}
+
+	/// Calls `body`, and if it raises an exception, calls `handler` with it.
+	///
+	/// If no handler is given, the exception is returned instead. Native errors are converted to
+	/// [`Exception`]s, whereas [thrown](Kernel::qs_throw) objects are passed as-is.
+	///
+	/// # Arguments
+	/// 1. (required) The body to run.
+	/// 2. (optional) The block to call with exceptions.
+	#[instrument(name="Kernel::catch", level="trace")]
+	pub fn qs_catch(body: &Object, args: Args) -> crate::Result<Object> {
+		call_catching(body, args.arg(0))
+	}
+
+	/// Calls `body`, and then calls `ensure` regardless of whether `body` succeeded, raised an
+	/// exception, or `return`ed.
+	///
+	/// # Arguments
+	/// 1. (required) The body to run.
+	/// 2. (required) The block to run afterwards.
+	#[instrument(name="Kernel::ensure", level="trace")]
+	pub fn qs_ensure(body: &Object, args: Args) -> crate::Result<Object> {
+		let ensure = args.try_arg(0)?;
+
+		call_ensure(body.call_attr_lit(&Literal::CALL, &[]), ensure)
+	}
+
+	/// Throws the first argument, which can be any object.
+	///
+	/// # Arguments
+	/// 1. (required) The object to throw.
+	#[instrument(name="Kernel::throw", level="trace")]
+	pub fn qs_throw(args: Args) -> crate::Result<Object> {
+		Err(Error::Thrown(args.try_arg(0)?.clone()))
+	}
+
+	/// Pauses the current thread.
+	///
+	/// # Arguments
+	/// 1. (optional, [`Duration`](super::Duration) or `@num`) How long to sleep for; numbers are
+	///    in seconds. If omitted, sleeps (practically) forever.
+	#[instrument(name="Kernel::sleep", level="trace")]
+	pub fn qs_sleep(args: Args) -> crate::Result<Object> {
+		let duration =
+			if let Some(arg) = args.arg(0) {
+				super::duration::to_std(arg)?
+			} else {
+				// technically doesn't sleep forever lol.
+				std::time::Duration::from_secs_f64(f64::MAX)
+			};
+
+		std::thread::sleep(duration);
 
 		Ok(Object::default())
 	}
 
+	/// Creates an [`Iter`](super::Iter) over the values that the block [`yield`](Kernel::qs_yield)s.
+	///
+	/// The block isn't run until the first value is asked for, and is paused after each `yield`
+	/// until the next one is, so generators can be infinite. If the iterator is dropped before the
+	/// block finishes, the block is stopped at its next `yield`; any `ensure` blocks it's in are
+	/// still run.
+	///
+	/// # Arguments
+	/// 1. (required) The block to run.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// $naturals = generator({
+	/// 	$n = 0;
+	/// 	while({ true }, { yield(n); n += 1; });
+	/// });
+	///
+	/// $expected = [0, 2, 4];
+	/// assert(naturals.map({ _0 * 2 }).take(3).@list() == expected);
+	/// ```
+	#[instrument(name="Kernel::generator", level="trace")]
+	pub fn qs_generator(args: Args) -> crate::Result<Object> {
+		Ok(super::Iter::generator(args.try_arg(0)?.clone()).into())
+	}
+
+	/// Gives a value to the [`generator`](Kernel::qs_generator) that's running the current block,
+	/// pausing until the next value is asked for.
+	///
+	/// It's an error to call this outside of a generator.
+	///
+	/// # Arguments
+	/// 1. (optional) The value to give; defaults to `null`.
+	#[instrument(name="Kernel::yield", level="trace")]
+	pub fn qs_yield(args: Args) -> crate::Result<Object> {
+		super::iterable::yield_value(args.arg(0).cloned().unwrap_or_default())?;
+
+		Ok(Object::default())
+	}
+
+	/// Opens a file, returning an [`Io::File`](super::io::File).
+	///
+	/// If a block is given, it's called with the file, which is then closed afterwards regardless
+	/// of whether the block succeeded, raised an exception, or `return`ed; the block's result is
+	/// returned instead of the file.
+	///
+	/// # Arguments
+	/// 1. (required, `@path` or [`Number`]) The path to open, or a file descriptor.
+	/// 2. (optional, [`Text`]) The mode, as accepted by [`Io::File`](super::io::File); defaults to
+	///    `"r"`. This can be omitted when a block is given.
+	/// 3. (optional) The block to call with the file.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// Io.Dir.temp({
+	/// 	$path = Path(_0) / "hello.txt";
+	///
+	/// 	open(path, "n", { _0.write("hello, world") });
+	/// 	assert(open(path, { _0.read() }) == "hello, world");
+	/// });
+	/// ```
 	#[instrument(name="Kernel::open", level="trace")]
-	pub fn qs_open(_: &Object, _args: Args) -> crate::Result<Object> {
-		// let filename = args.try_arg(0)?.downcast_call::<types::Text>();
-		todo!("open")
+	pub fn qs_open(path: &Object, args: Args) -> crate::Result<Object> {
+		let (mode, block) =
+			match (args.arg(0), args.arg(1)) {
+				(Some(mode), block @ Some(_)) => (Some(mode), block),
+				(Some(mode), None) if mode.is_a::<Text>() => (Some(mode), None),
+				(block, _) => (None, block)
+			};
+
+		let default_mode = Object::from("r");
+		let file = super::io::File::qs_call(path, Args::new(vec![path, mode.unwrap_or(&default_mode)]))?;
+
+		let block =
+			if let Some(block) = block {
+				block
+			} else {
+				return Ok(file);
+			};
+
+		let result = block.call_attr_lit(&Literal::CALL, &[&file]);
+
+		file.try_downcast_mut::<super::io::File>()?.close();
+
+		result
 	}
 }
 
+/// Gets the class of a type that only exists on Unix platforms, or `null` on other platforms.
+macro_rules! unix_only {
+	($ty:ident) => {{
+		#[cfg(unix)]
+		let class = super::$ty::mapping().clone();
+		#[cfg(not(unix))]
+		let class = Object::from(Null);
+
+		class
+	}};
+}
+
 impl_object_type!{
 for Kernel [(parents super::Basic)]: // todo: do i want its parent to be pristine?
 	"true" => const Boolean::new(true),
@@ -284,6 +529,10 @@ for Kernel [(parents super::Basic)]: // todo: do i want its parent to be pristin
 
 	"Io" => const super::Io::mapping().clone(),
 	"Tcp" => const super::Tcp::mapping().clone(),
+	"TcpServer" => const super::TcpServer::mapping().clone(),
+	"Udp" => const super::Udp::mapping().clone(),
+	"UnixSocket" => const unix_only!(UnixSocket),
+	"UnixListener" => const unix_only!(UnixListener),
 	"Basic" => const super::Basic::mapping().clone(),
 	"Boolean" => const super::Boolean::mapping().clone(),
 	"BoundFunction" => const super::BoundFunction::mapping().clone(),
@@ -291,22 +540,43 @@ for Kernel [(parents super::Basic)]: // todo: do i want its parent to be pristin
 	"RustClosure" => const super::RustClosure::mapping().clone(),
 	"Kernel" => const Kernel::mapping().clone(),
 	"List" => const super::List::mapping().clone(),
+	"Map" => const super::Map::mapping().clone(),
+	"Set" => const super::Set::mapping().clone(),
 	"Null" => const super::Null::mapping().clone(),
 	"Number" => const super::Number::mapping().clone(),
+	"Complex" => const super::Complex::mapping().clone(),
 	"Pristine" => const super::Pristine::mapping().clone(),
 	"RustFn" => const super::RustFn::mapping().clone(),
 	"Scope" => const super::Scope::mapping().clone(),
 	"Text" => const super::Text::mapping().clone(),
+	"Bytes" => const super::Bytes::mapping().clone(),
+	"Duration" => const super::Duration::mapping().clone(),
+	"Date" => const super::Date::mapping().clone(),
+	"Time" => const super::Time::mapping().clone(),
+	"Json" => const super::Json::mapping().clone(),
+	"Http" => const super::Http::mapping().clone(),
+	"HttpServer" => const super::HttpServer::mapping().clone(),
+	"Process" => const super::Process::mapping().clone(),
+	"Mutex" => const super::Mutex::mapping().clone(),
+	"Condvar" => const super::Condvar::mapping().clone(),
+	"Channel" => const super::Channel::mapping().clone(),
+	"Atomic" => const super::Atomic::mapping().clone(),
+	"Thread" => const super::Thread::mapping().clone(),
+	"ThreadPool" => const super::ThreadPool::mapping().clone(),
 	"Regex" => const super::Regex::mapping().clone(),
+	"Path" => const super::Path::mapping().clone(),
 	"Comparable" => const super::Comparable::mapping().clone(),
 	"Iterable" => const super::Iterable::mapping().clone(),
 	"Iter" => const super::Iter::mapping().clone(),
 	"StopIteration" => const super::StopIteration::mapping().clone(),
+	"Exception" => const super::Exception::mapping().clone(),
+	"Pattern" => const super::Pattern::mapping().clone(),
 
 	"if" => method Self::qs_if,
 	"ifl" => method Self::qs_ifl,
 	"unless" => method Self::qs_unless,
 	"unlessl" => method Self::qs_unlessl,
+	"match" => method Self::qs_match,
 	"disp" => function Self::qs_disp,
 	"dispn" => function Self::qs_dispn,
 	"print" => function Self::qs_disp,
@@ -317,11 +587,18 @@ for Kernel [(parents super::Basic)]: // todo: do i want its parent to be pristin
 	"prompt" => function Self::qs_prompt,
 	"while" => method Self::qs_while,
 	"until" => method Self::qs_until,
+	"for" => method Self::qs_for,
 	"loop" => method Self::qs_loop,
 	"sleep" => function Self::qs_sleep,
 	"open" => method Self::qs_open,
+	"generator" => function Self::qs_generator,
+	"yield" => function Self::qs_yield,
 	"return" => function Self::qs_return,
 	"assert" => method Self::qs_assert,
+	"try" => method Self::qs_try,
+	"catch" => method Self::qs_catch,
+	"ensure" => method Self::qs_ensure,
+	"throw" => function Self::qs_throw,
 
 	"__set_stack__" => method |stack, _| {
 		let stack = stack.call_downcast::<crate::types::List>()?;
@@ -346,27 +623,12 @@ for Kernel [(parents super::Basic)]: // todo: do i want its parent to be pristin
 		object.call_attr_lit("instance_exec", &[body]).and(Ok(object))
 	},
 
-	"spawn" => method |block, _| {
-		use std::thread::{self, JoinHandle};
-		use std::sync::Arc;
-		use parking_lot::Mutex;
-		#[derive(Debug, Clone)]
-		struct Thread(Arc<Mutex<Option<JoinHandle<crate::Result<Object>>>>>);
-
-		impl_object_type! { for Thread [(parents super::Basic)]:
-			"join" => method |this, _| this.try_downcast::<Thread>().and_then(|this| {
-				this.0.lock().take().expect("no join handle?")
-					.join()
-					.unwrap()
-			})
-		}
-
-		Thread::initialize().unwrap();
+	"spawn" => method |block, args| {
+		// any extra arguments are passed along to the block, after the block itself.
+		let mut block_args = vec![block.clone()];
+		block_args.extend(args.iter().cloned());
 
-		let block = block.clone();
-		Ok(Thread(Arc::new(Mutex::new(Some(thread::spawn(move ||
-			block.call_attr_lit(&Literal::CALL, &[&block])
-		))))).into())
+		Ok(super::Thread::spawn(None, block.clone(), block_args)?.into())
 	},
 }
 
@@ -428,7 +690,7 @@ mod tests {
 		assert_mapping_eq!(
 			"Basic" Basic, /*"Block" Block,*/ "Boolean" Boolean, "Function" Function,
 			"Kernel" Kernel, "List" List, "Null" Null, "Number" Number,
-			"Pristine" Pristine, "RustFn" RustFn, "Text" Text
+			"Pristine" Pristine, "RustFn" RustFn, "Text" Text, "Exception" Exception
 		);
 	}
 
@@ -461,14 +723,68 @@ mod tests {
 	fn r#while() { todo!() }
 
 	#[test]
-	#[ignore]
-	fn r#for() { todo!() }
+	fn r#for() {
+		use std::sync::{Arc, Mutex};
+		crate::init();
+
+		// a user-defined iterator, whose `next` counts up to 3.
+		let counter = Object::default();
+		counter.set_attr_lit("next", crate::types::RustClosure::new({
+			let count = Arc::new(Mutex::new(0i64));
+			move |_| {
+				let mut count = count.lock().unwrap();
+				*count += 1;
+				Ok(if *count <= 3 { Object::from(*count) } else { crate::types::StopIteration.into() })
+			}
+		}).into()).unwrap();
+
+		let iterable = Object::default();
+		iterable.set_attr_lit("@iter", crate::types::RustClosure::new(move |_| Ok(counter.clone())).into()).unwrap();
+
+		let seen = Arc::new(Mutex::new(Vec::new()));
+		let block = crate::types::RustClosure::new({
+			let seen = seen.clone();
+			move |args| {
+				seen.lock().unwrap().push(args.try_arg(0)?.downcast::<Number>().unwrap().clone());
+				Ok(args.try_arg(0)?.clone())
+			}
+		});
+
+		let result = Kernel::qs_for(&iterable, args!(block)).unwrap();
+		assert_eq!(*result.downcast::<Number>().unwrap(), Number::from(3));
+		assert_eq!(*seen.lock().unwrap(), vec![Number::from(1), Number::from(2), Number::from(3)]);
+	}
 
 	#[test]
 	#[ignore]
 	fn sleep() { todo!() }
 
 	#[test]
-	#[ignore]
-	fn open() { todo!() }
+	fn open() {
+		crate::init();
+
+		let dir = crate::types::io::dir::temp().unwrap();
+		let path = Object::from(dir.join("open.txt").to_string_lossy().into_owned());
+		std::fs::write(dir.join("open.txt"), "hello").unwrap();
+
+		let file = Kernel::qs_open(&path, args!()).unwrap();
+		assert_eq!(*file.call_attr_lit("read", &[]).unwrap().downcast::<Text>().unwrap(), Text::from("hello"));
+
+		// the file is closed after the block, even if it throws.
+		let opened = Object::default();
+		let block = crate::types::RustClosure::new({
+			let opened = opened.clone();
+			move |args| {
+				opened.set_attr_lit("file", args.try_arg(0)?.clone())?;
+				Err(Error::Thrown(Object::from("oops")))
+			}
+		});
+
+		assert!(Kernel::qs_open(&path, args!(block)).is_err());
+
+		let file = opened.get_attr_lit("file").unwrap();
+		assert!(file.call_attr_lit("read", &[]).unwrap().is_a::<Null>());
+
+		crate::types::io::dir::remove(&dir).unwrap();
+	}
 }
@@ -609,11 +609,11 @@ impl List {
 	/// ```
 	#[instrument(name="List::get", level="trace", skip(this, args), fields(self=?this, ?args))]
 	pub fn qs_get(this: &Object, args: Args) -> crate::Result<Object> {
-		let start: isize = args.try_arg(0)?.call_downcast::<Number>().map(|n| *n)?.try_into()?;
+		let start: isize = args.try_arg(0)?.call_downcast::<Number>().map(|n| n.clone())?.try_into()?;
 
 		let stop = 
 			args.arg(1)
-			.map(|n| n.call_downcast::<Number>().map(|n| *n))
+			.map(|n| n.call_downcast::<Number>().map(|n| n.clone()))
 			.transpose()?
 			.map(isize::try_from)
 			.transpose()?;
@@ -665,7 +665,7 @@ impl List {
 	/// ```
 	#[instrument(name="List::set", level="trace", skip(this, args), fields(self=?this, ?args))]
 	pub fn qs_set(this: &Object, args: Args) -> crate::Result<Object> {
-		let pos: isize = args.try_arg(0)?.call_downcast::<Number>().map(|n| *n)?.try_into()?;
+		let pos: isize = args.try_arg(0)?.call_downcast::<Number>().map(|n| n.clone())?.try_into()?;
 
 		if args.len() == 2 {
 			let ele = args.arg(1).unwrap().clone();
@@ -677,7 +677,7 @@ impl List {
 				Ok(ele)
 			}
 		} else {
-			let end: isize = args.try_arg(1)?.call_downcast::<Number>().map(|n| *n)?.try_into()?;
+			let end: isize = args.try_arg(1)?.call_downcast::<Number>().map(|n| n.clone())?.try_into()?;
 			let ele = args.try_arg(2)?.call_downcast::<Self>()?.clone();
 			let mut this = this.try_downcast_mut::<Self>()?;
 
@@ -693,7 +693,7 @@ impl List {
 
 	#[instrument(name="List::delete", level="trace", skip(this, args), fields(self=?this, ?args))]
 	pub fn qs_delete(this: &Object, args: Args) -> crate::Result<Object> {
-		let idx: isize = args.try_arg(0)?.call_downcast::<Number>().map(|n| *n)?.try_into()?;
+		let idx: isize = args.try_arg(0)?.call_downcast::<Number>().map(|n| n.clone())?.try_into()?;
 
 		let mut this = this.try_downcast_mut::<Self>()?;
 
@@ -723,14 +723,14 @@ impl List {
 	#[instrument(name="List::*", level="trace", skip(this, args), fields(self=?this, ?args))]
 	pub fn qs_mul(this: &Object, args: Args) -> crate::Result<Object> {
 		let this = this.try_downcast::<Self>()?;
-		let amnt = usize::try_from(*args.try_arg(0)?.call_downcast::<Number>()?)?;
+		let amnt = usize::try_from(args.try_arg(0)?.call_downcast::<Number>()?.clone())?;
 
 		Ok((&*this * amnt).into())
 	}
 
 	#[instrument(name="List::*=", level="trace", skip(this, args), fields(self=?this, args=?args))]
 	pub fn qs_mul_assign(this: &Object, args: Args) -> crate::Result<Object> {
-		let amnt = usize::try_from(*args.try_arg(0)?.call_downcast::<Number>()?)?;
+		let amnt = usize::try_from(args.try_arg(0)?.call_downcast::<Number>()?.clone())?;
 
 		*this.try_downcast_mut::<Self>()? *= amnt;
 
@@ -766,6 +766,22 @@ impl List {
 		eql.map(Object::from)
 	}
 
+	/// Hashes `this` by combining the `hash` of each element.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// assert([1, "a"].hash() == [1, "a"].hash());
+	/// ```
+	#[instrument(name="List::hash", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_hash(this: &Object, _: Args) -> crate::Result<Object> {
+		let this = this.try_downcast::<Self>()?.clone();
+		let hashes = this.iter()
+			.map(|obj| obj.call_attr_lit(&Literal::HASH, &[])?.call_downcast::<Number>().map(|n| n.clone()))
+			.collect::<crate::Result<Vec<_>>>()?;
+
+		Ok(crate::utils::hash(&hashes).into())
+	}
+
 	/// Add an element to the back of the list, returning the list.
 	///
 	/// # Arguments
@@ -1125,6 +1141,7 @@ for List [(init_parent super::Basic super::Iterable) (parents super::Basic)]:
 	"shift"   => method Self::qs_shift,
 
 	"==" => method Self::qs_eql,
+	"hash" => method Self::qs_hash,
 	"+"  => method Self::qs_add,
 	"+=" => method Self::qs_add_assign,
 	"-"  => method Self::qs_sub,
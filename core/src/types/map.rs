@@ -0,0 +1,523 @@
+use crate::{Object, Args, Literal};
+use crate::error::{ValueError, ArgumentError};
+use crate::types::{Text, Boolean, Number, List, Iter};
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use tracing::instrument;
+
+/// An insertion-ordered mapping of keys to values.
+///
+/// Unlike the attributes of an object, any object can be used as a key: Keys are first hashed with
+/// their `hash` attribute, and then compared with `==` to any other keys that have the same hash.
+#[derive(Debug, Clone, Default)]
+pub struct Map {
+	/// Every entry that's been inserted, in order. Removed entries are left as `None` until the map
+	/// is compacted.
+	entries: Vec<Option<(Object, Object)>>,
+	/// The indices of entries within `entries`, grouped by the hash of their key.
+	buckets: HashMap<Number, Vec<usize>>,
+	/// The amount of entries that aren't `None`.
+	len: usize
+}
+
+/// Get the hash of `key` by calling its `hash` attribute.
+fn hash_key(key: &Object) -> crate::Result<Number> {
+	Ok(key.call_attr_lit(&Literal::HASH, &[])?.call_downcast::<Number>()?.clone())
+}
+
+/// Rust-centric map methods
+impl Map {
+	/// Create a new, empty map.
+	#[inline]
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Get the amount of entries in the map.
+	#[inline]
+	pub fn len(&self) -> usize {
+		self.len
+	}
+
+	/// Checks to see if the map is empty.
+	#[inline]
+	pub fn is_empty(&self) -> bool {
+		self.len == 0
+	}
+
+	/// Get an iterator over the map's entries, in insertion order.
+	pub fn iter(&self) -> impl Iterator<Item=(&Object, &Object)> {
+		self.entries.iter().flatten().map(|(key, value)| (key, value))
+	}
+
+	/// Get an iterator over the map's keys, in insertion order.
+	#[inline]
+	pub fn keys(&self) -> impl Iterator<Item=&Object> {
+		self.iter().map(|(key, _)| key)
+	}
+
+	/// Get an iterator over the map's values, in insertion order.
+	#[inline]
+	pub fn values(&self) -> impl Iterator<Item=&Object> {
+		self.iter().map(|(_, value)| value)
+	}
+
+	/// Find the index of `key` within `entries`, if it exists.
+	fn find(&self, hash: &Number, key: &Object) -> crate::Result<Option<usize>> {
+		for &index in self.buckets.get(hash).into_iter().flatten() {
+			let (stored, _) = self.entries[index].as_ref().expect("bucket points to a removed entry");
+
+			if stored.is_identical(key) || key.eq_obj(stored)? {
+				return Ok(Some(index));
+			}
+		}
+
+		Ok(None)
+	}
+
+	/// Get the value associated with `key`.
+	pub fn get(&self, key: &Object) -> crate::Result<Option<&Object>> {
+		let hash = hash_key(key)?;
+
+		Ok(self.find(&hash, key)?.and_then(|index| self.entries[index].as_ref()).map(|(_, value)| value))
+	}
+
+	/// Checks to see if `key` is in the map.
+	#[inline]
+	pub fn contains_key(&self, key: &Object) -> crate::Result<bool> {
+		self.get(key).map(|value| value.is_some())
+	}
+
+	/// Associate `value` with `key`, returning the previous value, if any.
+	///
+	/// If `key` already exists, its position within the map is unchanged.
+	pub fn insert(&mut self, key: Object, value: Object) -> crate::Result<Option<Object>> {
+		let hash = hash_key(&key)?;
+
+		if let Some(index) = self.find(&hash, &key)? {
+			let (_, old) = self.entries[index].as_mut().expect("found a removed entry");
+			return Ok(Some(std::mem::replace(old, value)));
+		}
+
+		self.buckets.entry(hash).or_default().push(self.entries.len());
+		self.entries.push(Some((key, value)));
+		self.len += 1;
+
+		Ok(None)
+	}
+
+	/// Remove `key` from the map, returning its value, if it existed.
+	pub fn remove(&mut self, key: &Object) -> crate::Result<Option<Object>> {
+		let hash = hash_key(key)?;
+		let index =
+			match self.find(&hash, key)? {
+				Some(index) => index,
+				None => return Ok(None)
+			};
+
+		let bucket = self.buckets.get_mut(&hash).expect("found key without a bucket");
+		bucket.retain(|&idx| idx != index);
+
+		if bucket.is_empty() {
+			self.buckets.remove(&hash);
+		}
+
+		let (_, value) = self.entries[index].take().expect("found a removed entry");
+		self.len -= 1;
+
+		// only compact once a majority of the entries are removed, so removal is amortized O(1).
+		if self.len < self.entries.len() / 2 {
+			self.compact();
+		}
+
+		Ok(Some(value))
+	}
+
+	/// Removes all the removed entries from `entries`, and updates `buckets` accordingly.
+	fn compact(&mut self) {
+		let mut new_indices = Vec::with_capacity(self.entries.len());
+		let mut next = 0;
+
+		for entry in &self.entries {
+			new_indices.push(next);
+
+			if entry.is_some() {
+				next += 1;
+			}
+		}
+
+		self.entries.retain(Option::is_some);
+
+		for bucket in self.buckets.values_mut() {
+			bucket.iter_mut().for_each(|index| *index = new_indices[*index]);
+		}
+	}
+
+	/// Removes all entries from the map.
+	pub fn clear(&mut self) {
+		self.entries.clear();
+		self.buckets.clear();
+		self.len = 0;
+	}
+
+	/// Checks to see if two maps have the same keys, and each key has the same value.
+	///
+	/// The order that keys were inserted in doesn't matter.
+	pub fn eql(&self, rhs: &Self) -> crate::Result<bool> {
+		if self.len() != rhs.len() {
+			return Ok(false);
+		}
+
+		for (key, value) in self.iter() {
+			match rhs.get(key)? {
+				Some(rhs_value) if value.eq_obj(rhs_value)? => {},
+				_ => return Ok(false)
+			}
+		}
+
+		Ok(true)
+	}
+}
+
+impl TryFrom<&Map> for Text {
+	type Error = crate::Error;
+
+	fn try_from(map: &Map) -> crate::Result<Self> {
+		let mut entries = Vec::with_capacity(map.len());
+
+		for (key, value) in map.iter() {
+			entries.push(format!("{}: {}",
+				key.call_attr_lit(&Literal::INSPECT, &[])?.call_downcast::<Text>()?.as_ref(),
+				value.call_attr_lit(&Literal::INSPECT, &[])?.call_downcast::<Text>()?.as_ref()));
+		}
+
+		Ok(format!("{{{}}}", entries.join(", ")).into())
+	}
+}
+
+impl From<&Map> for Boolean {
+	#[inline]
+	fn from(map: &Map) -> Self {
+		(!map.is_empty()).into()
+	}
+}
+
+impl From<&Map> for List {
+	/// Converts the map into a list of `[key, value]` pairs.
+	fn from(map: &Map) -> Self {
+		map.iter()
+			.map(|(key, value)| Object::from(vec![key.clone(), value.clone()]))
+			.collect()
+	}
+}
+
+/// Quest methods
+impl Map {
+	/// Create a new map, optionally with initial entries.
+	///
+	/// # Arguments
+	/// 1. (optional, `@iter`) The initial entries, where each element is a `[key, value]` list.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// map = Map([["a", 1], [2, "b"]]);
+	///
+	/// assert((map["a"]) == 1);
+	/// assert(map.len() == 2);
+	/// ```
+	#[instrument(name="Map::()", level="trace", skip(args), fields(?args))]
+	pub fn qs_call(_: &Object, args: Args) -> crate::Result<Object> {
+		let mut map = Self::new();
+
+		if let Some(entries) = args.arg(0) {
+			let entries = Iter::from_object(entries)?;
+
+			for entry in entries {
+				let entry = entry?;
+				let entry = entry.call_downcast::<List>()?;
+
+				if entry.len() != 2 {
+					return Err(ValueError::Messaged(
+						format!("map entries must be `[key, value]`, not {} elements long", entry.len())).into());
+				}
+
+				map.insert(entry.as_ref()[0].clone(), entry.as_ref()[1].clone())?;
+			}
+		}
+
+		Ok(map.into())
+	}
+
+	/// Simply returns `this`.
+	#[instrument(name="Map::@map", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_at_map(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.clone())
+	}
+
+	/// Converts `this` into a [`Text`] of the form `{key: value, ...}`.
+	///
+	/// Both the keys and values are [inspect](Literal::INSPECT)ed.
+	#[instrument(name="Map::@text", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_at_text(this: &Object, _: Args) -> crate::Result<Object> {
+		let this = this.try_downcast::<Self>()?.clone();
+
+		Ok(Text::try_from(&this)?.into())
+	}
+
+	/// Inspects `this`.
+	///
+	/// This is identical to [`qs_at_text`](#qs_at_text).
+	#[instrument(name="Map::inspect", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_inspect(this: &Object, args: Args) -> crate::Result<Object> {
+		Self::qs_at_text(this, args)
+	}
+
+	/// Converts `this` into a [`Boolean`]; only empty maps are false.
+	#[instrument(name="Map::@bool", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_at_bool(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(Boolean::from(&*this.try_downcast::<Self>()?).into())
+	}
+
+	/// Converts `this` into a [`List`] of `[key, value]` pairs.
+	#[instrument(name="Map::@list", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_at_list(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(List::from(&*this.try_downcast::<Self>()?).into())
+	}
+
+	/// Get an [`Iter`] over the `[key, value]` pairs of `this`.
+	#[instrument(name="Map::@iter", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_at_iter(this: &Object, _: Args) -> crate::Result<Object> {
+		let pairs = List::from(&*this.try_downcast::<Self>()?);
+
+		Ok(Iter::with_objects(pairs).into())
+	}
+
+	/// Get the amount of entries in `this`.
+	#[instrument(name="Map::len", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_len(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.len().into())
+	}
+
+	/// Checks to see if `this` has no entries.
+	#[instrument(name="Map::empty?", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_empty_q(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.is_empty().into())
+	}
+
+	/// Get the value associated with the first argument, or `null` if it doesn't exist.
+	///
+	/// # Arguments
+	/// 1. (required) The key.
+	#[instrument(name="Map::[]", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_index(this: &Object, args: Args) -> crate::Result<Object> {
+		let key = args.try_arg(0)?;
+
+		Ok(this.try_downcast::<Self>()?.get(key)?.cloned().unwrap_or_default())
+	}
+
+	/// Get the value associated with the first argument, or the second argument if it doesn't exist.
+	///
+	/// # Arguments
+	/// 1. (required) The key.
+	/// 2. (optional) The default value; defaults to `null`.
+	#[instrument(name="Map::get", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_get(this: &Object, args: Args) -> crate::Result<Object> {
+		let key = args.try_arg(0)?;
+
+		match this.try_downcast::<Self>()?.get(key)? {
+			Some(value) => Ok(value.clone()),
+			None => Ok(args.arg(1).cloned().unwrap_or_default())
+		}
+	}
+
+	/// Associate the second argument with the first, returning the second.
+	///
+	/// # Arguments
+	/// 1. (required) The key.
+	/// 2. (required) The value.
+	#[instrument(name="Map::set", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_set(this: &Object, args: Args) -> crate::Result<Object> {
+		let key = args.try_arg(0)?.clone();
+		let value = args.try_arg(1)?.clone();
+
+		this.try_downcast_mut::<Self>()?.insert(key, value.clone())?;
+
+		Ok(value)
+	}
+
+	/// Checks to see if the first argument is a key in `this`.
+	///
+	/// # Arguments
+	/// 1. (required) The key.
+	#[instrument(name="Map::has?", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_has_q(this: &Object, args: Args) -> crate::Result<Object> {
+		let key = args.try_arg(0)?;
+
+		Ok(this.try_downcast::<Self>()?.contains_key(key)?.into())
+	}
+
+	/// Removes the first argument from `this`, returning its value, or `null` if it didn't exist.
+	///
+	/// # Arguments
+	/// 1. (required) The key.
+	#[instrument(name="Map::delete", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_delete(this: &Object, args: Args) -> crate::Result<Object> {
+		let key = args.try_arg(0)?;
+
+		Ok(this.try_downcast_mut::<Self>()?.remove(key)?.unwrap_or_default())
+	}
+
+	/// Get a [`List`] of the keys in `this`, in insertion order.
+	#[instrument(name="Map::keys", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_keys(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.keys().cloned().collect::<List>().into())
+	}
+
+	/// Get a [`List`] of the values in `this`, in insertion order.
+	#[instrument(name="Map::values", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_values(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.values().cloned().collect::<List>().into())
+	}
+
+	/// Removes all entries from `this`, returning `this`.
+	#[instrument(name="Map::clear", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_clear(this: &Object, _: Args) -> crate::Result<Object> {
+		this.try_downcast_mut::<Self>()?.clear();
+
+		Ok(this.clone())
+	}
+
+	/// Checks to see if `this` is equal to the first argument.
+	///
+	/// Maps are equal if they have the same keys and values, regardless of insertion order.
+	///
+	/// # Arguments
+	/// 1. (required) The other object to compare against.
+	#[instrument(name="Map::==", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_eql(this: &Object, args: Args) -> crate::Result<Object> {
+		let rhs = args.try_arg(0)?;
+
+		if this.is_identical(rhs) {
+			return Ok(true.into());
+		}
+
+		let rhs =
+			match rhs.downcast::<Self>() {
+				Some(rhs) => rhs.clone(),
+				None => return Ok(false.into())
+			};
+
+		let this = this.try_downcast::<Self>()?.clone();
+
+		Ok(this.eql(&rhs)?.into())
+	}
+}
+
+impl crate::types::Convertible for Map {
+	const CONVERT_FUNC: Literal = Literal::new("@map");
+}
+
+impl_object_type!{
+for Map [(init_parent super::Basic super::Iterable) (parents super::Basic)]:
+	"()" => method Self::qs_call,
+	"@map" => method Self::qs_at_map,
+	"@text" => method Self::qs_at_text,
+	"inspect" => method Self::qs_inspect,
+	"@bool" => method Self::qs_at_bool,
+	"@list" => method Self::qs_at_list,
+	"@iter" => method Self::qs_at_iter,
+
+	"len" => method Self::qs_len,
+	"empty?" => method Self::qs_empty_q,
+	"[]" => method Self::qs_index,
+	"[]=" => method |this, args| {
+		let key = match args.try_arg(0)?.call_downcast::<List>()?.as_ref() {
+			[key] => key.clone(),
+			keys => return Err(ArgumentError::InvalidLength { given: keys.len(), expected: 1 }.into())
+		};
+
+		Self::qs_set(this, vec![&key, args.try_arg(1)?].into())
+	},
+	"get" => method Self::qs_get,
+	"set" => method Self::qs_set,
+	"has?" => method Self::qs_has_q,
+	"delete" => method Self::qs_delete,
+	"keys" => method Self::qs_keys,
+	"values" => method Self::qs_values,
+	"clear" => method Self::qs_clear,
+	"==" => method Self::qs_eql,
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn insertion_order() {
+		crate::init();
+
+		let mut map = Map::new();
+		map.insert("b".into(), 1.into()).unwrap();
+		map.insert(2.into(), 2.into()).unwrap();
+		map.insert("a".into(), 3.into()).unwrap();
+		assert_eq!(map.insert("b".into(), 4.into()).unwrap().unwrap().downcast::<Number>().unwrap().clone(), 1);
+
+		let keys = map.keys().map(|key| key.call_downcast::<Text>().unwrap().to_string()).collect::<Vec<_>>();
+		assert_eq!(keys, ["b", "2", "a"]);
+		assert_eq!(*map.get(&"b".into()).unwrap().unwrap().downcast::<Number>().unwrap(), 4);
+	}
+
+	#[test]
+	fn remove() {
+		crate::init();
+
+		let mut map = Map::new();
+		for i in 0..10 {
+			map.insert(i.into(), (i * 10).into()).unwrap();
+		}
+
+		for i in 0..8 {
+			assert!(map.remove(&i.into()).unwrap().is_some());
+		}
+
+		assert!(map.remove(&0.into()).unwrap().is_none());
+		assert_eq!(map.len(), 2);
+		assert_eq!(*map.get(&9.into()).unwrap().unwrap().downcast::<Number>().unwrap(), 90);
+		assert!(!map.contains_key(&3.into()).unwrap());
+	}
+
+	#[test]
+	fn eql() {
+		crate::init();
+
+		let mut lhs = Map::new();
+		lhs.insert(1.into(), "a".into()).unwrap();
+		lhs.insert(2.into(), "b".into()).unwrap();
+
+		let mut rhs = Map::new();
+		rhs.insert(2.into(), "b".into()).unwrap();
+		rhs.insert(1.into(), "a".into()).unwrap();
+
+		assert!(lhs.eql(&rhs).unwrap());
+		rhs.insert(1.into(), "c".into()).unwrap();
+		assert!(!lhs.eql(&rhs).unwrap());
+	}
+
+	#[test]
+	fn index_assign_needs_one_key() {
+		crate::init();
+
+		let map = Object::from(Map::new());
+		let value = Object::from(1);
+
+		for keys in [vec![], vec![Object::from(1), Object::from(2)]] {
+			let keys = Object::from(List::from(keys));
+			assert!(matches!(
+				map.call_attr_lit("[]=", &[&keys, &value]).unwrap_err(),
+				crate::Error::ArgumentError(ArgumentError::InvalidLength { expected: 1, .. })
+			));
+		}
+
+		assert!(map.downcast::<Map>().unwrap().is_empty());
+	}
+}
@@ -38,13 +38,33 @@ pub mod rustfn;
 mod null;
 mod class;
 mod text;
+mod bytes;
 pub mod boolean;
 pub mod number;
+mod complex;
 pub mod regex;
+mod path;
 pub mod io;
 mod list;
+mod map;
+mod set;
+mod duration;
+mod date;
+pub(crate) mod time;
+pub mod json;
+pub mod http;
+pub mod http_server;
+pub mod process;
+pub mod sync;
+pub mod thread;
+mod pattern;
 
 mod tcp;
+mod tcp_server;
+mod udp;
+#[cfg(unix)]
+mod unix_socket;
+mod exception;
 
 pub use function::BoundRustFn;
 
@@ -54,6 +74,28 @@ pub use io::Io;
 #[doc(inline)]
 pub use tcp::Tcp;
 
+#[doc(inline)]
+pub use tcp_server::TcpServer;
+
+#[doc(inline)]
+pub use udp::Udp;
+
+#[cfg(unix)]
+#[doc(inline)]
+pub use unix_socket::{UnixSocket, UnixListener};
+
+#[doc(inline)]
+pub use path::Path;
+
+#[doc(inline)]
+pub use sync::{Mutex, Condvar, Channel, Atomic};
+
+#[doc(inline)]
+pub use thread::{Thread, ThreadPool};
+
+#[doc(inline)]
+pub use exception::Exception;
+
 #[doc(inline)]
 pub use class::Class;
 
@@ -87,6 +129,9 @@ pub use rustfn::{RustFn, RustClosure};
 #[doc(inline)]
 pub use scope::Scope;
 
+#[doc(inline)]
+pub use pattern::Pattern;
+
 #[doc(inline)]
 pub use null::Null;
 
@@ -96,12 +141,45 @@ pub use boolean::Boolean;
 #[doc(inline)]
 pub use number::Number;
 
+#[doc(inline)]
+pub use complex::Complex;
+
 #[doc(inline)]
 pub use text::Text;
 
+#[doc(inline)]
+pub use bytes::Bytes;
+
 #[doc(inline)]
 pub use list::List;
 
+#[doc(inline)]
+pub use map::Map;
+
+#[doc(inline)]
+pub use set::Set;
+
+#[doc(inline)]
+pub use duration::Duration;
+
+#[doc(inline)]
+pub use date::Date;
+
+#[doc(inline)]
+pub use time::Time;
+
+#[doc(inline)]
+pub use json::Json;
+
+#[doc(inline)]
+pub use http::Http;
+
+#[doc(inline)]
+pub use http_server::HttpServer;
+
+#[doc(inline)]
+pub use process::Process;
+
 #[doc(inline)]
 pub use self::regex::Regex;
 
@@ -156,7 +156,7 @@ impl Null {
 	///
 	/// # Quest Examples
 	/// ```quest
-	/// assert(null.@list() == []);
+	/// assert((null.@list()) == []);
 	/// ```
 	#[instrument(name="Null::@list", level="trace")]
 	pub fn qs_at_list(_: &Object, _: Args) -> Result<Object> {
@@ -296,6 +296,12 @@ impl Null {
 	pub fn qs_eql(_: &Object, args: Args) -> Result<Object> {
 		Ok(args.try_arg(0)?.is_a::<Self>().into())
 	}
+
+	/// Hashes `this`; as every `null` is equal, they all have the same hash.
+	#[instrument(name="Null::hash", level="trace")]
+	pub fn qs_hash(_: &Object, _: Args) -> Result<Object> {
+		Ok(crate::utils::hash(&Null).into())
+	}
 }
 
 impl_object_type!{
@@ -320,6 +326,7 @@ for Null {
 	"@num" => method Self::qs_at_num,
 	"()" => method Self::qs_call,
 	"==" => method Self::qs_eql,
+	"hash" => method Self::qs_hash,
 }
 
 #[cfg(test)]
@@ -0,0 +1,431 @@
+use crate::{Object, Args};
+use crate::error::ValueError;
+use crate::types::{Text, List};
+use std::fmt::{self, Display, Formatter};
+use std::path::{self, Component, PathBuf};
+use tracing::instrument;
+
+/// A filesystem path.
+///
+/// Unlike joining [`Text`]s with `"/"`, paths handle things like trailing slashes and `..`
+/// correctly. Anywhere a filename is taken, either a `Path` or a [`Text`] can be given.
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Path(PathBuf);
+
+impl From<PathBuf> for Path {
+	#[inline]
+	fn from(path: PathBuf) -> Self {
+		Self(path)
+	}
+}
+
+impl From<Path> for PathBuf {
+	#[inline]
+	fn from(path: Path) -> Self {
+		path.0
+	}
+}
+
+impl From<&str> for Path {
+	#[inline]
+	fn from(path: &str) -> Self {
+		Self(PathBuf::from(path))
+	}
+}
+
+impl AsRef<path::Path> for Path {
+	#[inline]
+	fn as_ref(&self) -> &path::Path {
+		&self.0
+	}
+}
+
+impl Display for Path {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		Display::fmt(&self.0.display(), f)
+	}
+}
+
+impl Path {
+	/// Resolves `.` and `..` components without looking at the filesystem.
+	///
+	/// A `..` directly after the root is dropped, and leading `..`s in relative paths are kept. An
+	/// empty result is `.`.
+	pub fn normalize(&self) -> Self {
+		let mut normalized = PathBuf::new();
+
+		for component in self.0.components() {
+			match component {
+				Component::CurDir => {},
+				Component::ParentDir =>
+					match normalized.components().next_back() {
+						Some(Component::Normal(_)) => { normalized.pop(); },
+						Some(Component::RootDir) | Some(Component::Prefix(_)) => {},
+						_ => normalized.push(".."),
+					},
+				other => normalized.push(other)
+			}
+		}
+
+		if normalized.as_os_str().is_empty() {
+			normalized.push(".");
+		}
+
+		Self(normalized)
+	}
+
+	/// Converts this to a normalized absolute path, relative to the current directory if it isn't
+	/// already absolute. Symlinks aren't resolved.
+	pub fn absolute(&self) -> std::io::Result<Self> {
+		if self.0.is_absolute() {
+			Ok(self.normalize())
+		} else {
+			Ok(Self(std::env::current_dir()?.join(&self.0)).normalize())
+		}
+	}
+
+	/// Get a relative path which leads from `base` to this path, using `..` as needed.
+	///
+	/// Both paths are made [absolute](Self::absolute) first. If there's no such path (for example,
+	/// they're on different drives), `None` is returned.
+	pub fn relative_to(&self, base: &Self) -> std::io::Result<Option<Self>> {
+		let path = self.absolute()?;
+		let base = base.absolute()?;
+
+		let mut path_components = path.0.components().peekable();
+		let mut base_components = base.0.components().peekable();
+
+		while let (Some(lhs), Some(rhs)) = (path_components.peek(), base_components.peek()) {
+			if lhs != rhs {
+				break;
+			}
+
+			path_components.next();
+			base_components.next();
+		}
+
+		let mut relative = PathBuf::new();
+
+		for component in base_components {
+			match component {
+				Component::Normal(_) => relative.push(".."),
+				_ => return Ok(None)
+			}
+		}
+
+		relative.extend(path_components);
+
+		if relative.as_os_str().is_empty() {
+			relative.push(".");
+		}
+
+		Ok(Some(Self(relative)))
+	}
+}
+
+fn optional(path: Option<&path::Path>) -> Object {
+	path.map(|path| Path(path.to_path_buf()).into()).unwrap_or_default()
+}
+
+fn optional_text(text: Option<&std::ffi::OsStr>) -> Object {
+	text.map(|text| Text::from(text.to_string_lossy().into_owned()).into()).unwrap_or_default()
+}
+
+/// Quest methods
+impl Path {
+	/// Create a new path by joining all the arguments together.
+	///
+	/// # Arguments
+	/// 1. (optional, `@path`) The path; defaults to `"."`
+	/// 2+. (optional, `@path`) More components to [join](#method.qs_join) onto it.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// assert(Path("a", "b/", "c.txt") == Path("a/b/c.txt"));
+	/// assert(Path("a", "/etc") == Path("/etc"));
+	/// ```
+	#[instrument(name="Path::()", level="trace", skip(args), fields(?args))]
+	pub fn qs_call(_: &Object, args: Args) -> crate::Result<Object> {
+		if args.is_empty() {
+			return Ok(Self::from(".").into());
+		}
+
+		let mut path = PathBuf::new();
+
+		for arg in args.as_ref() {
+			path.push(&arg.call_downcast::<Self>()?.0);
+		}
+
+		Ok(Self(path).into())
+	}
+
+	/// Converts `this` to a [`Text`]. Any invalid unicode is replaced.
+	#[instrument(name="Path::@text", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_at_text(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(Text::from(this.try_downcast::<Self>()?.0.to_string_lossy().into_owned()).into())
+	}
+
+	/// Converts `this` to a [`Path`]; as paths are immutable, this just returns `this`.
+	#[instrument(name="Path::@path", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_at_path(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.clone())
+	}
+
+	/// Inspects `this`.
+	#[instrument(name="Path::inspect", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_inspect(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(Text::from(format!("Path({:?})", this.try_downcast::<Self>()?.0)).into())
+	}
+
+	/// Hashes `this`.
+	#[instrument(name="Path::hash", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_hash(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(crate::utils::hash(&*this.try_downcast::<Self>()?).into())
+	}
+
+	/// See if `this` is equal to the first argument.
+	///
+	/// Paths are compared by component, so `a//b/` and `a/b` are equal, but `a/../b` and `b` aren't.
+	///
+	/// # Arguments
+	/// 1. (required) The other object to compare against.
+	#[instrument(name="Path::==", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_eql(this: &Object, args: Args) -> crate::Result<Object> {
+		let this = this.try_downcast::<Self>()?;
+		let rhs = args.try_arg(0)?.downcast::<Self>();
+
+		Ok(rhs.map(|rhs| *rhs == *this).unwrap_or(false).into())
+	}
+
+	/// Compares `this` to the first argument.
+	///
+	/// # Arguments
+	/// 1. (required) The path to compare against.
+	#[instrument(name="Path::<=>", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_cmp(this: &Object, args: Args) -> crate::Result<Object> {
+		let this = this.try_downcast::<Self>()?;
+		let rhs = args.try_arg(0)?.downcast::<Self>();
+
+		Ok(rhs.map(|rhs| this.cmp(&*rhs).into()).unwrap_or_default())
+	}
+
+	/// Joins the arguments onto `this`, in order.
+	///
+	/// Joining an absolute path replaces everything before it. This is also available as `/`.
+	///
+	/// # Arguments
+	/// 1+. (required, `@path`) The components to join.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// $dir = Path("/usr/");
+	/// assert(dir.join("local", "bin") == Path("/usr/local/bin"));
+	/// assert((dir / "lib") == Path("/usr/lib"));
+	/// ```
+	#[instrument(name="Path::join", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_join(this: &Object, args: Args) -> crate::Result<Object> {
+		args.try_arg(0)?;
+
+		let mut path = this.try_downcast::<Self>()?.0.clone();
+
+		for arg in args.as_ref() {
+			path.push(&arg.call_downcast::<Self>()?.0);
+		}
+
+		Ok(Self(path).into())
+	}
+
+	/// Get the parent of `this`, or [`Null`](crate::types::Null) if it has none (such as `/`).
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// assert(Path("a/b/c.txt").parent() == Path("a/b"));
+	/// assert(Path("/").parent() == null);
+	/// ```
+	#[instrument(name="Path::parent", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_parent(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(optional(this.try_downcast::<Self>()?.0.parent()))
+	}
+
+	/// Get the last component of `this` as a [`Text`], or [`Null`](crate::types::Null) if it ends
+	/// in `..` or is `/`.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// assert(Path("a/b/c.txt").basename() == "c.txt");
+	/// assert(Path("a/b/").basename() == "b");
+	/// ```
+	#[instrument(name="Path::basename", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_basename(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(optional_text(this.try_downcast::<Self>()?.0.file_name()))
+	}
+
+	/// Get the [`basename`](#method.qs_basename) of `this` without its extension.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// assert(Path("a/b/c.tar.gz").stem() == "c.tar");
+	/// ```
+	#[instrument(name="Path::stem", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_stem(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(optional_text(this.try_downcast::<Self>()?.0.file_stem()))
+	}
+
+	/// Get the extension of `this`, without the leading `.`, or [`Null`](crate::types::Null) if
+	/// there is none.
+	///
+	/// Files that start with a `.` and contain no others (such as `.bashrc`) have no extension.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// assert(Path("a/b/c.tar.gz").extension() == "gz");
+	/// assert(Path("a/.bashrc").extension() == null);
+	/// ```
+	#[instrument(name="Path::extension", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_extension(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(optional_text(this.try_downcast::<Self>()?.0.extension()))
+	}
+
+	/// Get a copy of `this` with its extension replaced.
+	///
+	/// # Arguments
+	/// 1. (required, `@text`) The new extension, without the leading `.`. If it's empty, the
+	///    extension is removed.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// assert(Path("a/b.txt").with_extension("md") == Path("a/b.md"));
+	/// assert(Path("a/b.txt").with_extension("") == Path("a/b"));
+	/// ```
+	#[instrument(name="Path::with_extension", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_with_extension(this: &Object, args: Args) -> crate::Result<Object> {
+		let extension = args.try_arg(0)?.call_downcast::<Text>()?;
+
+		Ok(Self(this.try_downcast::<Self>()?.0.with_extension(extension.as_ref())).into())
+	}
+
+	/// Get the components of `this` as a [`List`] of [`Text`]s.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// $expected = ["/", "usr", "bin"];
+	/// assert(Path("/usr/bin/").components() == expected);
+	/// ```
+	#[instrument(name="Path::components", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_components(this: &Object, _: Args) -> crate::Result<Object> {
+		let components = this.try_downcast::<Self>()?.0.components()
+			.map(|component| Text::from(component.as_os_str().to_string_lossy().into_owned()).into())
+			.collect::<Vec<Object>>();
+
+		Ok(List::from(components).into())
+	}
+
+	/// Checks to see if `this` is absolute.
+	#[instrument(name="Path::absolute?", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_absolute_q(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.0.is_absolute().into())
+	}
+
+	/// Resolves `.` and `..` in `this`, without looking at the filesystem.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// assert(Path("a/./b/../c").normalize() == Path("a/c"));
+	/// assert(Path("../a/..").normalize() == Path(".."));
+	/// ```
+	#[instrument(name="Path::normalize", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_normalize(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.normalize().into())
+	}
+
+	/// Get the absolute version of `this`, relative to the current directory.
+	///
+	/// Unlike [`canonical`](#method.qs_canonical), this doesn't touch the filesystem, so the path
+	/// needn't exist and symlinks aren't resolved.
+	#[instrument(name="Path::absolute", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_absolute(this: &Object, _: Args) -> crate::Result<Object> {
+		let absolute = this.try_downcast::<Self>()?.absolute()?;
+
+		Ok(absolute.into())
+	}
+
+	/// Get the canonical version of `this`, which is absolute and has all symlinks resolved.
+	///
+	/// An `IoError` is thrown if the path doesn't exist.
+	#[instrument(name="Path::canonical", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_canonical(this: &Object, _: Args) -> crate::Result<Object> {
+		let canonical = std::fs::canonicalize(&this.try_downcast::<Self>()?.0)?;
+
+		Ok(Self(canonical).into())
+	}
+
+	/// Get a relative path which leads from the first argument to `this`.
+	///
+	/// Both paths are made [absolute](#method.qs_absolute) first.
+	///
+	/// # Arguments
+	/// 1. (required, `@path`) The path to be relative to.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// assert(Path("/a/b/c").relative_to("/a/d") == Path("../b/c"));
+	/// assert(Path("/a").relative_to("/a") == Path("."));
+	/// ```
+	#[instrument(name="Path::relative_to", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_relative_to(this: &Object, args: Args) -> crate::Result<Object> {
+		let base = args.try_arg(0)?.call_downcast::<Self>()?;
+		let this = this.try_downcast::<Self>()?;
+
+		match this.relative_to(&base)? {
+			Some(relative) => Ok(relative.into()),
+			None => Err(ValueError::Messaged(format!("{} isn't reachable from {}", *this, *base)).into())
+		}
+	}
+}
+
+impl_object_type!{
+for Path [(init_parent super::Basic super::Comparable) (parents super::Basic) (convert "@path")]:
+	"()" => method Self::qs_call,
+	"@text" => method Self::qs_at_text,
+	"@path" => method Self::qs_at_path,
+	"inspect" => method Self::qs_inspect,
+	"hash" => method Self::qs_hash,
+	"==" => method Self::qs_eql,
+	"<=>" => method Self::qs_cmp,
+	"join" => method Self::qs_join,
+	"/" => method Self::qs_join,
+	"parent" => method Self::qs_parent,
+	"basename" => method Self::qs_basename,
+	"stem" => method Self::qs_stem,
+	"extension" => method Self::qs_extension,
+	"with_extension" => method Self::qs_with_extension,
+	"components" => method Self::qs_components,
+	"absolute?" => method Self::qs_absolute_q,
+	"normalize" => method Self::qs_normalize,
+	"absolute" => method Self::qs_absolute,
+	"canonical" => method Self::qs_canonical,
+	"relative_to" => method Self::qs_relative_to,
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn normalize() {
+		assert_eq!(Path::from("a/./b/../c").normalize(), Path::from("a/c"));
+		assert_eq!(Path::from("../a/..").normalize(), Path::from(".."));
+		assert_eq!(Path::from("/../a").normalize(), Path::from("/a"));
+		assert_eq!(Path::from("a/..").normalize(), Path::from("."));
+	}
+
+	#[test]
+	fn relative_to() {
+		let relative = |path: &str, base: &str| Path::from(path).relative_to(&Path::from(base)).unwrap();
+
+		assert_eq!(relative("/a/b/c", "/a/d"), Some(Path::from("../b/c")));
+		assert_eq!(relative("/a/b/", "/a/b"), Some(Path::from(".")));
+		assert_eq!(relative("/a", "/a/b/c"), Some(Path::from("../..")));
+		assert_eq!(relative("/a/../b", "/"), Some(Path::from("b")));
+	}
+}
@@ -0,0 +1,374 @@
+//! Patterns for [`Kernel::match`](super::Kernel::qs_match).
+//!
+//! Most objects are matched by value, with `==`. A handful of objects are treated specially:
+//! - [`List`]s match lists with the same shape, element by element. A single [`Pattern::Rest`]
+//!   can be used in them to match any number of elements.
+//! - [`Class`]es (such as `Number` or `Text`) match any object that has them as an ancestor.
+//! - [`Pattern`]s match wildcards, bind variables, and check objects' attributes.
+
+use crate::{Object, Args, Literal, Result};
+use crate::error::ValueError;
+use crate::types::{Class, List, ObjectType, RustClosure, Scope, Text};
+use parking_lot::Mutex;
+use std::sync::Arc;
+use tracing::instrument;
+
+/// A pattern that can't be expressed by an ordinary object.
+#[derive(Debug, Clone)]
+pub enum Pattern {
+	/// Matches anything.
+	Any,
+	/// Matches anything the inner pattern (if any) matches, binding the value to the name.
+	Bind(Object, Option<Object>),
+	/// Matches the rest of a list, optionally binding it to a name.
+	Rest(Option<Object>),
+	/// Matches objects that have each attribute, whose values match the corresponding pattern.
+	Attrs(Vec<(Object, Object)>)
+}
+
+/// The variables a pattern introduces, in the order they were bound.
+pub type Bindings = Vec<(Object, Object)>;
+
+/// Checks to see if `object` is `class`, or has it as an ancestor.
+fn is_instance(object: &Object, class: &Object, seen: &mut Vec<usize>) -> Result<bool> {
+	if object.is_identical(class) {
+		return Ok(true);
+	}
+
+	if seen.contains(&object.id()) {
+		return Ok(false);
+	}
+
+	seen.push(object.id());
+
+	let parents = object.get_attr_lit(&Literal::__PARENTS__)?;
+	let parents = parents.downcast::<List>().map(|parents| parents.iter().cloned().collect::<Vec<_>>());
+
+	for parent in parents.unwrap_or_default() {
+		if is_instance(&parent, class, seen)? {
+			return Ok(true);
+		}
+	}
+
+	Ok(false)
+}
+
+fn matches_list(patterns: &[Object], values: &[Object], bindings: &mut Bindings) -> Result<bool> {
+	let rest = patterns.iter()
+		.position(|pattern| matches!(pattern.downcast::<Pattern>().as_deref(), Some(Pattern::Rest(_))));
+
+	let (before, after) =
+		match rest {
+			Some(idx) => (&patterns[..idx], &patterns[idx + 1..]),
+			None if patterns.len() == values.len() => (patterns, &[][..]),
+			None => return Ok(false)
+		};
+
+	if before.len() + after.len() > values.len() {
+		return Ok(false);
+	}
+
+	let middle = &values[before.len()..values.len() - after.len()];
+
+	for (pattern, value) in before.iter().zip(values).chain(after.iter().zip(&values[values.len() - after.len()..])) {
+		if !Pattern::matches(pattern, value, bindings)? {
+			return Ok(false);
+		}
+	}
+
+	if let Some(idx) = rest {
+		if let Pattern::Rest(Some(name)) = Clone::clone(&*patterns[idx].downcast::<Pattern>().unwrap()) {
+			bindings.push((name, List::from(middle.to_vec()).into()));
+		}
+	}
+
+	Ok(true)
+}
+
+impl Pattern {
+	/// Checks to see if `value` matches `pattern`, adding any variables it binds to `bindings`.
+	///
+	/// If the value doesn't match, `bindings` may have been partially filled in.
+	pub fn matches(pattern: &Object, value: &Object, bindings: &mut Bindings) -> Result<bool> {
+		if let Some(this) = pattern.downcast::<Self>().map(|this| Clone::clone(&*this)) {
+			return this.matches_value(value, bindings);
+		}
+
+		if let Some(patterns) = pattern.downcast::<List>().map(|list| list.iter().cloned().collect::<Vec<_>>()) {
+			return match value.downcast::<List>().map(|list| list.iter().cloned().collect::<Vec<_>>()) {
+				Some(values) => matches_list(&patterns, &values, bindings),
+				None => Ok(false)
+			};
+		}
+
+		if pattern.is_a::<Class>() {
+			return is_instance(value, pattern, &mut Vec::new());
+		}
+
+		pattern.eq_obj(value)
+	}
+
+	fn matches_value(&self, value: &Object, bindings: &mut Bindings) -> Result<bool> {
+		match self {
+			Self::Any => Ok(true),
+			Self::Bind(name, pattern) => {
+				if let Some(pattern) = pattern {
+					if !Self::matches(pattern, value, bindings)? {
+						return Ok(false);
+					}
+				}
+
+				bindings.push((name.clone(), value.clone()));
+				Ok(true)
+			},
+			Self::Rest(_) => Err(ValueError::Messaged("rest patterns can only be used within lists".into()).into()),
+			Self::Attrs(attrs) => {
+				for (attr, pattern) in attrs {
+					if !value.has_attr(attr)? || !Self::matches(pattern, &value.get_attr(attr)?, bindings)? {
+						return Ok(false);
+					}
+				}
+
+				Ok(true)
+			}
+		}
+	}
+}
+
+/// Runs `body` with `bindings` as local variables, returning its result.
+fn exec_with(body: &Object, bindings: &Bindings) -> Result<Object> {
+	let scope = Object::from(Scope);
+
+	for (name, value) in bindings {
+		scope.set_attr(name.clone(), value.clone())?;
+	}
+
+	scope.add_parent(body.clone())?;
+	scope.call_attr_lit("instance_exec", &[body])
+}
+
+#[derive(Debug, Default)]
+struct MatchState {
+	result: Option<Object>
+}
+
+/// Matches `value` against the arms declared by `block`, returning the result of the first one
+/// that matches.
+///
+/// `block` is executed with `case` and each of [`Pattern`]'s attributes in scope. Arms are declared
+/// with `case(pattern, body)` or `case(pattern, guard, body)`; once an arm has matched, the rest
+/// are ignored.
+pub fn run_match(value: &Object, block: &Object) -> Result<Object> {
+	let state = Arc::new(Mutex::new(MatchState::default()));
+	let matcher = Object::from(Scope);
+
+	matcher.set_attr_lit("case", RustClosure::new({
+		let state = state.clone();
+		let value = value.clone();
+
+		move |args| {
+			let (guard, body) =
+				match args.len() {
+					2 => (None, args.try_arg(1)?),
+					3 => (Some(args.try_arg(1)?), args.try_arg(2)?),
+					len => return Err(ValueError::Messaged(
+						format!("case takes 2 or 3 arguments (given {})", len)).into())
+				};
+
+			if state.lock().result.is_some() {
+				return Ok(Object::default());
+			}
+
+			let mut bindings = Bindings::new();
+
+			if !Pattern::matches(args.try_arg(0)?, &value, &mut bindings)? {
+				return Ok(Object::default());
+			}
+
+			if let Some(guard) = guard {
+				if !exec_with(guard, &bindings)?.call_downcast::<crate::types::Boolean>()?.into_inner() {
+					return Ok(Object::default());
+				}
+			}
+
+			let result = exec_with(body, &bindings)?;
+			state.lock().result = Some(result);
+
+			Ok(Object::default())
+		}
+	}).into())?;
+
+	matcher.add_parent(Pattern::mapping().clone())?;
+	matcher.add_parent(block.clone())?;
+	matcher.call_attr_lit("instance_exec", &[block])?;
+
+	let result = state.lock().result.take();
+
+	if let Some(result) = result {
+		Ok(result)
+	} else {
+		let inspected = value.call_attr_lit("inspect", &[])?;
+		Err(ValueError::Messaged(format!("no arm matched {}", inspected.call_downcast::<Text>()?.as_ref())).into())
+	}
+}
+
+/// Quest methods
+impl Pattern {
+	/// Creates a pattern that binds the matched value to a variable.
+	///
+	/// # Arguments
+	/// 1. (required) The name of the variable.
+	/// 2. (optional) A pattern the value must also match.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// $result = match([1, 2]) {
+	/// 	case([bind("x", Number), 2], { x * 10 });
+	/// };
+	/// assert(result == 10);
+	/// ```
+	#[instrument(name="Pattern::bind", level="trace", skip(args), fields(?args))]
+	pub fn qs_bind(args: Args) -> Result<Object> {
+		Ok(Self::Bind(args.try_arg(0)?.clone(), args.arg(1).cloned()).into())
+	}
+
+	/// Creates a pattern that matches the rest of a list.
+	///
+	/// # Arguments
+	/// 1. (optional) The name of the variable to bind the rest of the list to.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// $result = match([1, 2, 3]) {
+	/// 	case([bind("first"), rest("others")], { others });
+	/// };
+	/// assert(result == [2, 3]);
+	/// ```
+	#[instrument(name="Pattern::rest", level="trace", skip(args), fields(?args))]
+	pub fn qs_rest(args: Args) -> Result<Object> {
+		Ok(Self::Rest(args.arg(0).cloned()).into())
+	}
+
+	/// Creates a pattern that matches objects' attributes.
+	///
+	/// # Arguments
+	/// Pairs of attribute names and the patterns their values must match.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// $point = object() { x = 1; y = 2; };
+	/// $result = match(point) {
+	/// 	case(attrs("x", 0), { "on the y axis" });
+	/// 	case(attrs("x", bind("x"), "y", bind("y")), { x + y });
+	/// };
+	/// assert(result == 3);
+	/// ```
+	#[instrument(name="Pattern::attrs", level="trace", skip(args), fields(?args))]
+	pub fn qs_attrs(args: Args) -> Result<Object> {
+		let args = args.as_ref();
+
+		if args.len() % 2 != 0 {
+			return Err(ValueError::Messaged("attrs requires pairs of names and patterns".into()).into());
+		}
+
+		Ok(Self::Attrs(args.chunks(2).map(|pair| (pair[0].clone(), pair[1].clone())).collect()).into())
+	}
+
+	#[instrument(name="Pattern::inspect", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_inspect(this: &Object, _: Args) -> Result<Object> {
+		let this = this.try_downcast::<Self>()?;
+
+		Ok(match &*this {
+			Self::Any => "Pattern(any)".to_string(),
+			Self::Bind(name, _) => format!("Pattern(bind {})", name.call_downcast::<Text>()?.as_ref()),
+			Self::Rest(_) => "Pattern(rest)".to_string(),
+			Self::Attrs(attrs) => format!("Pattern(attrs, {} total)", attrs.len())
+		}.into())
+	}
+}
+
+impl_object_type!{
+for Pattern [(parents super::Basic)]:
+	"any" => const Self::Any,
+	"bind" => function Self::qs_bind,
+	"rest" => function Self::qs_rest,
+	"attrs" => function Self::qs_attrs,
+	"inspect" => method Self::qs_inspect,
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::types::Number;
+
+	fn list(items: Vec<Object>) -> Object {
+		List::from(items).into()
+	}
+
+	#[test]
+	fn literals_and_types() {
+		crate::init();
+
+		let mut bindings = Bindings::new();
+		assert!(Pattern::matches(&Object::from(1), &Object::from(1), &mut bindings).unwrap());
+		assert!(!Pattern::matches(&Object::from(1), &Object::from("1"), &mut bindings).unwrap());
+		assert!(Pattern::matches(Number::mapping(), &Object::from(1), &mut bindings).unwrap());
+		assert!(!Pattern::matches(Text::mapping(), &Object::from(1), &mut bindings).unwrap());
+		assert!(bindings.is_empty());
+	}
+
+	#[test]
+	fn lists_and_bindings() {
+		crate::init();
+
+		let pattern = list(vec![
+			Pattern::Bind("a".into(), None).into(),
+			Pattern::Rest(Some("middle".into())).into(),
+			Object::from(4)
+		]);
+
+		let mut bindings = Bindings::new();
+		assert!(Pattern::matches(&pattern, &list(vec![1.into(), 2.into(), 3.into(), 4.into()]), &mut bindings).unwrap());
+		assert_eq!(bindings.len(), 2);
+		assert_eq!(*bindings[0].1.downcast::<Number>().unwrap(), Number::from(1));
+		assert_eq!(bindings[1].1.downcast::<List>().unwrap().len(), 2);
+
+		assert!(Pattern::matches(&pattern, &list(vec![1.into(), 4.into()]), &mut Bindings::new()).unwrap());
+		assert!(!Pattern::matches(&pattern, &list(vec![4.into()]), &mut Bindings::new()).unwrap());
+		assert!(!Pattern::matches(&pattern, &list(vec![1.into(), 5.into()]), &mut Bindings::new()).unwrap());
+		assert!(!Pattern::matches(&list(vec![1.into()]), &list(vec![1.into(), 2.into()]), &mut Bindings::new()).unwrap());
+		assert!(Pattern::matches(&Pattern::Rest(None).into(), &Object::from(1), &mut Bindings::new()).is_err());
+	}
+
+	#[test]
+	fn attributes() {
+		crate::init();
+
+		let point = Object::from(Scope);
+		point.set_attr_lit("x", 1.into()).unwrap();
+
+		let pattern = Object::from(Pattern::Attrs(vec![("x".into(), Pattern::Bind("x".into(), None).into())]));
+		let mut bindings = Bindings::new();
+		assert!(Pattern::matches(&pattern, &point, &mut bindings).unwrap());
+		assert_eq!(bindings.len(), 1);
+
+		let pattern = Object::from(Pattern::Attrs(vec![("y".into(), Pattern::Any.into())]));
+		assert!(!Pattern::matches(&pattern, &point, &mut Bindings::new()).unwrap());
+	}
+
+	#[test]
+	fn case_argument_count() {
+		crate::init();
+
+		let block = Object::from(RustClosure::new(|_| {
+			let case = crate::Binding::instance().get_attr_lit("case")?;
+			let any = Object::from(Pattern::Any);
+			let body = Object::from(RustClosure::new(|_| Ok(1.into())));
+
+			case.call_attr_lit(&Literal::CALL, &[&any, &body, &body, &body])
+		}));
+
+		let err = run_match(&Object::from(1), &block).unwrap_err();
+		assert!(err.to_string().contains("case takes 2 or 3 arguments (given 4)"), "bad error: {}", err);
+	}
+}
@@ -0,0 +1,576 @@
+use crate::{Object, Args, Result};
+use crate::error::ValueError;
+use crate::types::{Text, Bytes, Boolean, List, Map, Null};
+use crate::types::io::File;
+
+use parking_lot::Mutex;
+use std::fmt::{self, Display, Formatter};
+use std::fs;
+use std::io::{self, Write};
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::instrument;
+
+/// How long [`Process::wait`] waits between checking whether the process has exited.
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// A process that was spawned from Quest.
+///
+/// Processes can be shared between threads: while one thread is [`wait`](Process::wait)ing,
+/// another can still [`kill`](Process::kill) it.
+#[derive(Debug, Clone)]
+pub struct Process(Arc<Inner>);
+
+#[derive(Debug)]
+struct Inner {
+	child: Mutex<Child>,
+	pid: u32,
+	// these are `Io::File`s, or `null` if the stream wasn't piped.
+	stdin: Object,
+	stdout: Object,
+	stderr: Object
+}
+
+/// How a process exited.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Status(ExitStatus);
+
+/// The result of [`Process::run`]: how the process exited, and everything it wrote.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Output {
+	status: Status,
+	stdout: Vec<u8>,
+	stderr: Vec<u8>,
+	binary: bool
+}
+
+/// The kind of handle that pipes to child processes can be converted into.
+#[cfg(unix)]
+type PipeHandle = std::os::unix::io::OwnedFd;
+
+/// The kind of handle that pipes to child processes can be converted into.
+#[cfg(windows)]
+type PipeHandle = std::os::windows::io::OwnedHandle;
+
+/// Wraps `pipe` (if there is one) in an `Io::File`.
+fn pipe_to_object(pipe: Option<impl Into<PipeHandle>>, binary: bool) -> Object {
+	pipe.map(|pipe| File::from_std(fs::File::from(pipe.into()), binary).into())
+		.unwrap_or_default()
+}
+
+/// Parses a `stdin`, `stdout`, or `stderr` option: one of `"pipe"`, `"inherit"`, or `"null"`.
+fn parse_stdio(obj: &Object) -> Result<Stdio> {
+	match obj.call_downcast::<Text>()?.as_ref() {
+		"pipe" => Ok(Stdio::piped()),
+		"inherit" => Ok(Stdio::inherit()),
+		"null" => Ok(Stdio::null()),
+		other => Err(ValueError::Messaged(
+			format!("stdio must be \"pipe\", \"inherit\", or \"null\", not {:?}", other)).into())
+	}
+}
+
+/// The options that processes are spawned with.
+#[derive(Debug, Default)]
+struct Options {
+	stdin: Option<Stdio>,
+	stdout: Option<Stdio>,
+	stderr: Option<Stdio>,
+	input: Option<Vec<u8>>,
+	binary: bool
+}
+
+/// Creates the command for `argv` (a list of the program and its arguments), and applies `options`
+/// to it.
+fn build_command(argv: &Object, options: Option<&Object>) -> Result<(Command, Options)> {
+	let argv = argv.call_downcast::<List>()?
+		.iter()
+		.map(|arg| arg.call_downcast::<Text>().map(|arg| arg.to_string()))
+		.collect::<Result<Vec<_>>>()?;
+
+	let (program, args) = argv.split_first()
+		.ok_or_else(|| ValueError::Messaged("the argv list must include the program".into()))?;
+
+	let mut command = Command::new(program);
+	let mut parsed = Options::default();
+	command.args(args);
+
+	let options =
+		match options {
+			Some(options) if !options.is_a::<Null>() => options.call_downcast::<Map>()?
+				.iter()
+				.map(|(key, value)| (key.clone(), value.clone()))
+				.collect::<Vec<_>>(),
+			_ => return Ok((command, parsed))
+		};
+
+	for (key, value) in options {
+		match key.call_downcast::<Text>()?.as_ref() {
+			"env" => {
+				let env = value.call_downcast::<Map>()?
+					.iter()
+					.map(|(key, value)| (key.clone(), value.clone()))
+					.collect::<Vec<_>>();
+
+				for (name, value) in env {
+					let name = name.call_downcast::<Text>()?.to_string();
+
+					if value.is_a::<Null>() {
+						command.env_remove(name);
+					} else {
+						command.env(name, value.call_downcast::<Text>()?.as_ref());
+					}
+				}
+			},
+			"clear_env" => if bool::from(*value.call_downcast::<Boolean>()?) {
+				command.env_clear();
+			},
+			"cwd" => { command.current_dir(value.call_downcast::<Text>()?.as_ref()); },
+			"stdin" => parsed.stdin = Some(parse_stdio(&value)?),
+			"stdout" => parsed.stdout = Some(parse_stdio(&value)?),
+			"stderr" => parsed.stderr = Some(parse_stdio(&value)?),
+			"input" => parsed.input = Some(crate::types::bytes::to_write(&value)?),
+			"binary" => parsed.binary = bool::from(*value.call_downcast::<Boolean>()?),
+			other => return Err(ValueError::Messaged(format!("unknown process option {:?}", other)).into())
+		}
+	}
+
+	Ok((command, parsed))
+}
+
+impl Process {
+	/// Spawns `command`, wrapping any piped streams in `Io::File`s (which are binary if `binary` is
+	/// set).
+	pub fn spawn(command: &mut Command, binary: bool) -> io::Result<Self> {
+		let mut child = command.spawn()?;
+
+		Ok(Self(Arc::new(Inner {
+			pid: child.id(),
+			stdin: pipe_to_object(child.stdin.take(), binary),
+			stdout: pipe_to_object(child.stdout.take(), binary),
+			stderr: pipe_to_object(child.stderr.take(), binary),
+			child: Mutex::new(child)
+		})))
+	}
+
+	/// Gets the process's id.
+	#[inline]
+	pub fn pid(&self) -> u32 {
+		self.0.pid
+	}
+
+	/// Checks to see if the process has exited, without waiting for it.
+	pub fn try_wait(&self) -> io::Result<Option<Status>> {
+		self.0.child.lock().try_wait().map(|status| status.map(Status))
+	}
+
+	/// Wait for the process to exit, giving up after `timeout` if it's given.
+	///
+	/// `None` is returned if the timeout expires.
+	pub fn wait(&self, timeout: Option<Duration>) -> io::Result<Option<Status>> {
+		let deadline = timeout.map(|timeout| Instant::now() + timeout);
+
+		// we poll so that the child isn't locked while waiting, which would prevent killing it.
+		loop {
+			if let Some(status) = self.try_wait()? {
+				return Ok(Some(status));
+			}
+
+			if matches!(deadline, Some(deadline) if deadline <= Instant::now()) {
+				return Ok(None);
+			}
+
+			std::thread::sleep(POLL_INTERVAL);
+		}
+	}
+
+	/// Forcibly kill the process. Killing a process that's already exited does nothing.
+	pub fn kill(&self) -> io::Result<()> {
+		let mut child = self.0.child.lock();
+
+		if child.try_wait()?.is_some() {
+			return Ok(());
+		}
+
+		match child.kill() {
+			Err(err) if err.kind() != io::ErrorKind::InvalidInput => Err(err),
+			_ => Ok(())
+		}
+	}
+
+	/// Runs `command` to completion, writing `input` to its stdin (if it's given) and capturing its
+	/// stdout and stderr.
+	pub fn run(command: &mut Command, input: Option<Vec<u8>>, binary: bool) -> io::Result<Output> {
+		command.stdout(Stdio::piped()).stderr(Stdio::piped());
+
+		if input.is_some() {
+			command.stdin(Stdio::piped());
+		}
+
+		let mut child = command.spawn()?;
+
+		// we write the input on its own thread so a process that fills up its stdout before reading
+		// all of its stdin won't deadlock.
+		let writer = input.and_then(|input| {
+			let mut stdin = child.stdin.take()?;
+			Some(std::thread::spawn(move || match stdin.write_all(&input) {
+				Err(err) if err.kind() != io::ErrorKind::BrokenPipe => Err(err),
+				_ => Ok(())
+			}))
+		});
+
+		let output = child.wait_with_output()?;
+
+		if let Some(writer) = writer {
+			writer.join().expect("writing to stdin panicked")?;
+		}
+
+		Ok(Output { status: Status(output.status), stdout: output.stdout, stderr: output.stderr, binary })
+	}
+}
+
+impl Status {
+	/// Gets the exit code, or `None` if the process was killed by a signal.
+	#[inline]
+	pub fn code(&self) -> Option<i32> {
+		self.0.code()
+	}
+
+	/// Gets the signal that killed the process, if it was killed by one.
+	#[cfg(unix)]
+	#[inline]
+	pub fn signal(&self) -> Option<i32> {
+		std::os::unix::process::ExitStatusExt::signal(&self.0)
+	}
+
+	/// Gets the signal that killed the process, if it was killed by one.
+	#[cfg(not(unix))]
+	#[inline]
+	pub fn signal(&self) -> Option<i32> {
+		None
+	}
+
+	/// Checks to see if the process exited successfully.
+	#[inline]
+	pub fn success(&self) -> bool {
+		self.0.success()
+	}
+}
+
+impl Display for Status {
+	#[inline]
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		Display::fmt(&self.0, f)
+	}
+}
+
+impl Output {
+	/// Gets how the process exited.
+	#[inline]
+	pub fn status(&self) -> Status {
+		self.status
+	}
+
+	/// Gets everything the process wrote to stdout.
+	#[inline]
+	pub fn stdout(&self) -> &[u8] {
+		&self.stdout
+	}
+
+	/// Gets everything the process wrote to stderr.
+	#[inline]
+	pub fn stderr(&self) -> &[u8] {
+		&self.stderr
+	}
+
+	/// Converts captured output to [`Bytes`] in binary mode, and [`Text`] otherwise (replacing any
+	/// invalid UTF-8).
+	fn captured(&self, data: &[u8]) -> Object {
+		if self.binary {
+			Bytes::from(data.to_vec()).into()
+		} else {
+			Text::from(String::from_utf8_lossy(data).into_owned()).into()
+		}
+	}
+}
+
+/// Quest methods
+impl Process {
+	/// Spawn a process, without waiting for it to finish.
+	///
+	/// The program is run directly, not through a shell, so its arguments don't need to be escaped.
+	///
+	/// # Arguments
+	/// 1. (required, [`List`]) The program followed by its arguments, each of which are `@text`.
+	/// 2. (optional, [`Map`]) Options for the process:
+	///    - `"env"`: A [`Map`] of environment variables to set; `null` values remove the variable.
+	///    - `"clear_env"`: If true, the process doesn't inherit any environment variables.
+	///    - `"cwd"`: The directory to run the process in.
+	///    - `"stdin"`, `"stdout"`, `"stderr"`: How to set up each stream: `"inherit"` (the default)
+	///      shares ours, `"pipe"` makes it available as an `Io::File`, and `"null"` discards it.
+	///    - `"binary"`: If true, piped streams read [`Bytes`] instead of [`Text`].
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// $proc = Process(["sort"], Map([["stdin", "pipe"], ["stdout", "pipe"]]));
+	/// proc.stdin().write("b\na\n");
+	/// proc.stdin().close();
+	/// assert(proc.stdout().read() == "a\nb\n");
+	/// assert(proc.wait().success?());
+	/// ```
+	#[instrument(name="Process::()", level="trace", skip(args), fields(?args))]
+	pub fn qs_call(_: &Object, args: Args) -> Result<Object> {
+		let (mut command, options) = build_command(args.try_arg(0)?, args.arg(1))?;
+
+		if options.input.is_some() {
+			return Err(ValueError::Messaged("the \"input\" option is only valid for `run`".into()).into());
+		}
+
+		command.stdin(options.stdin.unwrap_or_else(Stdio::inherit))
+			.stdout(options.stdout.unwrap_or_else(Stdio::inherit))
+			.stderr(options.stderr.unwrap_or_else(Stdio::inherit));
+
+		Ok(Self::spawn(&mut command, options.binary)?.into())
+	}
+
+	/// Run a process to completion, capturing its stdout and stderr.
+	///
+	/// This returns a `Process::Output`, regardless of whether the process succeeded.
+	///
+	/// # Arguments
+	/// 1. (required, [`List`]) The program followed by its arguments, each of which are `@text`.
+	/// 2. (optional, [`Map`]) The same options as [`Process`](#method.qs_call) (except `stdout` and
+	///    `stderr`, which are always captured), along with:
+	///    - `"input"`: [`Bytes`] or `@text` to write to the process's stdin. If not given, stdin is
+	///      `"null"`.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// $out = Process.run(["tr", "a-z", "A-Z"], Map([["input", "hello"]]));
+	/// assert(out.success?());
+	/// assert(out.stdout() == "HELLO");
+	/// ```
+	#[instrument(name="Process::run", level="trace", skip(args), fields(?args))]
+	pub fn qs_run(_: &Object, args: Args) -> Result<Object> {
+		let (mut command, options) = build_command(args.try_arg(0)?, args.arg(1))?;
+
+		if options.stdout.is_some() || options.stderr.is_some() {
+			return Err(ValueError::Messaged("`run` always captures stdout and stderr".into()).into());
+		}
+
+		let stdin =
+			match (options.stdin, &options.input) {
+				(Some(_), Some(_)) =>
+					return Err(ValueError::Messaged("\"stdin\" and \"input\" can't both be given".into()).into()),
+				(Some(stdin), None) => stdin,
+				(None, Some(_)) => Stdio::piped(),
+				(None, None) => Stdio::null()
+			};
+
+		command.stdin(stdin);
+
+		Ok(Self::run(&mut command, options.input, options.binary)?.into())
+	}
+
+	/// Get the process's id.
+	#[instrument(name="Process::pid", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_pid(this: &Object, _: Args) -> Result<Object> {
+		Ok(this.try_downcast::<Self>()?.pid().into())
+	}
+
+	/// Get the process's stdin as an `Io::File`, or [`Null`] if it wasn't piped.
+	///
+	/// Close it to signal to the process that there's no more input.
+	#[instrument(name="Process::stdin", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_stdin(this: &Object, _: Args) -> Result<Object> {
+		Ok(this.try_downcast::<Self>()?.0.stdin.clone())
+	}
+
+	/// Get the process's stdout as an `Io::File`, or [`Null`] if it wasn't piped.
+	#[instrument(name="Process::stdout", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_stdout(this: &Object, _: Args) -> Result<Object> {
+		Ok(this.try_downcast::<Self>()?.0.stdout.clone())
+	}
+
+	/// Get the process's stderr as an `Io::File`, or [`Null`] if it wasn't piped.
+	#[instrument(name="Process::stderr", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_stderr(this: &Object, _: Args) -> Result<Object> {
+		Ok(this.try_downcast::<Self>()?.0.stderr.clone())
+	}
+
+	/// Wait for the process to exit, returning its `Process::Status`.
+	///
+	/// # Arguments
+	/// 1. (optional, [`Duration`](super::Duration) or `@num`) How long to wait; numbers are in
+	///    seconds. If the timeout expires, [`Null`] is returned. If omitted, waits until the process
+	///    exits.
+	#[instrument(name="Process::wait", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_wait(this: &Object, args: Args) -> Result<Object> {
+		let timeout = args.arg(0).map(super::duration::to_std).transpose()?;
+		let this = this.try_downcast::<Self>()?.clone();
+
+		Ok(this.wait(timeout)?.map(Object::from).unwrap_or_default())
+	}
+
+	/// Checks to see if the process is still running.
+	#[instrument(name="Process::running?", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_running_q(this: &Object, _: Args) -> Result<Object> {
+		Ok(this.try_downcast::<Self>()?.try_wait()?.is_none().into())
+	}
+
+	/// Forcibly kill the process, returning `this`.
+	#[instrument(name="Process::kill", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_kill(this: &Object, _: Args) -> Result<Object> {
+		this.try_downcast::<Self>()?.kill()?;
+
+		Ok(this.clone())
+	}
+
+	/// Gets a debugging representation of `this`, such as `Process(1234)`.
+	#[instrument(name="Process::inspect", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_inspect(this: &Object, _: Args) -> Result<Object> {
+		Ok(Text::from(format!("Process({})", this.try_downcast::<Self>()?.pid())).into())
+	}
+}
+
+impl_object_type!{
+for Process [(parents super::Basic)]:
+	"Status" => const Status::mapping().clone(),
+	"Output" => const Output::mapping().clone(),
+	"()" => method Self::qs_call,
+	"run" => method Self::qs_run,
+	"pid" => method Self::qs_pid,
+	"stdin" => method Self::qs_stdin,
+	"stdout" => method Self::qs_stdout,
+	"stderr" => method Self::qs_stderr,
+	"wait" => method Self::qs_wait,
+	"running?" => method Self::qs_running_q,
+	"kill" => method Self::qs_kill,
+	"inspect" => method Self::qs_inspect,
+}
+
+/// Quest methods
+impl Status {
+	/// Gets the exit code, or [`Null`] if the process was killed by a signal.
+	#[instrument(name="Process::Status::code", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_code(this: &Object, _: Args) -> Result<Object> {
+		Ok(this.try_downcast::<Self>()?.code().map(Object::from).unwrap_or_default())
+	}
+
+	/// Gets the signal that killed the process, or [`Null`] if it wasn't killed by one.
+	#[instrument(name="Process::Status::signal", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_signal(this: &Object, _: Args) -> Result<Object> {
+		Ok(this.try_downcast::<Self>()?.signal().map(Object::from).unwrap_or_default())
+	}
+
+	/// Checks to see if the process exited successfully.
+	#[instrument(name="Process::Status::success?", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_success_q(this: &Object, _: Args) -> Result<Object> {
+		Ok(this.try_downcast::<Self>()?.success().into())
+	}
+
+	/// Gets a description of the status, such as `exit status: 1`.
+	#[instrument(name="Process::Status::@text", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_at_text(this: &Object, _: Args) -> Result<Object> {
+		Ok(Text::from(this.try_downcast::<Self>()?.to_string()).into())
+	}
+
+	/// Gets a debugging representation of `this`, such as `Process::Status(exit status: 1)`.
+	#[instrument(name="Process::Status::inspect", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_inspect(this: &Object, _: Args) -> Result<Object> {
+		Ok(Text::from(format!("Process::Status({})", *this.try_downcast::<Self>()?)).into())
+	}
+}
+
+impl_object_type!{
+for Status [(parents super::Basic)]:
+	"code" => method Self::qs_code,
+	"signal" => method Self::qs_signal,
+	"success?" => method Self::qs_success_q,
+	"@bool" => method Self::qs_success_q,
+	"@text" => method Self::qs_at_text,
+	"inspect" => method Self::qs_inspect,
+}
+
+/// Quest methods
+impl Output {
+	/// Gets how the process exited, as a `Process::Status`.
+	#[instrument(name="Process::Output::status", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_status(this: &Object, _: Args) -> Result<Object> {
+		Ok(this.try_downcast::<Self>()?.status.into())
+	}
+
+	/// Checks to see if the process exited successfully.
+	#[instrument(name="Process::Output::success?", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_success_q(this: &Object, _: Args) -> Result<Object> {
+		Ok(this.try_downcast::<Self>()?.status.success().into())
+	}
+
+	/// Gets everything the process wrote to stdout.
+	///
+	/// This is [`Bytes`] if the `"binary"` option was given, and [`Text`] otherwise.
+	#[instrument(name="Process::Output::stdout", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_stdout(this: &Object, _: Args) -> Result<Object> {
+		let this = this.try_downcast::<Self>()?;
+
+		Ok(this.captured(&this.stdout))
+	}
+
+	/// Gets everything the process wrote to stderr.
+	///
+	/// This is [`Bytes`] if the `"binary"` option was given, and [`Text`] otherwise.
+	#[instrument(name="Process::Output::stderr", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_stderr(this: &Object, _: Args) -> Result<Object> {
+		let this = this.try_downcast::<Self>()?;
+
+		Ok(this.captured(&this.stderr))
+	}
+
+	/// Gets a debugging representation of `this`, such as `Process::Output(exit status: 0)`.
+	#[instrument(name="Process::Output::inspect", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_inspect(this: &Object, _: Args) -> Result<Object> {
+		Ok(Text::from(format!("Process::Output({})", this.try_downcast::<Self>()?.status)).into())
+	}
+}
+
+impl_object_type!{
+for Output [(parents super::Basic)]:
+	"status" => method Self::qs_status,
+	"success?" => method Self::qs_success_q,
+	"stdout" => method Self::qs_stdout,
+	"stderr" => method Self::qs_stderr,
+	"inspect" => method Self::qs_inspect,
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn run_and_capture() {
+		let mut command = Command::new("sh");
+		command.args(["-c", "tr a-z A-Z; echo oops >&2; exit 3"]).env("UNUSED", "1");
+
+		let output = Process::run(&mut command, Some(b"hello".to_vec()), false).unwrap();
+
+		assert_eq!(output.stdout(), b"HELLO");
+		assert_eq!(output.stderr(), b"oops\n");
+		assert_eq!(output.status().code(), Some(3));
+		assert!(!output.status().success());
+	}
+
+	#[test]
+	fn wait_and_kill() {
+		let proc = Process::spawn(Command::new("sleep").arg("10"), false).unwrap();
+
+		assert_eq!(proc.wait(Some(Duration::from_millis(20))).unwrap(), None);
+		proc.kill().unwrap();
+
+		let status = proc.wait(Some(Duration::from_secs(5))).unwrap().expect("process wasn't killed");
+		assert_eq!(status.code(), None);
+		assert_eq!(status.signal(), Some(9));
+
+		// killing a process that's already exited is fine.
+		proc.kill().unwrap();
+	}
+}
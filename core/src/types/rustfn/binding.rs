@@ -104,6 +104,7 @@ impl Binding {
 			match func(&binding) {
 				Err(crate::Error::Return { to, obj }) if to.as_ref().eq_obj(binding.as_ref())?
 					=> Ok(obj),
+				Err(err) => Err(err.raised()),
 				other => other
 			}
 		})
@@ -133,6 +134,7 @@ impl Binding {
 			match func(&binding) {
 				Err(crate::Error::Return { to, obj }) if to.as_ref().eq_obj(binding.as_ref())?
 					=> Ok(obj),
+				Err(err) => Err(err.raised()),
 				other => other
 			}
 		})
@@ -0,0 +1,449 @@
+use crate::{Object, Args, Literal};
+use crate::types::{Text, Boolean, List, Iter, Map};
+use std::convert::TryFrom;
+use tracing::instrument;
+
+/// An insertion-ordered collection of unique objects.
+///
+/// Like [`Map`] keys, elements are hashed with their `hash` attribute and compared with `==`, so
+/// checking to see if an element is in the set is O(1).
+#[derive(Debug, Clone, Default)]
+pub struct Set(Map);
+
+/// Rust-centric set methods
+impl Set {
+	/// Create a new, empty set.
+	#[inline]
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Create a set from the elements of `iter`.
+	pub fn try_from_iter(iter: impl IntoIterator<Item=crate::Result<Object>>) -> crate::Result<Self> {
+		let mut set = Self::new();
+
+		for obj in iter {
+			set.insert(obj?)?;
+		}
+
+		Ok(set)
+	}
+
+	/// Get the amount of elements in the set.
+	#[inline]
+	pub fn len(&self) -> usize {
+		self.0.len()
+	}
+
+	/// Checks to see if the set is empty.
+	#[inline]
+	pub fn is_empty(&self) -> bool {
+		self.0.is_empty()
+	}
+
+	/// Get an iterator over the elements of the set, in insertion order.
+	#[inline]
+	pub fn iter(&self) -> impl Iterator<Item=&Object> {
+		self.0.keys()
+	}
+
+	/// Checks to see if `obj` is in the set.
+	#[inline]
+	pub fn contains(&self, obj: &Object) -> crate::Result<bool> {
+		self.0.contains_key(obj)
+	}
+
+	/// Add `obj` to the set, returning whether it was newly added.
+	#[inline]
+	pub fn insert(&mut self, obj: Object) -> crate::Result<bool> {
+		self.0.insert(obj, Object::default()).map(|old| old.is_none())
+	}
+
+	/// Remove `obj` from the set, returning whether it was in the set.
+	#[inline]
+	pub fn remove(&mut self, obj: &Object) -> crate::Result<bool> {
+		self.0.remove(obj).map(|old| old.is_some())
+	}
+
+	/// Removes all elements from the set.
+	#[inline]
+	pub fn clear(&mut self) {
+		self.0.clear();
+	}
+
+	/// Create a set of the elements in `self` that `func` returns true for.
+	fn filter(&self, mut func: impl FnMut(&Object) -> crate::Result<bool>) -> crate::Result<Self> {
+		let mut set = Self::new();
+
+		for obj in self.iter() {
+			if func(obj)? {
+				set.insert(obj.clone())?;
+			}
+		}
+
+		Ok(set)
+	}
+
+	/// Create a set of the elements in either `self` or `rhs`.
+	pub fn union(&self, rhs: &Self) -> crate::Result<Self> {
+		let mut set = self.clone();
+
+		for obj in rhs.iter() {
+			set.insert(obj.clone())?;
+		}
+
+		Ok(set)
+	}
+
+	/// Create a set of the elements in both `self` and `rhs`.
+	#[inline]
+	pub fn intersection(&self, rhs: &Self) -> crate::Result<Self> {
+		self.filter(|obj| rhs.contains(obj))
+	}
+
+	/// Create a set of the elements in `self` but not `rhs`.
+	#[inline]
+	pub fn difference(&self, rhs: &Self) -> crate::Result<Self> {
+		self.filter(|obj| rhs.contains(obj).map(|contains| !contains))
+	}
+
+	/// Create a set of the elements in exactly one of `self` and `rhs`.
+	pub fn symmetric_difference(&self, rhs: &Self) -> crate::Result<Self> {
+		self.difference(rhs)?.union(&rhs.difference(self)?)
+	}
+
+	/// Checks to see if every element in `self` is also in `rhs`.
+	pub fn is_subset(&self, rhs: &Self) -> crate::Result<bool> {
+		if self.len() > rhs.len() {
+			return Ok(false);
+		}
+
+		for obj in self.iter() {
+			if !rhs.contains(obj)? {
+				return Ok(false);
+			}
+		}
+
+		Ok(true)
+	}
+
+	/// Checks to see if every element in `rhs` is also in `self`.
+	#[inline]
+	pub fn is_superset(&self, rhs: &Self) -> crate::Result<bool> {
+		rhs.is_subset(self)
+	}
+
+	/// Checks to see if `self` and `rhs` have the same elements, regardless of order.
+	#[inline]
+	pub fn eql(&self, rhs: &Self) -> crate::Result<bool> {
+		Ok(self.len() == rhs.len() && self.is_subset(rhs)?)
+	}
+}
+
+impl TryFrom<&Set> for Text {
+	type Error = crate::Error;
+
+	fn try_from(set: &Set) -> crate::Result<Self> {
+		let mut elements = Vec::with_capacity(set.len());
+
+		for obj in set.iter() {
+			elements.push(obj.call_attr_lit(&Literal::INSPECT, &[])?.call_downcast::<Text>()?.to_string());
+		}
+
+		Ok(format!("Set({})", elements.join(", ")).into())
+	}
+}
+
+impl From<&Set> for Boolean {
+	#[inline]
+	fn from(set: &Set) -> Self {
+		(!set.is_empty()).into()
+	}
+}
+
+impl From<&Set> for List {
+	#[inline]
+	fn from(set: &Set) -> Self {
+		set.iter().cloned().collect()
+	}
+}
+
+/// Quest methods
+impl Set {
+	/// Create a new set, optionally with initial elements.
+	///
+	/// # Arguments
+	/// 1. (optional, `@iter`) The initial elements.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// set = Set([1, 2, 2, 3]);
+	///
+	/// assert(set.len() == 3);
+	/// assert(set.include?(2));
+	/// ```
+	#[instrument(name="Set::()", level="trace", skip(args), fields(?args))]
+	pub fn qs_call(_: &Object, args: Args) -> crate::Result<Object> {
+		if let Some(elements) = args.arg(0) {
+			Ok(Self::try_from_iter(Iter::from_object(elements)?)?.into())
+		} else {
+			Ok(Self::new().into())
+		}
+	}
+
+	/// Simply returns `this`.
+	#[instrument(name="Set::@set", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_at_set(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.clone())
+	}
+
+	/// Converts `this` into a [`Text`] of the form `Set(ele, ...)`.
+	#[instrument(name="Set::@text", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_at_text(this: &Object, _: Args) -> crate::Result<Object> {
+		let this = this.try_downcast::<Self>()?.clone();
+
+		Ok(Text::try_from(&this)?.into())
+	}
+
+	/// Inspects `this`.
+	///
+	/// This is identical to [`qs_at_text`](#qs_at_text).
+	#[instrument(name="Set::inspect", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_inspect(this: &Object, args: Args) -> crate::Result<Object> {
+		Self::qs_at_text(this, args)
+	}
+
+	/// Converts `this` into a [`Boolean`]; only empty sets are false.
+	#[instrument(name="Set::@bool", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_at_bool(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(Boolean::from(&*this.try_downcast::<Self>()?).into())
+	}
+
+	/// Converts `this` into a [`List`], in insertion order.
+	#[instrument(name="Set::@list", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_at_list(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(List::from(&*this.try_downcast::<Self>()?).into())
+	}
+
+	/// Get an [`Iter`] over the elements of `this`.
+	#[instrument(name="Set::@iter", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_at_iter(this: &Object, _: Args) -> crate::Result<Object> {
+		let elements = List::from(&*this.try_downcast::<Self>()?);
+
+		Ok(Iter::with_objects(elements).into())
+	}
+
+	/// Get the amount of elements in `this`.
+	#[instrument(name="Set::len", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_len(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.len().into())
+	}
+
+	/// Checks to see if `this` has no elements.
+	#[instrument(name="Set::empty?", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_empty_q(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.is_empty().into())
+	}
+
+	/// Checks to see if the first argument is in `this`.
+	///
+	/// # Arguments
+	/// 1. (required) The element to look for.
+	#[instrument(name="Set::include?", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_include_q(this: &Object, args: Args) -> crate::Result<Object> {
+		let obj = args.try_arg(0)?;
+
+		Ok(this.try_downcast::<Self>()?.contains(obj)?.into())
+	}
+
+	/// Adds the first argument to `this`, returning `this`.
+	///
+	/// # Arguments
+	/// 1. (required) The element to add.
+	#[instrument(name="Set::add", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_add(this: &Object, args: Args) -> crate::Result<Object> {
+		let obj = args.try_arg(0)?.clone();
+
+		this.try_downcast_mut::<Self>()?.insert(obj)?;
+
+		Ok(this.clone())
+	}
+
+	/// Removes the first argument from `this`, returning whether it was in `this`.
+	///
+	/// # Arguments
+	/// 1. (required) The element to remove.
+	#[instrument(name="Set::delete", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_delete(this: &Object, args: Args) -> crate::Result<Object> {
+		let obj = args.try_arg(0)?;
+
+		Ok(this.try_downcast_mut::<Self>()?.remove(obj)?.into())
+	}
+
+	/// Removes all elements from `this`, returning `this`.
+	#[instrument(name="Set::clear", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_clear(this: &Object, _: Args) -> crate::Result<Object> {
+		this.try_downcast_mut::<Self>()?.clear();
+
+		Ok(this.clone())
+	}
+
+	/// Get the elements in either `this` or the first argument.
+	///
+	/// # Arguments
+	/// 1. (required, `@set`) The other set.
+	#[instrument(name="Set::|", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_union(this: &Object, args: Args) -> crate::Result<Object> {
+		let rhs = args.try_arg(0)?.call_downcast::<Self>()?.clone();
+		let this = this.try_downcast::<Self>()?.clone();
+
+		Ok(this.union(&rhs)?.into())
+	}
+
+	/// Get the elements in both `this` and the first argument.
+	///
+	/// # Arguments
+	/// 1. (required, `@set`) The other set.
+	#[instrument(name="Set::&", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_intersection(this: &Object, args: Args) -> crate::Result<Object> {
+		let rhs = args.try_arg(0)?.call_downcast::<Self>()?.clone();
+		let this = this.try_downcast::<Self>()?.clone();
+
+		Ok(this.intersection(&rhs)?.into())
+	}
+
+	/// Get the elements in `this` but not the first argument.
+	///
+	/// # Arguments
+	/// 1. (required, `@set`) The other set.
+	#[instrument(name="Set::-", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_difference(this: &Object, args: Args) -> crate::Result<Object> {
+		let rhs = args.try_arg(0)?.call_downcast::<Self>()?.clone();
+		let this = this.try_downcast::<Self>()?.clone();
+
+		Ok(this.difference(&rhs)?.into())
+	}
+
+	/// Get the elements in exactly one of `this` and the first argument.
+	///
+	/// # Arguments
+	/// 1. (required, `@set`) The other set.
+	#[instrument(name="Set::^", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_symmetric_difference(this: &Object, args: Args) -> crate::Result<Object> {
+		let rhs = args.try_arg(0)?.call_downcast::<Self>()?.clone();
+		let this = this.try_downcast::<Self>()?.clone();
+
+		Ok(this.symmetric_difference(&rhs)?.into())
+	}
+
+	/// Checks to see if every element of `this` is in the first argument.
+	///
+	/// # Arguments
+	/// 1. (required, `@set`) The other set.
+	#[instrument(name="Set::subset?", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_subset_q(this: &Object, args: Args) -> crate::Result<Object> {
+		let rhs = args.try_arg(0)?.call_downcast::<Self>()?.clone();
+		let this = this.try_downcast::<Self>()?.clone();
+
+		Ok(this.is_subset(&rhs)?.into())
+	}
+
+	/// Checks to see if every element of the first argument is in `this`.
+	///
+	/// # Arguments
+	/// 1. (required, `@set`) The other set.
+	#[instrument(name="Set::superset?", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_superset_q(this: &Object, args: Args) -> crate::Result<Object> {
+		let rhs = args.try_arg(0)?.call_downcast::<Self>()?.clone();
+		let this = this.try_downcast::<Self>()?.clone();
+
+		Ok(this.is_superset(&rhs)?.into())
+	}
+
+	/// Checks to see if `this` is equal to the first argument.
+	///
+	/// Sets are equal if they have the same elements, regardless of insertion order.
+	///
+	/// # Arguments
+	/// 1. (required) The other object to compare against.
+	#[instrument(name="Set::==", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_eql(this: &Object, args: Args) -> crate::Result<Object> {
+		let rhs = args.try_arg(0)?;
+
+		if this.is_identical(rhs) {
+			return Ok(true.into());
+		}
+
+		let rhs =
+			match rhs.downcast::<Self>() {
+				Some(rhs) => rhs.clone(),
+				None => return Ok(false.into())
+			};
+
+		let this = this.try_downcast::<Self>()?.clone();
+
+		Ok(this.eql(&rhs)?.into())
+	}
+}
+
+impl crate::types::Convertible for Set {
+	const CONVERT_FUNC: Literal = Literal::new("@set");
+}
+
+impl_object_type!{
+for Set [(init_parent super::Basic super::Iterable) (parents super::Basic)]:
+	"()" => method Self::qs_call,
+	"@set" => method Self::qs_at_set,
+	"@text" => method Self::qs_at_text,
+	"inspect" => method Self::qs_inspect,
+	"@bool" => method Self::qs_at_bool,
+	"@list" => method Self::qs_at_list,
+	"@iter" => method Self::qs_at_iter,
+
+	"len" => method Self::qs_len,
+	"empty?" => method Self::qs_empty_q,
+	"include?" => method Self::qs_include_q,
+	"add" => method Self::qs_add,
+	"<<" => method Self::qs_add,
+	"delete" => method Self::qs_delete,
+	"clear" => method Self::qs_clear,
+
+	"|" => method Self::qs_union,
+	"&" => method Self::qs_intersection,
+	"-" => method Self::qs_difference,
+	"^" => method Self::qs_symmetric_difference,
+	"union" => method Self::qs_union,
+	"intersection" => method Self::qs_intersection,
+	"difference" => method Self::qs_difference,
+	"symmetric_difference" => method Self::qs_symmetric_difference,
+	"subset?" => method Self::qs_subset_q,
+	"superset?" => method Self::qs_superset_q,
+	"==" => method Self::qs_eql,
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn set(elements: &[i64]) -> Set {
+		Set::try_from_iter(elements.iter().map(|&n| Ok(n.into()))).unwrap()
+	}
+
+	#[test]
+	fn algebra() {
+		crate::init();
+
+		let lhs = set(&[1, 2, 3, 3]);
+		let rhs = set(&[3, 4]);
+
+		assert_eq!(lhs.len(), 3);
+		assert!(lhs.union(&rhs).unwrap().eql(&set(&[1, 2, 3, 4])).unwrap());
+		assert!(lhs.intersection(&rhs).unwrap().eql(&set(&[3])).unwrap());
+		assert!(lhs.difference(&rhs).unwrap().eql(&set(&[1, 2])).unwrap());
+		assert!(lhs.symmetric_difference(&rhs).unwrap().eql(&set(&[4, 2, 1])).unwrap());
+
+		assert!(set(&[1, 3]).is_subset(&lhs).unwrap());
+		assert!(!rhs.is_subset(&lhs).unwrap());
+		assert!(lhs.is_superset(&set(&[])).unwrap());
+	}
+}
@@ -0,0 +1,752 @@
+//! Coordinating threads created by [`Kernel::spawn`](super::Kernel).
+
+use crate::{Object, Args, Literal, Result};
+use crate::error::ValueError;
+use crate::types::{Text, Number, Iter};
+use parking_lot::{Condvar as RawCondvar, Mutex as RawMutex};
+use std::collections::VecDeque;
+use std::convert::TryFrom;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::thread::{self, ThreadId};
+use std::time::{Duration, Instant};
+use tracing::instrument;
+
+/// A lock that protects a value, so only one thread can use it at a time.
+///
+/// Unlike most locks, this isn't reentrant: locking a mutex that the current thread already holds
+/// is an error, rather than a deadlock.
+#[derive(Debug, Clone)]
+pub struct Mutex(Arc<MutexInner>);
+
+#[derive(Debug)]
+struct MutexInner {
+	/// The thread holding the lock, if any.
+	owner: RawMutex<Option<ThreadId>>,
+	unlocked: RawCondvar,
+	value: RawMutex<Object>
+}
+
+/// Releases a [`Mutex`] when dropped, so it's released even if the block raises an error.
+struct MutexGuard<'a>(&'a MutexInner);
+
+impl Drop for MutexGuard<'_> {
+	fn drop(&mut self) {
+		*self.0.owner.lock() = None;
+		self.0.unlocked.notify_one();
+	}
+}
+
+impl Mutex {
+	/// Creates a new, unlocked mutex protecting `value`.
+	pub fn new(value: Object) -> Self {
+		Self(Arc::new(MutexInner {
+			owner: RawMutex::new(None),
+			unlocked: RawCondvar::new(),
+			value: RawMutex::new(value)
+		}))
+	}
+
+	/// Checks to see if any thread currently holds the lock.
+	pub fn is_locked(&self) -> bool {
+		self.0.owner.lock().is_some()
+	}
+
+	/// Checks to see if the current thread holds the lock.
+	fn is_held(&self) -> bool {
+		*self.0.owner.lock() == Some(thread::current().id())
+	}
+
+	/// Waits for the lock, and then holds it until the returned guard is dropped.
+	fn acquire(&self) -> Result<MutexGuard<'_>> {
+		let current = thread::current().id();
+		let mut owner = self.0.owner.lock();
+
+		if *owner == Some(current) {
+			return Err(ValueError::Messaged("mutex is already locked by this thread".into()).into());
+		}
+
+		while owner.is_some() {
+			self.0.unlocked.wait(&mut owner);
+		}
+
+		*owner = Some(current);
+
+		Ok(MutexGuard(&self.0))
+	}
+
+	/// Calls `func` with the protected value while holding the lock.
+	pub fn with_lock<F: FnOnce(&Object) -> Result<Object>>(&self, func: F) -> Result<Object> {
+		let _guard = self.acquire()?;
+		let value = self.0.value.lock().clone();
+
+		func(&value)
+	}
+}
+
+/// A way for threads to wait until another thread notifies them, tied to a [`Mutex`].
+#[derive(Debug, Clone)]
+pub struct Condvar(Arc<CondvarInner>);
+
+#[derive(Debug)]
+struct CondvarInner {
+	mutex: Mutex,
+	condvar: RawCondvar
+}
+
+impl Condvar {
+	/// Creates a new condvar that's used with `mutex`.
+	pub fn new(mutex: Mutex) -> Self {
+		Self(Arc::new(CondvarInner { mutex, condvar: RawCondvar::new() }))
+	}
+
+	/// Releases the mutex, waits until notified (or until `timeout` passes), and then reacquires
+	/// the mutex. Returns whether it was notified.
+	///
+	/// The mutex must be held by the current thread.
+	pub fn wait(&self, timeout: Option<Duration>) -> Result<bool> {
+		let mutex = &self.0.mutex.0;
+		let mut owner = mutex.owner.lock();
+		let current = thread::current().id();
+
+		if *owner != Some(current) {
+			return Err(ValueError::Messaged("condvar's mutex isn't locked by this thread".into()).into());
+		}
+
+		*owner = None;
+		mutex.unlocked.notify_one();
+
+		let notified =
+			match timeout {
+				Some(timeout) => !self.0.condvar.wait_for(&mut owner, timeout).timed_out(),
+				None => { self.0.condvar.wait(&mut owner); true }
+			};
+
+		while owner.is_some() {
+			mutex.unlocked.wait(&mut owner);
+		}
+
+		*owner = Some(current);
+
+		Ok(notified)
+	}
+}
+
+/// A queue for sending values between threads, which can optionally hold a limited amount.
+///
+/// Once a channel is closed, nothing more can be sent, but values that were already sent can still
+/// be received.
+#[derive(Debug, Clone)]
+pub struct Channel(Arc<ChannelInner>);
+
+#[derive(Debug)]
+struct ChannelInner {
+	state: RawMutex<ChannelState>,
+	capacity: Option<usize>,
+	not_empty: RawCondvar,
+	not_full: RawCondvar
+}
+
+#[derive(Debug, Default)]
+struct ChannelState {
+	queue: VecDeque<Object>,
+	closed: bool
+}
+
+impl Channel {
+	/// Creates a new channel, which holds at most `capacity` values, if given.
+	pub fn new(capacity: Option<usize>) -> Self {
+		Self(Arc::new(ChannelInner {
+			state: RawMutex::new(ChannelState::default()),
+			capacity,
+			not_empty: RawCondvar::new(),
+			not_full: RawCondvar::new()
+		}))
+	}
+
+	/// Sends `value`, waiting for room if the channel is full.
+	///
+	/// If the channel is closed, `value` is given back.
+	pub fn send(&self, value: Object) -> std::result::Result<(), Object> {
+		let mut state = self.0.state.lock();
+
+		while !state.closed && matches!(self.0.capacity, Some(capacity) if state.queue.len() >= capacity) {
+			self.0.not_full.wait(&mut state);
+		}
+
+		if state.closed {
+			return Err(value);
+		}
+
+		state.queue.push_back(value);
+		self.0.not_empty.notify_one();
+
+		Ok(())
+	}
+
+	/// Receives a value, waiting until one is sent, the channel is closed, or `timeout` passes.
+	///
+	/// `None` is returned if nothing was received.
+	pub fn recv(&self, timeout: Option<Duration>) -> Option<Object> {
+		let deadline = timeout.map(|timeout| Instant::now() + timeout);
+		let mut state = self.0.state.lock();
+
+		while state.queue.is_empty() && !state.closed {
+			match deadline {
+				Some(deadline) => if self.0.not_empty.wait_until(&mut state, deadline).timed_out() {
+					break;
+				},
+				None => self.0.not_empty.wait(&mut state)
+			}
+		}
+
+		let value = state.queue.pop_front();
+
+		if value.is_some() {
+			self.0.not_full.notify_one();
+		}
+
+		value
+	}
+
+	/// Receives a value if there's one available, without waiting.
+	pub fn try_recv(&self) -> Option<Object> {
+		let value = self.0.state.lock().queue.pop_front();
+
+		if value.is_some() {
+			self.0.not_full.notify_one();
+		}
+
+		value
+	}
+
+	/// Closes the channel, waking up everything that's waiting on it.
+	pub fn close(&self) {
+		self.0.state.lock().closed = true;
+		self.0.not_empty.notify_all();
+		self.0.not_full.notify_all();
+	}
+
+	/// Checks to see if the channel is closed.
+	pub fn is_closed(&self) -> bool {
+		self.0.state.lock().closed
+	}
+
+	/// Gets the amount of values waiting to be received.
+	pub fn len(&self) -> usize {
+		self.0.state.lock().queue.len()
+	}
+
+	/// Checks to see if there are no values waiting to be received.
+	pub fn is_empty(&self) -> bool {
+		self.0.state.lock().queue.is_empty()
+	}
+}
+
+/// An integer that can be safely updated by multiple threads at once.
+#[derive(Debug, Clone, Default)]
+pub struct Atomic(Arc<AtomicI64>);
+
+impl Atomic {
+	/// Creates a new atomic integer.
+	pub fn new(value: i64) -> Self {
+		Self(Arc::new(AtomicI64::new(value)))
+	}
+
+	/// Gets the current value.
+	pub fn get(&self) -> i64 {
+		self.0.load(Ordering::SeqCst)
+	}
+}
+
+fn to_i64(obj: &Object) -> Result<i64> {
+	Ok(i64::try_from(obj.call_downcast::<Number>()?.clone())?)
+}
+
+/// Quest methods
+impl Mutex {
+	/// Creates a new mutex.
+	///
+	/// # Arguments
+	/// 1. (optional) The value the mutex protects; defaults to `null`.
+	#[instrument(name="Mutex::()", level="trace", skip(args), fields(?args))]
+	pub fn qs_call(_: &Object, args: Args) -> Result<Object> {
+		Ok(Self::new(args.arg(0).cloned().unwrap_or_default()).into())
+	}
+
+	/// Calls the block with the protected value while holding the lock, returning the block's result.
+	///
+	/// The lock is released afterwards, even if the block raises an error or `return`s.
+	///
+	/// # Arguments
+	/// 1. (required) The block to call.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// $mutex = Mutex([]);
+	/// $threads = [1, 2, 3].map({
+	/// 	$n = _0;
+	/// 	spawn({ mutex.lock({ _0.push(n) }) })
+	/// });
+	/// threads.each({ _0.join() });
+	///
+	/// assert(mutex.lock({ _0.len() }) == 3);
+	/// ```
+	#[instrument(name="Mutex::lock", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_lock(this: &Object, args: Args) -> Result<Object> {
+		let block = args.try_arg(0)?;
+		let this = this.try_downcast::<Self>()?.clone();
+
+		this.with_lock(|value| block.call_attr_lit(&Literal::CALL, &[value]))
+	}
+
+	/// Calls the block with the protected value while holding the lock, replacing the value with
+	/// the block's result, which is also returned.
+	///
+	/// # Arguments
+	/// 1. (required) The block to call.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// $mutex = Mutex(1);
+	/// mutex.update({ _0 + 2 });
+	/// assert(mutex.lock({ _0 }) == 3);
+	/// ```
+	#[instrument(name="Mutex::update", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_update(this: &Object, args: Args) -> Result<Object> {
+		let block = args.try_arg(0)?;
+		let this = this.try_downcast::<Self>()?.clone();
+
+		this.with_lock(|value| {
+			let value = block.call_attr_lit(&Literal::CALL, &[value])?;
+			*this.0.value.lock() = value.clone();
+			Ok(value)
+		})
+	}
+
+	/// Checks to see if any thread currently holds the lock.
+	#[instrument(name="Mutex::locked?", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_locked_q(this: &Object, _: Args) -> Result<Object> {
+		Ok(this.try_downcast::<Self>()?.is_locked().into())
+	}
+
+	/// Checks to see if the current thread holds the lock.
+	#[instrument(name="Mutex::held?", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_held_q(this: &Object, _: Args) -> Result<Object> {
+		Ok(this.try_downcast::<Self>()?.is_held().into())
+	}
+
+	/// Creates a new [`Condvar`] that's used with `this`.
+	#[instrument(name="Mutex::condvar", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_condvar(this: &Object, _: Args) -> Result<Object> {
+		Ok(Condvar::new(this.try_downcast::<Self>()?.clone()).into())
+	}
+}
+
+impl_object_type!{
+for Mutex [(parents super::Basic)]:
+	"()" => method Self::qs_call,
+	"lock" => method Self::qs_lock,
+	"update" => method Self::qs_update,
+	"locked?" => method Self::qs_locked_q,
+	"held?" => method Self::qs_held_q,
+	"condvar" => method Self::qs_condvar,
+}
+
+/// Quest methods
+impl Condvar {
+	/// Creates a new condvar.
+	///
+	/// # Arguments
+	/// 1. (required, [`Mutex`]) The mutex to use the condvar with.
+	#[instrument(name="Condvar::()", level="trace", skip(args), fields(?args))]
+	pub fn qs_call(_: &Object, args: Args) -> Result<Object> {
+		let mutex = args.try_arg(0)?.try_downcast::<Mutex>()?.clone();
+
+		Ok(Self::new(mutex).into())
+	}
+
+	/// Waits until notified, returning whether it was notified.
+	///
+	/// This must be called while holding the condvar's mutex, which is released while waiting and
+	/// reacquired before returning. As wakeups can be spurious, this should be called in a loop.
+	///
+	/// # Arguments
+	/// 1. (optional, [`Duration`](super::Duration) or `@num`) The longest amount of time to wait;
+	///    numbers are in seconds. If it passes, `false` is returned.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// $mutex = Mutex([]);
+	/// $ready = Condvar(mutex);
+	///
+	/// $thread = spawn({
+	/// 	mutex.lock({
+	/// 		while({ _0.len() == 0 }, { ready.wait() });
+	/// 		_0.pop()
+	/// 	})
+	/// });
+	///
+	/// mutex.lock({ _0.push("hi") });
+	/// ready.notify_one();
+	/// assert(thread.join() == "hi");
+	/// ```
+	#[instrument(name="Condvar::wait", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_wait(this: &Object, args: Args) -> Result<Object> {
+		let timeout = args.arg(0).map(super::duration::to_std).transpose()?;
+		let this = this.try_downcast::<Self>()?.clone();
+
+		Ok(this.wait(timeout)?.into())
+	}
+
+	/// Wakes up one thread that's waiting, if there are any.
+	#[instrument(name="Condvar::notify_one", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_notify_one(this: &Object, _: Args) -> Result<Object> {
+		this.try_downcast::<Self>()?.0.condvar.notify_one();
+
+		Ok(this.clone())
+	}
+
+	/// Wakes up every thread that's waiting.
+	#[instrument(name="Condvar::notify_all", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_notify_all(this: &Object, _: Args) -> Result<Object> {
+		this.try_downcast::<Self>()?.0.condvar.notify_all();
+
+		Ok(this.clone())
+	}
+
+	/// Gets the [`Mutex`] the condvar is used with.
+	#[instrument(name="Condvar::mutex", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_mutex(this: &Object, _: Args) -> Result<Object> {
+		Ok(this.try_downcast::<Self>()?.0.mutex.clone().into())
+	}
+}
+
+impl_object_type!{
+for Condvar [(parents super::Basic)]:
+	"()" => method Self::qs_call,
+	"wait" => method Self::qs_wait,
+	"notify_one" => method Self::qs_notify_one,
+	"notify_all" => method Self::qs_notify_all,
+	"mutex" => method Self::qs_mutex,
+}
+
+/// Quest methods
+impl Channel {
+	/// Creates a new channel.
+	///
+	/// # Arguments
+	/// 1. (optional, `@num`) The most values the channel can hold before `send` waits. If omitted
+	///    or `null`, there's no limit.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// $channel = Channel(2);
+	/// $producer = spawn({
+	/// 	[1, 2, 3, 4].each({ channel.send(_0) });
+	/// 	channel.close();
+	/// });
+	///
+	/// $expected = [1, 2, 3, 4];
+	/// assert(channel.@list() == expected);
+	/// ```
+	#[instrument(name="Channel::()", level="trace", skip(args), fields(?args))]
+	pub fn qs_call(_: &Object, args: Args) -> Result<Object> {
+		let capacity =
+			match args.arg(0).filter(|arg| !arg.is_a::<super::Null>()) {
+				Some(capacity) => Some(usize::try_from(capacity.call_downcast::<Number>()?.clone())?),
+				None => None
+			};
+
+		if capacity == Some(0) {
+			return Err(ValueError::Messaged("channel capacity must be positive".into()).into());
+		}
+
+		Ok(Self::new(capacity).into())
+	}
+
+	/// Sends the first argument, waiting for room if the channel is full.
+	///
+	/// Sending to a closed channel is an error.
+	///
+	/// # Arguments
+	/// 1. (required) The value to send.
+	#[instrument(name="Channel::send", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_send(this: &Object, args: Args) -> Result<Object> {
+		let value = args.try_arg(0)?.clone();
+		let channel = this.try_downcast::<Self>()?.clone();
+
+		match channel.send(value) {
+			Ok(()) => Ok(this.clone()),
+			Err(_) => Err(ValueError::Messaged("sent to a closed channel".into()).into())
+		}
+	}
+
+	/// Receives a value, waiting until one's sent.
+	///
+	/// If the channel is closed and there's nothing left to receive, `null` is returned. (If `null`
+	/// itself is sent, use `closed?` or iterate over the channel instead.)
+	#[instrument(name="Channel::recv", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_recv(this: &Object, _: Args) -> Result<Object> {
+		let channel = this.try_downcast::<Self>()?.clone();
+
+		Ok(channel.recv(None).unwrap_or_default())
+	}
+
+	/// Receives a value if there's one available, returning `null` otherwise.
+	#[instrument(name="Channel::try_recv", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_try_recv(this: &Object, _: Args) -> Result<Object> {
+		Ok(this.try_downcast::<Self>()?.try_recv().unwrap_or_default())
+	}
+
+	/// Receives a value, waiting at most the given amount of time; `null` is returned if nothing
+	/// was received.
+	///
+	/// # Arguments
+	/// 1. (required, [`Duration`](super::Duration) or `@num`) How long to wait; numbers are in
+	///    seconds.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// $channel = Channel();
+	/// assert(channel.recv_timeout(0.01) == null);
+	/// ```
+	#[instrument(name="Channel::recv_timeout", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_recv_timeout(this: &Object, args: Args) -> Result<Object> {
+		let timeout = super::duration::to_std(args.try_arg(0)?)?;
+		let channel = this.try_downcast::<Self>()?.clone();
+
+		Ok(channel.recv(Some(timeout)).unwrap_or_default())
+	}
+
+	/// Closes the channel, so nothing more can be sent. Anything waiting on the channel is woken up.
+	#[instrument(name="Channel::close", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_close(this: &Object, _: Args) -> Result<Object> {
+		this.try_downcast::<Self>()?.close();
+
+		Ok(this.clone())
+	}
+
+	/// Checks to see if the channel is closed.
+	#[instrument(name="Channel::closed?", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_closed_q(this: &Object, _: Args) -> Result<Object> {
+		Ok(this.try_downcast::<Self>()?.is_closed().into())
+	}
+
+	/// Gets the amount of values waiting to be received.
+	#[instrument(name="Channel::len", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_len(this: &Object, _: Args) -> Result<Object> {
+		Ok(this.try_downcast::<Self>()?.len().into())
+	}
+
+	/// Gets the most values the channel can hold, or `null` if there's no limit.
+	#[instrument(name="Channel::capacity", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_capacity(this: &Object, _: Args) -> Result<Object> {
+		Ok(this.try_downcast::<Self>()?.0.capacity.map(Object::from).unwrap_or_default())
+	}
+
+	/// Gets an [`Iter`] that receives values until the channel is closed and empty.
+	#[instrument(name="Channel::@iter", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_at_iter(this: &Object, _: Args) -> Result<Object> {
+		let channel = this.try_downcast::<Self>()?.clone();
+
+		Ok(Iter::from_fn(move || channel.recv(None).map(Ok)).into())
+	}
+}
+
+impl_object_type!{
+for Channel [(parents super::Iterable)]:
+	"()" => method Self::qs_call,
+	"send" => method Self::qs_send,
+	"recv" => method Self::qs_recv,
+	"try_recv" => method Self::qs_try_recv,
+	"recv_timeout" => method Self::qs_recv_timeout,
+	"close" => method Self::qs_close,
+	"closed?" => method Self::qs_closed_q,
+	"len" => method Self::qs_len,
+	"capacity" => method Self::qs_capacity,
+	"@iter" => method Self::qs_at_iter,
+}
+
+/// Quest methods
+impl Atomic {
+	/// Creates a new atomic integer.
+	///
+	/// # Arguments
+	/// 1. (optional, `@num`) The starting value; defaults to zero.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// $counter = Atomic();
+	/// $threads = [1, 2, 3, 4].map({ spawn({ 1.upto(100).each({ counter.add() }) }) });
+	/// threads.each({ _0.join() });
+	///
+	/// assert(counter.get() == 400);
+	/// ```
+	#[instrument(name="Atomic::()", level="trace", skip(args), fields(?args))]
+	pub fn qs_call(_: &Object, args: Args) -> Result<Object> {
+		Ok(Self::new(args.arg(0).map(to_i64).transpose()?.unwrap_or(0)).into())
+	}
+
+	/// Gets the current value.
+	#[instrument(name="Atomic::get", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_get(this: &Object, _: Args) -> Result<Object> {
+		Ok(this.try_downcast::<Self>()?.get().into())
+	}
+
+	/// Sets the value, returning the previous one.
+	///
+	/// # Arguments
+	/// 1. (required, `@num`) The new value.
+	#[instrument(name="Atomic::set", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_set(this: &Object, args: Args) -> Result<Object> {
+		let value = to_i64(args.try_arg(0)?)?;
+
+		Ok(this.try_downcast::<Self>()?.0.swap(value, Ordering::SeqCst).into())
+	}
+
+	/// Adds to the value, returning the new one. Overflow wraps around.
+	///
+	/// # Arguments
+	/// 1. (optional, `@num`) The amount to add; defaults to one.
+	#[instrument(name="Atomic::add", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_add(this: &Object, args: Args) -> Result<Object> {
+		let amount = args.arg(0).map(to_i64).transpose()?.unwrap_or(1);
+
+		Ok(this.try_downcast::<Self>()?.0.fetch_add(amount, Ordering::SeqCst).wrapping_add(amount).into())
+	}
+
+	/// Subtracts from the value, returning the new one. Overflow wraps around.
+	///
+	/// # Arguments
+	/// 1. (optional, `@num`) The amount to subtract; defaults to one.
+	#[instrument(name="Atomic::sub", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_sub(this: &Object, args: Args) -> Result<Object> {
+		let amount = args.arg(0).map(to_i64).transpose()?.unwrap_or(1);
+
+		Ok(this.try_downcast::<Self>()?.0.fetch_sub(amount, Ordering::SeqCst).wrapping_sub(amount).into())
+	}
+
+	/// Sets the value to the second argument if it's currently the first argument, returning
+	/// whether it was set.
+	///
+	/// # Arguments
+	/// 1. (required, `@num`) The value it must currently be.
+	/// 2. (required, `@num`) The new value.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// $flag = Atomic(0);
+	/// assert(flag.compare_and_set(0, 1));
+	/// assert(!flag.compare_and_set(0, 2));
+	/// assert(flag.get() == 1);
+	/// ```
+	#[instrument(name="Atomic::compare_and_set", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_compare_and_set(this: &Object, args: Args) -> Result<Object> {
+		let current = to_i64(args.try_arg(0)?)?;
+		let new = to_i64(args.try_arg(1)?)?;
+
+		Ok(this.try_downcast::<Self>()?.0
+			.compare_exchange(current, new, Ordering::SeqCst, Ordering::SeqCst)
+			.is_ok()
+			.into())
+	}
+
+	/// Converts the current value to a [`Text`].
+	#[instrument(name="Atomic::@text", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_at_text(this: &Object, _: Args) -> Result<Object> {
+		Ok(Text::from(this.try_downcast::<Self>()?.get().to_string()).into())
+	}
+
+	/// Inspects `this`, such as `Atomic(3)`.
+	#[instrument(name="Atomic::inspect", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_inspect(this: &Object, _: Args) -> Result<Object> {
+		Ok(Text::from(format!("Atomic({})", this.try_downcast::<Self>()?.get())).into())
+	}
+}
+
+impl_object_type!{
+for Atomic [(parents super::Basic)]:
+	"()" => method Self::qs_call,
+	"get" => method Self::qs_get,
+	"@num" => method Self::qs_get,
+	"@text" => method Self::qs_at_text,
+	"inspect" => method Self::qs_inspect,
+	"set" => method Self::qs_set,
+	"add" => method Self::qs_add,
+	"sub" => method Self::qs_sub,
+	"compare_and_set" => method Self::qs_compare_and_set,
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn channel_send_and_close() {
+		let channel = Channel::new(Some(1));
+		let sender = channel.clone();
+
+		let handle = thread::spawn(move || {
+			for i in 0..3 {
+				sender.send(Object::from(i)).unwrap();
+			}
+
+			sender.close();
+			assert!(sender.send(Object::from(3)).is_err());
+		});
+
+		let mut received = Vec::new();
+		while let Some(value) = channel.recv(None) {
+			received.push(value.downcast::<Number>().unwrap().clone());
+		}
+
+		handle.join().unwrap();
+		assert_eq!(received, vec![Number::from(0), Number::from(1), Number::from(2)]);
+		assert!(channel.recv(Some(Duration::from_millis(1))).is_none());
+	}
+
+	#[test]
+	fn recv_timeout() {
+		let channel = Channel::new(None);
+		let start = Instant::now();
+
+		assert!(channel.recv(Some(Duration::from_millis(20))).is_none());
+		assert!(start.elapsed() >= Duration::from_millis(20));
+		assert!(channel.try_recv().is_none());
+	}
+
+	#[test]
+	fn mutex_and_condvar() {
+		let mutex = Mutex::new(Object::default());
+		let condvar = Condvar::new(mutex.clone());
+		let ready = Arc::new(std::sync::atomic::AtomicBool::new(false));
+
+		let handle = {
+			let (mutex, condvar, ready) = (mutex.clone(), condvar.clone(), ready.clone());
+
+			thread::spawn(move || {
+				mutex.with_lock(|_| {
+					while !ready.load(Ordering::SeqCst) {
+						condvar.wait(None)?;
+					}
+
+					assert!(mutex.is_held());
+					Ok(Object::default())
+				}).unwrap();
+			})
+		};
+
+		mutex.with_lock(|_| {
+			// locking a mutex twice on the same thread is an error, not a deadlock.
+			assert!(mutex.with_lock(|_| Ok(Object::default())).is_err());
+			ready.store(true, Ordering::SeqCst);
+			condvar.0.condvar.notify_all();
+			Ok(Object::default())
+		}).unwrap();
+
+		handle.join().unwrap();
+		assert!(!mutex.is_locked());
+		assert!(condvar.wait(None).is_err());
+	}
+}
@@ -1,80 +1,201 @@
 use crate::{Object, Args, Result};
-use crate::types::Text;
+use crate::types::{Text, Bytes};
 
 use std::sync::{Arc, Mutex};
-use std::net::{ToSocketAddrs, TcpStream};
+use std::net::{SocketAddr, ToSocketAddrs, TcpStream};
 use std::io::{self, Write};
 use tracing::instrument;
 
+/// A TCP connection, along with whether it reads [`Bytes`] instead of [`Text`].
+///
+/// The last field is a clone of the stream that's used for things which shouldn't wait on a read in
+/// another thread to finish, such as shutting the connection down.
 #[derive(Debug, Clone)]
-pub struct Tcp(Arc<Mutex<TcpStream>>);
+pub struct Tcp(Arc<Mutex<TcpStream>>, bool, Arc<TcpStream>);
 
 impl Tcp {
 	#[inline]
 	pub fn connect<A: ToSocketAddrs>(addr: A) -> io::Result<Self> {
-		Ok(Tcp(Arc::new(Mutex::new(TcpStream::connect(addr)?))))
+		Tcp::from_stream(TcpStream::connect(addr)?, false)
+	}
+
+	/// Wraps an already-connected stream.
+	pub fn from_stream(stream: TcpStream, binary: bool) -> io::Result<Self> {
+		let handle = stream.try_clone()?;
+
+		Ok(Tcp(Arc::new(Mutex::new(stream)), binary, Arc::new(handle)))
+	}
+
+	/// Get the local address of the connection.
+	#[inline]
+	pub fn local_addr(&self) -> io::Result<SocketAddr> {
+		self.2.local_addr()
+	}
+
+	/// Get the address of the remote end of the connection.
+	#[inline]
+	pub fn peer_addr(&self) -> io::Result<SocketAddr> {
+		self.2.peer_addr()
+	}
+
+	/// Checks to see if the connection is in binary mode.
+	#[inline]
+	pub fn is_binary(&self) -> bool {
+		self.1
+	}
+}
+
+/// Parses the optional mode argument of [`Tcp`] and [`TcpServer`](super::TcpServer), returning
+/// whether connections should be in binary mode.
+pub(crate) fn parse_mode(mode: Option<&Object>) -> Result<bool> {
+	match mode.map(|mode| mode.call_downcast::<Text>().map(|mode| mode.to_string())).transpose()? {
+		None => Ok(false),
+		Some(mode) if mode.is_empty() => Ok(false),
+		Some(mode) if mode == "b" => Ok(true),
+		Some(mode) => Err(crate::error::ValueError::Messaged(format!("invalid tcp mode given: {:?}", mode)).into())
 	}
 }
 
 impl Tcp {
+	/// Connect to the address given by the first argument.
+	///
+	/// # Arguments
+	/// 1. (required, `Text`) The address to connect to.
+	/// 2. (optional, `@text`) The mode; `"b"` reads [`Bytes`] instead of [`Text`].
 	#[instrument(name="Tcp::()", level="trace")]
 	pub fn qs_call(_: &Object, args: Args) -> Result<Object> {
+		let binary = parse_mode(args.arg(1))?;
+
 		args.try_arg(0)?.try_downcast::<Text>().and_then(|addr| {
 			Tcp::connect(addr.as_ref())
-				.map(Object::from)
-				.map_err(|err| crate::Error::Messaged(err.to_string()))
+				.map(|tcp| Object::from(Tcp(tcp.0, binary, tcp.2)))
+				.map_err(From::from)
 		})
 	}
+
+	/// Listen for connections on the address given by the first argument.
+	///
+	/// This is identical to calling [`TcpServer`](super::TcpServer).
+	#[instrument(name="Tcp::listen", level="trace")]
+	pub fn qs_listen(this: &Object, args: Args) -> Result<Object> {
+		super::TcpServer::qs_call(this, args)
+	}
+
+	/// Get the local address of the connection, such as `"127.0.0.1:54321"`.
+	#[instrument(name="Tcp::local_addr", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_local_addr(this: &Object, _: Args) -> Result<Object> {
+		this.try_downcast::<Self>()?.local_addr()
+			.map(|addr| Text::from(addr.to_string()).into())
+			.map_err(From::from)
+	}
+
+	/// Get the address of the remote end of the connection.
+	#[instrument(name="Tcp::peer_addr", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_peer_addr(this: &Object, _: Args) -> Result<Object> {
+		this.try_downcast::<Self>()?.peer_addr()
+			.map(|addr| Text::from(addr.to_string()).into())
+			.map_err(From::from)
+	}
+
+	/// Shut down both halves of the connection.
+	///
+	/// Any reads that are blocked in other threads will return immediately.
+	#[instrument(name="Tcp::shutdown", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_shutdown(this: &Object, _: Args) -> Result<Object> {
+		match this.try_downcast::<Self>()?.2.shutdown(std::net::Shutdown::Both) {
+			Ok(()) => Ok(this.clone()),
+			// shutting down a connection the other end already closed is fine.
+			Err(err) if err.kind() == io::ErrorKind::NotConnected => Ok(this.clone()),
+			Err(err) => Err(err.into())
+		}
+	}
+
+	/// Reads [`Bytes`] from a binary connection: either the amount given, or until the connection
+	/// is closed.
+	fn read_binary(&self, amnt: Option<&Object>) -> Result<Object> {
+		let mut tcp = self.0.lock().unwrap();
+
+		let bytes: Bytes = crate::types::bytes::read_from(&mut *tcp, amnt)?;
+
+		Ok(bytes.into())
+	}
 }
 
 impl_object_type!{
 for Tcp [(parents super::Basic)]:
 	"()" => method Self::qs_call,
-	"get" => method |this, _| {
-		Ok(ureq::get(this.call_downcast::<Text>()?.as_ref())
-			.call()
-			.into_string()
-			.unwrap()
-			.into())
-	},
+	"listen" => method Self::qs_listen,
+	"local_addr" => method Self::qs_local_addr,
+	"peer_addr" => method Self::qs_peer_addr,
+	"shutdown" => method Self::qs_shutdown,
 	"write" => method |this, args| {
-		let arg = args.try_arg(0)?.call_downcast::<Text>()?.clone();
+		let arg = crate::types::bytes::to_write(args.try_arg(0)?)?;
 
-		this.try_downcast_mut::<Self>().and_then(|tcp| {
-			tcp.0.lock().unwrap().write(&arg.as_ref().as_ref())
-				.map(Object::from)
-				.map_err(|err| crate::Error::Messaged(err.to_string()))
-		})
+		// we write through the handle so that writing isn't blocked by a read in another thread.
+		(&*this.try_downcast::<Self>()?.2).write_all(&arg)?;
+
+		Ok(arg.len().into())
+	},
+	"binary?" => method |this, _| {
+		Ok(this.try_downcast::<Self>()?.is_binary().into())
 	},
 	"read" => method |this, args| -> Result<Object> {
-		this.try_downcast_mut::<Self>().and_then(|tcp| {
-			use std::io::{Read, BufReader, BufRead};
-			let mut tcp = tcp.0.lock().unwrap();
-
-			let mut res;
-			if let Some(arg) = args.arg(0) {
-				let arg = arg.call_downcast::<crate::types::Number>()?;
-				res = vec![0; arg.truncate() as usize];
-				dbg!(res.len());
-				tcp.read_exact(&mut res)
-					.map_err(|err| crate::Error::Messaged(err.to_string()))?;
-			} else {
-				res = Vec::<u8>::with_capacity(5);
-				let mut bufr = BufReader::new(&*tcp);
-				while bufr.read_until(b'\n', res.as_mut())
-					.map_err(|err| crate::Error::Messaged(err.to_string()))? != 0
-				{
-					if res.ends_with(b"\r\n\r\n") {
-						break;
-					}
+		use std::io::{Read, BufReader, BufRead};
+
+		// clone the connection so the object isn't locked while we're blocked reading, which would
+		// stop other threads from shutting it down.
+		let tcp = this.try_downcast::<Self>()?.clone();
+
+		if tcp.is_binary() {
+			return tcp.read_binary(args.arg(0));
+		}
+
+		let mut tcp = tcp.0.lock().unwrap();
+
+		let mut res;
+		if let Some(arg) = args.arg(0) {
+			let arg = arg.call_downcast::<crate::types::Number>()?;
+			res = vec![0; arg.truncate() as usize];
+			tcp.read_exact(&mut res)?;
+		} else {
+			res = Vec::<u8>::with_capacity(5);
+			let mut bufr = BufReader::new(&*tcp);
+			while bufr.read_until(b'\n', res.as_mut())? != 0 {
+				if res.ends_with(b"\r\n\r\n") {
+					break;
 				}
 			}
+		}
+
+		Ok(String::from_utf8_lossy(&res).into_owned().into())
+	},
+}
 
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::net::TcpListener;
+	use std::sync::mpsc;
+	use std::time::Duration;
 
+	#[test]
+	fn shutdown_while_reading() {
+		crate::init();
 
+		let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+		let conn = Object::from(Tcp::connect(listener.local_addr().unwrap()).unwrap());
+		let (_server, _) = listener.accept().unwrap();
 
+		let (tx, rx) = mpsc::channel();
+		let reader = conn.clone();
+		std::thread::spawn(move || {
+			let _ = reader.call_attr_lit("read", &[&5.into()]);
+			tx.send(()).unwrap();
+		});
 
-			Ok(String::from_utf8_lossy(&res).into_owned().into())
-		})
-	},
+		std::thread::sleep(Duration::from_millis(50));
+		conn.call_attr_lit("shutdown", &[]).unwrap();
+
+		rx.recv_timeout(Duration::from_secs(5)).expect("read wasn't interrupted by shutdown");
+	}
 }
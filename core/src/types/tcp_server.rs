@@ -0,0 +1,235 @@
+use crate::{Object, Args, Literal, Result};
+use crate::types::{Text, Tcp, Null};
+
+use parking_lot::Mutex;
+use std::io;
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::instrument;
+
+/// How long [`TcpServer::accept`] waits between checking for new connections.
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// A socket that listens for TCP connections.
+///
+/// Servers can be shared between threads; shutting one down wakes up anyone waiting to accept.
+#[derive(Debug, Clone)]
+pub struct TcpServer(Arc<Inner>);
+
+#[derive(Debug)]
+struct Inner {
+	// this is `None` once the server has been shut down.
+	listener: Mutex<Option<TcpListener>>,
+	local_addr: SocketAddr,
+	binary: bool
+}
+
+impl TcpServer {
+	/// Start listening on `addr`. If `binary` is set, accepted connections read [`Bytes`](
+	/// super::Bytes) instead of [`Text`].
+	pub fn bind<A: ToSocketAddrs>(addr: A, binary: bool) -> io::Result<Self> {
+		let listener = TcpListener::bind(addr)?;
+
+		// we poll for connections so that `shutdown` and timeouts work without any platform-specific
+		// code.
+		listener.set_nonblocking(true)?;
+
+		Ok(Self(Arc::new(Inner {
+			local_addr: listener.local_addr()?,
+			listener: Mutex::new(Some(listener)),
+			binary
+		})))
+	}
+
+	/// Get the address the server is listening on.
+	#[inline]
+	pub fn local_addr(&self) -> SocketAddr {
+		self.0.local_addr
+	}
+
+	/// Checks to see if the server has been shut down.
+	#[inline]
+	pub fn is_closed(&self) -> bool {
+		self.0.listener.lock().is_none()
+	}
+
+	/// Stop listening for connections, which closes the socket.
+	pub fn shutdown(&self) {
+		self.0.listener.lock().take();
+	}
+
+	/// Wait for a connection, giving up after `timeout` if it's given.
+	///
+	/// `None` is returned if the timeout expires or the server is shut down.
+	pub fn accept(&self, timeout: Option<Duration>) -> io::Result<Option<Tcp>> {
+		match self.accept_stream(timeout)? {
+			Some(stream) => Tcp::from_stream(stream, self.0.binary).map(Some),
+			None => Ok(None)
+		}
+	}
+
+	/// Like [`accept`](#method.accept), except the raw stream is returned.
+	pub(crate) fn accept_stream(&self, timeout: Option<Duration>) -> io::Result<Option<TcpStream>> {
+		let deadline = timeout.map(|timeout| Instant::now() + timeout);
+
+		loop {
+			match self.0.listener.lock().as_ref().map(TcpListener::accept) {
+				None => return Ok(None),
+				Some(Ok((stream, _))) => {
+					stream.set_nonblocking(false)?;
+					return Ok(Some(stream));
+				},
+				Some(Err(err)) if err.kind() == io::ErrorKind::WouldBlock => {},
+				Some(Err(err)) => return Err(err)
+			}
+
+			if matches!(deadline, Some(deadline) if deadline <= Instant::now()) {
+				return Ok(None);
+			}
+
+			std::thread::sleep(POLL_INTERVAL);
+		}
+	}
+}
+
+/// Quest methods
+impl TcpServer {
+	/// Listen for connections on the address given by the first argument.
+	///
+	/// # Arguments
+	/// 1. (required, `@text`) The address to listen on, such as `"127.0.0.1:8080"`. Use port `0` to
+	///    have one picked for you.
+	/// 2. (optional, `@text`) The mode; `"b"` means accepted connections read [`Bytes`](super::Bytes)
+	///    instead of [`Text`].
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// $server = TcpServer("127.0.0.1:0");
+	/// $client = Tcp(server.local_addr());
+	/// $conn = server.accept();
+	/// assert(conn.peer_addr() == client.local_addr());
+	/// server.shutdown();
+	/// ```
+	#[instrument(name="TcpServer::()", level="trace")]
+	pub fn qs_call(_: &Object, args: Args) -> Result<Object> {
+		let binary = super::tcp::parse_mode(args.arg(1))?;
+		let addr = args.try_arg(0)?.call_downcast::<Text>()?;
+
+		Ok(Self::bind(addr.as_ref(), binary)?.into())
+	}
+
+	/// Wait for a connection, returning it as a [`Tcp`].
+	///
+	/// [`Null`] is returned if the timeout expires, or if the server is shut down (either before or
+	/// during the call).
+	///
+	/// # Arguments
+	/// 1. (optional, [`Duration`](super::Duration) or `@num`) How long to wait; numbers are in
+	///    seconds. If omitted, waits until a connection is made.
+	#[instrument(name="TcpServer::accept", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_accept(this: &Object, args: Args) -> Result<Object> {
+		let timeout = args.arg(0).map(super::duration::to_std).transpose()?;
+		let this = this.try_downcast::<Self>()?.clone();
+
+		match this.accept(timeout)? {
+			Some(tcp) => Ok(tcp.into()),
+			None => Ok(Null.into())
+		}
+	}
+
+	/// Accept connections until the server is shut down, calling the first argument with each one.
+	///
+	/// To handle each connection on its own thread, pass it along to `spawn`.
+	///
+	/// # Arguments
+	/// 1. (required, callable) The block to call with each [`Tcp`] connection.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// server.each({
+	///     spawn({ _1.write("hello, " + _1.read()); _1.shutdown(); }, _0);
+	/// });
+	/// ```
+	#[instrument(name="TcpServer::each", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_each(this: &Object, args: Args) -> Result<Object> {
+		let block = args.try_arg(0)?;
+		let server = this.try_downcast::<Self>()?.clone();
+
+		while let Some(tcp) = server.accept(None)? {
+			block.call_attr_lit(&Literal::CALL, &[&tcp.into()])?;
+		}
+
+		Ok(this.clone())
+	}
+
+	/// Get the address `this` is listening on, such as `"127.0.0.1:8080"`.
+	#[instrument(name="TcpServer::local_addr", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_local_addr(this: &Object, _: Args) -> Result<Object> {
+		Ok(Text::from(this.try_downcast::<Self>()?.local_addr().to_string()).into())
+	}
+
+	/// Get the port `this` is listening on.
+	#[instrument(name="TcpServer::port", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_port(this: &Object, _: Args) -> Result<Object> {
+		Ok(this.try_downcast::<Self>()?.local_addr().port().into())
+	}
+
+	/// Stop listening for connections.
+	///
+	/// Any calls to [`accept`](#method.qs_accept) waiting in other threads will return [`Null`].
+	#[instrument(name="TcpServer::shutdown", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_shutdown(this: &Object, _: Args) -> Result<Object> {
+		this.try_downcast::<Self>()?.shutdown();
+
+		Ok(this.clone())
+	}
+
+	/// Checks to see if `this` has been shut down.
+	#[instrument(name="TcpServer::closed?", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_closed_q(this: &Object, _: Args) -> Result<Object> {
+		Ok(this.try_downcast::<Self>()?.is_closed().into())
+	}
+
+	/// Checks to see if accepted connections are in binary mode.
+	#[instrument(name="TcpServer::binary?", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_binary_q(this: &Object, _: Args) -> Result<Object> {
+		Ok(this.try_downcast::<Self>()?.0.binary.into())
+	}
+}
+
+impl_object_type!{
+for TcpServer [(parents super::Basic)]:
+	"()" => method Self::qs_call,
+	"accept" => method Self::qs_accept,
+	"each" => method Self::qs_each,
+	"local_addr" => method Self::qs_local_addr,
+	"port" => method Self::qs_port,
+	"shutdown" => method Self::qs_shutdown,
+	"closed?" => method Self::qs_closed_q,
+	"binary?" => method Self::qs_binary_q,
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn accept_and_shutdown() {
+		let server = TcpServer::bind("127.0.0.1:0", true).unwrap();
+		assert!(server.accept(Some(Duration::from_millis(20))).unwrap().is_none());
+
+		let client = TcpStream::connect(server.local_addr()).unwrap();
+		let conn = server.accept(Some(Duration::from_secs(5))).unwrap().expect("no connection");
+		assert!(conn.is_binary());
+		assert_eq!(conn.peer_addr().unwrap(), client.local_addr().unwrap());
+		assert_eq!(conn.local_addr().unwrap(), server.local_addr());
+
+		let waiting = server.clone();
+		let waiter = std::thread::spawn(move || waiting.accept(None).unwrap().is_none());
+		server.shutdown();
+
+		assert!(waiter.join().unwrap());
+		assert!(server.is_closed());
+	}
+}
@@ -247,6 +247,14 @@ impl Text {
 		Ok(this.clone())
 	}
 
+	/// Encodes `this` as UTF-8 [`Bytes`](crate::types::Bytes).
+	#[instrument(name="Text::@bytes", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_at_bytes(this: &Object, _: Args) -> crate::Result<Object> {
+		let this = this.try_downcast::<Self>()?;
+
+		Ok(crate::types::Bytes::from(&*this).into())
+	}
+
 	#[instrument(name="Text::@regex", level="trace", skip(this), fields(self=?this))]
 	pub fn qs_at_regex(this: &Object, _: Args) -> crate::Result<Object> {
 		let this = this.try_downcast::<Self>()?;
@@ -256,6 +264,11 @@ impl Text {
 			.map_err(|err| crate::Error::Messaged(err.to_string()))
 	}
 
+	#[instrument(name="Text::@path", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_at_path(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(crate::types::Path::from(this.try_downcast::<Self>()?.as_ref()).into())
+	}
+
 	#[instrument(name="Text::inspect", level="trace", skip(this), fields(self=?this))]
 	pub fn qs_inspect(this: &Object, _: Args) -> crate::Result<Object> {
 		let this = this.try_downcast::<Self>()?;
@@ -298,8 +311,8 @@ impl Text {
 		let this = this.try_downcast::<Self>()?;
 
 		if let Some(radix) = args.arg(0) {
-			let radix = *radix.call_downcast::<Number>()?;
-			let radix = u32::try_from(radix)
+			let radix = radix.call_downcast::<Number>()?.clone();
+			let radix = u32::try_from(radix.clone())
 				.map_err(|err| ValueError::Messaged(format!("bad radix '{}': {}", radix, err)))?;
 
 			Number::from_str_radix(this.as_ref(), radix)
@@ -346,6 +359,14 @@ impl Text {
 		Ok(rhs.map(|rhs| *this == *rhs).unwrap_or(false).into())
 	}
 
+	/// Hashes `this`, so that equal texts have the same hash.
+	#[instrument(name="Text::hash", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_hash(this: &Object, _: Args) -> crate::Result<Object> {
+		let this = this.try_downcast::<Self>()?;
+
+		Ok(crate::utils::hash(&*this).into())
+	}
+
 	#[instrument(name="Text::<=>", level="trace", skip(this, args), fields(self=?this, ?args))]
 	pub fn qs_cmp(this: &Object, args: Args) -> crate::Result<Object> {
 		let arg = args.try_arg(0)?.call_downcast::<Self>();
@@ -405,10 +426,10 @@ impl Text {
 	pub fn qs_get(this: &Object, args: Args) -> crate::Result<Object> {
 		let this = this.try_downcast::<Self>()?;
 
-		let start: isize = isize::try_from(*args.try_arg(0)?.try_downcast::<Number>()?)?;
+		let start: isize = isize::try_from(args.try_arg(0)?.try_downcast::<Number>()?.clone())?;
 
 		let end = args.arg(1)
-			.map(|n| n.call_downcast::<Number>().map(|n| *n))
+			.map(|n| n.call_downcast::<Number>().map(|n| n.clone()))
 			.transpose()?
 			.map(isize::try_from)
 			.transpose()?;
@@ -615,6 +636,8 @@ for Text
 [(init_parent super::Basic super::Comparable super::Iterable) (parents super::Basic) (convert "@text")]:
 	"@text" => method Self::qs_at_text,
 	"@regex" => method Self::qs_at_regex,
+	"@path" => method Self::qs_at_path,
+	"@bytes"  => method Self::qs_at_bytes,
 	"inspect"  => method Self::qs_inspect,
 	"@num"    => method Self::qs_at_num,
 	"@list"   => method Self::qs_at_list,
@@ -627,6 +650,7 @@ for Text
 	"->"      => method Self::qs_arrow,
 	"<=>"     => method Self::qs_cmp,
 	"=="      => method Self::qs_eql,
+	"hash"    => method Self::qs_hash,
 	"+"       => method Self::qs_add,
 	"+="      => method Self::qs_add_assign,
 
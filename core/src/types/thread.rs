@@ -0,0 +1,570 @@
+//! Running Quest code on other threads.
+
+use crate::{Object, Args, Binding, Error, Literal, Result};
+use crate::error::ValueError;
+use crate::types::{Text, Number, List, Iter, Exception, ObjectType};
+use parking_lot::{Condvar, Mutex};
+use std::convert::TryFrom;
+use std::io;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{mpsc, Arc};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+use tracing::instrument;
+
+/// What a thread or pool task ended with: either its value, or the exception it raised.
+type Outcome = std::result::Result<Object, Object>;
+
+/// The eventual result of running a block on another thread.
+///
+/// Once the block finishes, its result is kept around, so a future can be joined any number of
+/// times.
+#[derive(Debug, Clone, Default)]
+pub struct Future(Arc<FutureInner>);
+
+#[derive(Debug, Default)]
+struct FutureInner {
+	outcome: Mutex<Option<Outcome>>,
+	done: Condvar
+}
+
+/// Calls `block` with `args`, converting errors and panics into exceptions.
+///
+/// A `return` to the block's own frame is handled by the block itself, so any `return` that makes
+/// it out of the block is aimed at a frame on another thread, and is raised as an exception.
+fn run(block: &Object, args: &[Object]) -> Outcome {
+	let result = panic::catch_unwind(AssertUnwindSafe(|| {
+		block.call_attr_lit(&Literal::CALL, args.iter().collect::<Vec<_>>())
+	}));
+
+	match result {
+		Ok(Ok(value)) => Ok(value),
+		Ok(Err(Error::Return { .. })) =>
+			Err(Exception::new("Error", "can't return out of a thread's block".into(), Binding::instance().into()).into()),
+		Ok(Err(err)) => Err(Exception::from_error(err, Binding::instance())),
+		Err(payload) => {
+			let message =
+				if let Some(message) = payload.downcast_ref::<&str>() {
+					(*message).to_string()
+				} else if let Some(message) = payload.downcast_ref::<String>() {
+					message.clone()
+				} else {
+					"<unknown>".to_string()
+				};
+
+			Err(Exception::new("Panic", format!("thread panicked: {}", message), Binding::instance().into()).into())
+		}
+	}
+}
+
+impl Future {
+	/// Creates a new future that hasn't finished.
+	#[inline]
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	fn complete(&self, outcome: Outcome) {
+		*self.0.outcome.lock() = Some(outcome);
+		self.0.done.notify_all();
+	}
+
+	/// Checks to see if the future has finished.
+	pub fn is_done(&self) -> bool {
+		self.0.outcome.lock().is_some()
+	}
+
+	/// Waits for the future to finish, returning its value or the exception it raised.
+	///
+	/// If `timeout` is given and passes first, `None` is returned.
+	pub fn join(&self, timeout: Option<Duration>) -> Option<Result<Object>> {
+		let mut outcome = self.0.outcome.lock();
+
+		match timeout {
+			Some(timeout) => {
+				let deadline = std::time::Instant::now() + timeout;
+
+				while outcome.is_none() {
+					if self.0.done.wait_until(&mut outcome, deadline).timed_out() {
+						break;
+					}
+				}
+			},
+			None => while outcome.is_none() {
+				self.0.done.wait(&mut outcome);
+			}
+		}
+
+		outcome.clone().map(|outcome| outcome.map_err(Error::Thrown))
+	}
+}
+
+/// A handle to a Quest block that's running on its own OS thread.
+#[derive(Debug, Clone)]
+pub struct Thread(Arc<ThreadInner>);
+
+#[derive(Debug)]
+struct ThreadInner {
+	name: Option<String>,
+	future: Future
+}
+
+impl Thread {
+	/// Starts running `block` on a new thread, passing it `args`.
+	pub fn spawn(name: Option<String>, block: Object, args: Vec<Object>) -> io::Result<Self> {
+		let future = Future::new();
+		let mut builder = thread::Builder::new();
+
+		if let Some(ref name) = name {
+			builder = builder.name(name.clone());
+		}
+
+		builder.spawn({
+			let future = future.clone();
+			move || future.complete(run(&block, &args))
+		})?;
+
+		Ok(Self(Arc::new(ThreadInner { name, future })))
+	}
+
+	/// Gets the thread's name, if it has one.
+	#[inline]
+	pub fn name(&self) -> Option<&str> {
+		self.0.name.as_deref()
+	}
+
+	/// Gets the [`Future`] for the thread's result.
+	#[inline]
+	pub fn future(&self) -> &Future {
+		&self.0.future
+	}
+}
+
+type Job = Box<dyn FnOnce() + Send>;
+
+/// A fixed amount of threads that run blocks given to them.
+#[derive(Debug, Clone)]
+pub struct ThreadPool(Arc<ThreadPoolInner>);
+
+#[derive(Debug)]
+struct ThreadPoolInner {
+	sender: Mutex<Option<mpsc::Sender<Job>>>,
+	workers: Mutex<Vec<JoinHandle<()>>>,
+	size: usize
+}
+
+impl ThreadPool {
+	/// Creates a new pool with `size` threads.
+	pub fn new(size: usize) -> io::Result<Self> {
+		let (sender, receiver) = mpsc::channel::<Job>();
+		let receiver = Arc::new(Mutex::new(receiver));
+
+		let workers = (0..size)
+			.map(|i| {
+				let receiver = receiver.clone();
+
+				thread::Builder::new()
+					.name(format!("quest-pool-{}", i))
+					.spawn(move || loop {
+						// the lock is released before the job runs, so other workers can get jobs.
+						let job = receiver.lock().recv();
+
+						match job {
+							Ok(job) => job(),
+							Err(_) => break
+						}
+					})
+			})
+			.collect::<io::Result<Vec<_>>>()?;
+
+		Ok(Self(Arc::new(ThreadPoolInner {
+			sender: Mutex::new(Some(sender)),
+			workers: Mutex::new(workers),
+			size
+		})))
+	}
+
+	/// Gets the amount of threads in the pool.
+	#[inline]
+	pub fn size(&self) -> usize {
+		self.0.size
+	}
+
+	/// Queues `block` to be called with `args` on one of the pool's threads.
+	///
+	/// If the pool has been shut down, `None` is returned.
+	pub fn submit(&self, block: Object, args: Vec<Object>) -> Option<Future> {
+		let future = Future::new();
+		let job = {
+			let future = future.clone();
+			Box::new(move || future.complete(run(&block, &args)))
+		};
+
+		self.0.sender.lock().as_ref()?.send(job).ok()?;
+
+		Some(future)
+	}
+
+	/// Stops accepting new blocks, and waits for the ones already submitted to finish.
+	pub fn shutdown(&self) {
+		self.0.sender.lock().take();
+
+		let current = thread::current().id();
+
+		for worker in self.0.workers.lock().drain(..) {
+			// a worker shutting down its own pool can't wait for itself.
+			if worker.thread().id() != current {
+				let _ = worker.join();
+			}
+		}
+	}
+}
+
+fn spawn_args(args: &Args, start: usize) -> Vec<Object> {
+	args.iter().skip(start).cloned().collect()
+}
+
+fn join_result(future: &Future, timeout: Option<&Object>) -> Result<Object> {
+	let timeout = timeout.map(super::duration::to_std).transpose()?;
+
+	future.join(timeout).unwrap_or_else(|| Ok(Object::default()))
+}
+
+/// Quest methods
+impl Future {
+	/// Waits for the future to finish, returning its value.
+	///
+	/// If the block raised an exception (or panicked), the exception is raised again.
+	///
+	/// # Arguments
+	/// 1. (optional, [`Duration`](super::Duration) or `@num`) The longest amount of time to wait;
+	///    numbers are in seconds. If it passes, `null` is returned.
+	#[instrument(name="Future::join", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_join(this: &Object, args: Args) -> Result<Object> {
+		let future = this.try_downcast::<Self>()?.clone();
+
+		join_result(&future, args.arg(0))
+	}
+
+	/// Checks to see if the future has finished.
+	#[instrument(name="Future::done?", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_done_q(this: &Object, _: Args) -> Result<Object> {
+		Ok(this.try_downcast::<Self>()?.is_done().into())
+	}
+}
+
+impl_object_type!{
+for Future [(parents super::Basic)]:
+	"join" => method Self::qs_join,
+	"done?" => method Self::qs_done_q,
+}
+
+/// Quest methods
+impl Thread {
+	/// Runs a block on a new thread, passing it any extra arguments.
+	///
+	/// Unlike [`Kernel::spawn`](super::Kernel), the block isn't passed to itself as the first
+	/// argument.
+	///
+	/// When called unbound (eg `Thread::spawn { ... }`), the block is `this` instead, as with
+	/// [`Kernel::spawn`](super::Kernel).
+	///
+	/// # Arguments
+	/// 1. (required) The block to run.
+	/// 2+. (optional) The arguments to pass to the block.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// $thread = Thread.spawn({ _0 + _1 }, 1, 2);
+	/// assert(thread.join() == 3);
+	/// assert(thread.join() == 3); # results are kept around.
+	///
+	/// assert(Thread::spawn({ 4 }).join() == 4);
+	/// ```
+	#[instrument(name="Thread::spawn", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_spawn(this: &Object, args: Args) -> Result<Object> {
+		if this.is_identical(Self::mapping()) || this.is_a::<Self>() {
+			let block = args.try_arg(0)?.clone();
+
+			Ok(Self::spawn(None, block, spawn_args(&args, 1))?.into())
+		} else {
+			Ok(Self::spawn(None, this.clone(), spawn_args(&args, 0))?.into())
+		}
+	}
+
+	/// Runs a block on a new thread with the given name.
+	///
+	/// # Arguments
+	/// 1. (required, `@text`) The thread's name.
+	/// 2. (required) The block to run.
+	/// 3+. (optional) The arguments to pass to the block.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// $thread = Thread.named("worker", { 1 });
+	/// assert(thread.name() == "worker");
+	/// ```
+	#[instrument(name="Thread::named", level="trace", skip(args), fields(?args))]
+	pub fn qs_named(_: &Object, args: Args) -> Result<Object> {
+		let name = args.try_arg(0)?.call_downcast::<Text>()?.to_string();
+		let block = args.try_arg(1)?.clone();
+
+		Ok(Self::spawn(Some(name), block, spawn_args(&args, 2))?.into())
+	}
+
+	/// Waits for the thread to finish, returning the block's result.
+	///
+	/// The result is kept, so threads can be joined more than once. If the block raised an
+	/// exception, it's raised again; if it panicked, an exception with the kind `"Panic"` is raised.
+	///
+	/// # Arguments
+	/// 1. (optional, [`Duration`](super::Duration) or `@num`) The longest amount of time to wait;
+	///    numbers are in seconds. If it passes, `null` is returned.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// $thread = spawn({ sleep(1); "done" });
+	/// assert(thread.join(0.01) == null);
+	/// assert(thread.join() == "done");
+	///
+	/// $failed = spawn({ throw(Exception("oops")) });
+	/// assert(catch({ failed.join() }).message() == "oops");
+	/// ```
+	#[instrument(name="Thread::join", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_join(this: &Object, args: Args) -> Result<Object> {
+		let thread = this.try_downcast::<Self>()?.clone();
+
+		join_result(thread.future(), args.arg(0))
+	}
+
+	/// Checks to see if the thread has finished.
+	#[instrument(name="Thread::done?", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_done_q(this: &Object, _: Args) -> Result<Object> {
+		Ok(this.try_downcast::<Self>()?.future().is_done().into())
+	}
+
+	/// Gets the thread's name, or `null` if it wasn't given one.
+	#[instrument(name="Thread::name", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_name(this: &Object, _: Args) -> Result<Object> {
+		Ok(this.try_downcast::<Self>()?.name().map(Object::from).unwrap_or_default())
+	}
+
+	/// Inspects `this`, such as `Thread("worker", done)`.
+	#[instrument(name="Thread::inspect", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_inspect(this: &Object, _: Args) -> Result<Object> {
+		let this = this.try_downcast::<Self>()?;
+		let state = if this.future().is_done() { "done" } else { "running" };
+
+		Ok(match this.name() {
+			Some(name) => format!("Thread({:?}, {})", name, state),
+			None => format!("Thread({})", state)
+		}.into())
+	}
+}
+
+impl_object_type!{
+for Thread [(parents super::Basic)]:
+	"spawn" => method Self::qs_spawn,
+	"named" => method Self::qs_named,
+	"join" => method Self::qs_join,
+	"done?" => method Self::qs_done_q,
+	"name" => method Self::qs_name,
+	"inspect" => method Self::qs_inspect,
+}
+
+/// Quest methods
+impl ThreadPool {
+	/// Creates a new thread pool.
+	///
+	/// # Arguments
+	/// 1. (optional, `@num`) The amount of threads; defaults to the amount of CPUs.
+	#[instrument(name="ThreadPool::()", level="trace", skip(args), fields(?args))]
+	pub fn qs_call(_: &Object, args: Args) -> Result<Object> {
+		let size =
+			match args.arg(0) {
+				Some(size) => usize::try_from(size.call_downcast::<Number>()?.clone())?,
+				None => thread::available_parallelism().map(usize::from).unwrap_or(1)
+			};
+
+		if size == 0 {
+			return Err(ValueError::Messaged("thread pools need at least one thread".into()).into());
+		}
+
+		Ok(Self::new(size)?.into())
+	}
+
+	/// Queues a block to be run on one of the pool's threads, returning a [`Future`] for its result.
+	///
+	/// # Arguments
+	/// 1. (required) The block to run.
+	/// 2+. (optional) The arguments to pass to the block.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// $pool = ThreadPool(2);
+	/// $future = pool.submit({ _0 * 2 }, 21);
+	/// assert(future.join() == 42);
+	/// ```
+	#[instrument(name="ThreadPool::submit", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_submit(this: &Object, args: Args) -> Result<Object> {
+		let block = args.try_arg(0)?.clone();
+		let pool = this.try_downcast::<Self>()?.clone();
+
+		pool.submit(block, spawn_args(&args, 1))
+			.map(Object::from)
+			.ok_or_else(|| ValueError::Messaged("submitted to a thread pool that was shut down".into()).into())
+	}
+
+	/// Calls a block with each element of an iterable on the pool's threads, returning a [`List`]
+	/// of the results in order.
+	///
+	/// If any of the calls raise an exception, the first one (in order) is raised again, after all
+	/// the calls are done.
+	///
+	/// # Arguments
+	/// 1. (required, `@iter`) The elements.
+	/// 2. (required) The block to call with each element.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// $pool = ThreadPool(4);
+	/// $expected = [1, 4, 9, 16];
+	/// assert(pool.map([1, 2, 3, 4], { _0 * _0 }) == expected);
+	/// ```
+	#[instrument(name="ThreadPool::map", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_map(this: &Object, args: Args) -> Result<Object> {
+		let mut elements = Iter::from_object(args.try_arg(0)?)?;
+		let block = args.try_arg(1)?;
+		let pool = this.try_downcast::<Self>()?.clone();
+
+		let futures = elements.try_into_list()?.iter()
+			.map(|element| pool.submit(block.clone(), vec![element.clone()]))
+			.collect::<Option<Vec<_>>>()
+			.ok_or_else(|| ValueError::Messaged("submitted to a thread pool that was shut down".into()))?;
+
+		let results = futures.iter()
+			.map(|future| future.join(None).expect("futures always finish without a timeout"))
+			.collect::<Vec<_>>();
+
+		Ok(List::from(results.into_iter().collect::<Result<Vec<_>>>()?).into())
+	}
+
+	/// Gets the amount of threads in the pool.
+	#[instrument(name="ThreadPool::size", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_size(this: &Object, _: Args) -> Result<Object> {
+		Ok(this.try_downcast::<Self>()?.size().into())
+	}
+
+	/// Stops accepting new blocks, and waits for the ones already submitted to finish, returning
+	/// `this`.
+	#[instrument(name="ThreadPool::shutdown", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_shutdown(this: &Object, _: Args) -> Result<Object> {
+		let pool = this.try_downcast::<Self>()?.clone();
+		pool.shutdown();
+
+		Ok(this.clone())
+	}
+
+	/// Inspects `this`, such as `ThreadPool(4)`.
+	#[instrument(name="ThreadPool::inspect", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_inspect(this: &Object, _: Args) -> Result<Object> {
+		Ok(Text::from(format!("ThreadPool({})", this.try_downcast::<Self>()?.size())).into())
+	}
+}
+
+impl_object_type!{
+for ThreadPool [(parents super::Basic)]:
+	"Future" => const Future::mapping().clone(),
+	"()" => method Self::qs_call,
+	"submit" => method Self::qs_submit,
+	"map" => method Self::qs_map,
+	"size" => method Self::qs_size,
+	"shutdown" => method Self::qs_shutdown,
+	"inspect" => method Self::qs_inspect,
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::types::RustClosure;
+
+	fn closure(func: impl Fn(Args) -> Result<Object> + Send + Sync + 'static) -> Object {
+		RustClosure::new(func).into()
+	}
+
+	#[test]
+	fn thread_results() {
+		crate::init();
+
+		let thread = Thread::spawn(Some("adder".into()), closure(|args| {
+			let lhs = args.try_arg(0)?.downcast::<Number>().unwrap().clone();
+			let rhs = args.try_arg(1)?.downcast::<Number>().unwrap().clone();
+			Ok((lhs + rhs).into())
+		}), vec![Object::from(1), Object::from(2)]).unwrap();
+
+		for _ in 0..2 {
+			let result = thread.future().join(None).unwrap().unwrap();
+			assert_eq!(*result.downcast::<Number>().unwrap(), Number::from(3));
+		}
+
+		assert!(thread.future().is_done());
+		assert_eq!(thread.name(), Some("adder"));
+
+		let slow = Thread::spawn(None, closure(|_| {
+			thread::sleep(Duration::from_millis(100));
+			Ok(Object::default())
+		}), vec![]).unwrap();
+
+		assert!(slow.future().join(Some(Duration::from_millis(1))).is_none());
+		assert!(slow.future().join(None).unwrap().is_ok());
+	}
+
+	#[test]
+	fn errors_and_panics() {
+		crate::init();
+
+		let failed = Thread::spawn(None, closure(|_| Err(ValueError::Messaged("oops".into()).into())), vec![]).unwrap();
+
+		match failed.future().join(None).unwrap() {
+			Err(Error::Thrown(exception)) => assert_eq!(exception.downcast::<Exception>().unwrap().kind(), "ValueError"),
+			other => panic!("expected an exception, got {:?}", other)
+		}
+
+		let panicked = Thread::spawn(None, closure(|_| panic!("whoops")), vec![]).unwrap();
+
+		match panicked.future().join(None).unwrap() {
+			Err(Error::Thrown(exception)) => {
+				let exception = exception.downcast::<Exception>().unwrap();
+				assert_eq!(exception.kind(), "Panic");
+				assert_eq!(exception.message(), "thread panicked: whoops");
+			},
+			other => panic!("expected an exception, got {:?}", other)
+		}
+
+		// returns aimed at frames outside of the thread can't be honoured.
+		let returned = Thread::spawn(None, closure(|_| Err(Error::Return { to: Binding::instance(), obj: 1.into() })), vec![]).unwrap();
+
+		match returned.future().join(None).unwrap() {
+			Err(Error::Thrown(exception)) => assert_eq!(exception.downcast::<Exception>().unwrap().kind(), "Error"),
+			other => panic!("expected an exception, got {:?}", other)
+		}
+	}
+
+	#[test]
+	fn pool() {
+		crate::init();
+
+		let pool = ThreadPool::new(3).unwrap();
+		let futures = (0..10)
+			.map(|i| pool.submit(closure(|args| Ok(args.try_arg(0)?.clone())), vec![Object::from(i)]).unwrap())
+			.collect::<Vec<_>>();
+
+		for (i, future) in futures.iter().enumerate() {
+			let result = future.join(None).unwrap().unwrap();
+			assert_eq!(*result.downcast::<Number>().unwrap(), Number::from(i));
+		}
+
+		pool.shutdown();
+		assert!(pool.submit(closure(|_| Ok(Object::default())), vec![]).is_none());
+	}
+}
@@ -0,0 +1,575 @@
+use crate::{Object, Args};
+use crate::error::ValueError;
+use crate::types::{Text, Number, Null, Duration, Date};
+use crate::types::number::FloatType;
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, Datelike, Timelike, Utc};
+use std::convert::TryFrom;
+use std::fmt::{self, Display, Formatter, Write};
+use std::sync::Arc;
+use tracing::instrument;
+
+/// An instant in time, along with the UTC offset it's displayed in.
+///
+/// Offsets are looked up in the system's time zone database (e.g. `/usr/share/zoneinfo`), with the
+/// local zone coming from the `TZ` environment variable or `/etc/localtime`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Time(DateTime<FixedOffset>);
+
+impl From<DateTime<FixedOffset>> for Time {
+	#[inline]
+	fn from(time: DateTime<FixedOffset>) -> Self {
+		Self(time)
+	}
+}
+
+impl From<Time> for DateTime<FixedOffset> {
+	#[inline]
+	fn from(time: Time) -> Self {
+		time.0
+	}
+}
+
+impl Display for Time {
+	/// Writes the time in RFC 3339 format.
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		f.write_str(&self.0.to_rfc3339())
+	}
+}
+
+/// A time zone, which is used to figure out the UTC offset at a given instant.
+#[derive(Debug, Clone)]
+pub(crate) enum Zone {
+	Fixed(FixedOffset),
+	Tz(Arc<tz::TimeZone>)
+}
+
+impl Zone {
+	/// Coordinated Universal Time.
+	pub fn utc() -> Self {
+		Self::Fixed(FixedOffset::east(0))
+	}
+
+	/// The local time zone, which is read from `TZ` (or `/etc/localtime` if it's not set) once and
+	/// then cached. If it can't be read, UTC is used instead.
+	pub fn local() -> Self {
+		use lazy_static::lazy_static;
+
+		lazy_static! {
+			static ref LOCAL: Option<Arc<tz::TimeZone>> =
+				match std::env::var("TZ") {
+					Ok(tz) if !tz.is_empty() => tz::TimeZone::from_posix_tz(&tz),
+					_ => tz::TimeZone::local()
+				}.ok().map(Arc::new);
+		}
+
+		LOCAL.clone().map(Self::Tz).unwrap_or_else(Self::utc)
+	}
+
+	/// Look up a time zone by its name, such as `"America/Chicago"`, `"UTC"`, `"local"`, or a POSIX
+	/// TZ string such as `"EST5EDT"`.
+	pub fn named(name: &str) -> crate::Result<Self> {
+		match name {
+			"local" => Ok(Self::local()),
+			"UTC" | "utc" | "Z" => Ok(Self::utc()),
+			_ => tz::TimeZone::from_posix_tz(name)
+				.map(|tz| Self::Tz(Arc::new(tz)))
+				// if it's not in the tz database, the name's parsed as a POSIX TZ string, whose error
+				// message isn't very helpful for the usual case of a misspelled zone name.
+				.map_err(|_| ValueError::Messaged(format!("unknown time zone '{}': not found in the tz database", name)).into())
+		}
+	}
+
+	/// Converts an optional argument to a zone; a [`Duration`] is a fixed offset east of UTC, and
+	/// anything else is converted to a [`Text`] and [looked up](#method.named). If the argument is
+	/// missing or [`Null`], the local zone is used.
+	pub fn from_arg(arg: Option<&Object>) -> crate::Result<Self> {
+		let arg =
+			match arg {
+				Some(arg) if !arg.is_a::<Null>() => arg,
+				_ => return Ok(Self::local())
+			};
+
+		if let Some(offset) = arg.downcast::<Duration>() {
+			let secs = chrono::Duration::from(*offset).num_seconds();
+
+			i32::try_from(secs).ok()
+				.and_then(FixedOffset::east_opt)
+				.map(Self::Fixed)
+				.ok_or_else(|| ValueError::Messaged(format!("utc offset out of range: {}", *offset)).into())
+		} else {
+			Self::named(arg.call_downcast::<Text>()?.as_ref())
+		}
+	}
+
+	/// Get the UTC offset in effect at `unix` seconds since the epoch.
+	pub fn offset_at(&self, unix: i64) -> FixedOffset {
+		match self {
+			Self::Fixed(offset) => *offset,
+			Self::Tz(tz) => tz.find_local_time_type(unix).ok()
+				.and_then(|local| FixedOffset::east_opt(local.ut_offset()))
+				.unwrap_or_else(|| FixedOffset::east(0))
+		}
+	}
+
+	/// Converts `time` to this zone.
+	pub fn at(&self, time: DateTime<Utc>) -> Time {
+		Time(time.with_timezone(&self.offset_at(time.timestamp())))
+	}
+
+	/// Interprets `naive` as a wall-clock time in this zone.
+	///
+	/// Wall-clock times that are skipped or repeated by a transition use the offset in effect just
+	/// before it.
+	pub fn localize(&self, naive: NaiveDateTime) -> Time {
+		let guess = self.offset_at(naive.timestamp());
+		let offset = self.offset_at(naive.timestamp() - i64::from(guess.local_minus_utc()));
+
+		Time(DateTime::from_utc(naive - chrono::Duration::seconds(offset.local_minus_utc().into()), offset))
+	}
+}
+
+/// Formats `value` using the strftime-style `fmt`, returning an error if `fmt` is invalid.
+pub(crate) fn format<T: Display>(value: T, fmt: &str) -> crate::Result<Text> {
+	let mut text = String::new();
+
+	write!(&mut text, "{}", value)
+		.map_err(|_| ValueError::Messaged(format!("invalid format string: {:?}", fmt)))?;
+
+	Ok(text.into())
+}
+
+/// Converts `secs` to whole seconds and nanoseconds, where the nanoseconds are never negative.
+fn split_secs(secs: Number) -> crate::Result<(i64, u32)> {
+	let nanos = chrono::Duration::from(Duration::from_secs(secs)?).num_nanoseconds()
+		.ok_or_else(|| ValueError::Messaged("time out of range".to_string()))?;
+
+	Ok((nanos.div_euclid(1_000_000_000), nanos.rem_euclid(1_000_000_000) as u32))
+}
+
+/// Gets the `index`th argument as a date or time component, or `default` if it's not given.
+pub(crate) fn component(args: &Args, index: usize, default: u32) -> crate::Result<u32> {
+	if let Some(arg) = args.arg(index) {
+		Ok(u32::try_from(arg.call_downcast::<Number>()?.clone())?)
+	} else {
+		Ok(default)
+	}
+}
+
+impl Time {
+	/// Get the current time in the given zone.
+	pub(crate) fn now(zone: &Zone) -> Self {
+		zone.at(std::time::SystemTime::now().into())
+	}
+
+	/// Build a time from its wall-clock components in `zone`; `second` may be fractional.
+	pub(crate) fn from_parts(date: NaiveDate, hour: u32, minute: u32, second: Number, zone: &Zone)
+		-> crate::Result<Self>
+	{
+		let (second, nano) = split_secs(second)?;
+
+		u32::try_from(second).ok()
+			.and_then(|second| date.and_hms_nano_opt(hour, minute, second, nano))
+			.map(|naive| zone.localize(naive))
+			.ok_or_else(|| ValueError::Messaged(
+				format!("invalid time: {} {:02}:{:02}:{:02}", date, hour, minute, second)).into())
+	}
+
+	/// Get the amount of seconds since the unix epoch, including fractional seconds.
+	pub fn unix(&self) -> Number {
+		let nanos = self.0.timestamp_subsec_nanos();
+
+		if nanos == 0 {
+			self.0.timestamp().into()
+		} else {
+			(self.0.timestamp() as FloatType + FloatType::from(nanos) / 1e9).into()
+		}
+	}
+
+	/// Get the calendar date of `self` in its own offset.
+	#[inline]
+	pub fn date(&self) -> Date {
+		self.0.naive_local().date().into()
+	}
+}
+
+/// Quest methods
+impl Time {
+	/// Create a new time from its wall-clock components.
+	///
+	/// # Arguments
+	/// 1. (required, `@num`) The year.
+	/// 2. (optional, `@num`) The month, starting at 1; defaults to 1.
+	/// 3. (optional, `@num`) The day of the month, starting at 1; defaults to 1.
+	/// 4. (optional, `@num`) The hour; defaults to 0.
+	/// 5. (optional, `@num`) The minute; defaults to 0.
+	/// 6. (optional, `@num`) The second, which may be fractional; defaults to 0.
+	/// 7. (optional) The time zone; see [`Time.now`](#method.qs_now). Defaults to the local zone.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// $t = Time(2021, 3, 14, 15, 9, 26.5, "UTC");
+	/// assert(t.@text() == "2021-03-14T15:09:26.500+00:00");
+	/// ```
+	#[instrument(name="Time::()", level="trace", skip(args), fields(?args))]
+	pub fn qs_call(_: &Object, args: Args) -> crate::Result<Object> {
+		let year = i32::try_from(args.try_arg(0)?.call_downcast::<Number>()?.clone())?;
+		let month = component(&args, 1, 1)?;
+		let day = component(&args, 2, 1)?;
+		let hour = component(&args, 3, 0)?;
+		let minute = component(&args, 4, 0)?;
+		let second =
+			if let Some(second) = args.arg(5) {
+				second.call_downcast::<Number>()?.clone()
+			} else {
+				Number::ZERO
+			};
+		let zone = Zone::from_arg(args.arg(6))?;
+
+		let date = NaiveDate::from_ymd_opt(year, month, day)
+			.ok_or_else(|| ValueError::Messaged(format!("invalid date: {}-{:02}-{:02}", year, month, day)))?;
+
+		Ok(Self::from_parts(date, hour, minute, second, &zone)?.into())
+	}
+
+	/// Get the current time.
+	///
+	/// # Arguments
+	/// 1. (optional) The time zone, which is either a [`Duration`] offset east of UTC, or the name of a
+	///    zone, such as `"America/New_York"`, `"UTC"`, or `"local"`. Defaults to the local zone.
+	#[instrument(name="Time::now", level="trace", skip(args), fields(?args))]
+	pub fn qs_now(_: &Object, args: Args) -> crate::Result<Object> {
+		Ok(Self::now(&Zone::from_arg(args.arg(0))?).into())
+	}
+
+	/// Create a time from the amount of seconds since the unix epoch.
+	///
+	/// # Arguments
+	/// 1. (required, `@num`) The seconds since the epoch, which may be fractional.
+	/// 2. (optional) The time zone; see [`Time.now`](#method.qs_now). Defaults to the local zone.
+	#[instrument(name="Time::at", level="trace", skip(args), fields(?args))]
+	pub fn qs_at(_: &Object, args: Args) -> crate::Result<Object> {
+		let (secs, nanos) = split_secs(args.try_arg(0)?.call_downcast::<Number>()?.clone())?;
+		let zone = Zone::from_arg(args.arg(1))?;
+
+		NaiveDateTime::from_timestamp_opt(secs, nanos)
+			.map(|naive| zone.at(DateTime::from_utc(naive, Utc)).into())
+			.ok_or_else(|| ValueError::Messaged("time out of range".to_string()).into())
+	}
+
+	/// Parses a time from text.
+	///
+	/// If the format doesn't include a UTC offset (`%z`), the time is interpreted as a wall-clock time
+	/// in the given zone. Likewise, if it doesn't include a time of day, midnight is used.
+	///
+	/// # Arguments
+	/// 1. (required, `@text`) The text to parse.
+	/// 2. (optional, `@text`) The strftime-style format; defaults to RFC 3339.
+	/// 3. (optional) The time zone; see [`Time.now`](#method.qs_now). Defaults to the local zone.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// $t = Time.parse("2020-02-29 12:30", "%Y-%m-%d %H:%M", "UTC");
+	/// assert(t.hour() == 12);
+	/// ```
+	#[instrument(name="Time::parse", level="trace", skip(args), fields(?args))]
+	pub fn qs_parse(_: &Object, args: Args) -> crate::Result<Object> {
+		let text = args.try_arg(0)?.call_downcast::<Text>()?;
+		let text = text.as_ref();
+
+		let fmt =
+			match args.arg(1) {
+				Some(fmt) if !fmt.is_a::<Null>() => fmt.call_downcast::<Text>()?.as_ref().to_string(),
+				_ => return DateTime::parse_from_rfc3339(text)
+					.map(|time| Self(time).into())
+					.map_err(|err| ValueError::Messaged(format!("cannot parse {:?}: {}", text, err)).into())
+			};
+
+		if let Ok(time) = DateTime::parse_from_str(text, &fmt) {
+			return Ok(Self(time).into());
+		}
+
+		let zone = Zone::from_arg(args.arg(2))?;
+
+		NaiveDateTime::parse_from_str(text, &fmt)
+			.or_else(|err| NaiveDate::parse_from_str(text, &fmt).map(|date| date.and_hms(0, 0, 0)).map_err(|_| err))
+			.map(|naive| zone.localize(naive).into())
+			.map_err(|err| ValueError::Messaged(format!("cannot parse {:?} with {:?}: {}", text, fmt, err)).into())
+	}
+
+	/// Get the time elapsed on a monotonic clock, which is unaffected by changes to the system time.
+	///
+	/// The starting point is unspecified, so this is only useful for measuring the [`Duration`]
+	/// between two calls.
+	#[instrument(name="Time::monotonic", level="trace")]
+	pub fn qs_monotonic(_: &Object, _: Args) -> crate::Result<Object> {
+		use lazy_static::lazy_static;
+		use std::time::Instant;
+
+		lazy_static! {
+			static ref START: Instant = Instant::now();
+		}
+
+		Ok(Duration::from(START.elapsed()).into())
+	}
+
+	/// Formats `this` with a strftime-style format.
+	///
+	/// # Arguments
+	/// 1. (required, `@text`) The format, such as `"%Y-%m-%d %H:%M:%S"`.
+	#[instrument(name="Time::format", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_format(this: &Object, args: Args) -> crate::Result<Object> {
+		let fmt = args.try_arg(0)?.call_downcast::<Text>()?;
+		let this = this.try_downcast::<Self>()?;
+
+		Ok(format(this.0.format(fmt.as_ref()), fmt.as_ref())?.into())
+	}
+
+	/// Converts `this` to a [`Text`] in RFC 3339 format.
+	#[instrument(name="Time::@text", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_at_text(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(Text::from(this.try_downcast::<Self>()?.to_string()).into())
+	}
+
+	/// Inspects `this`.
+	#[instrument(name="Time::inspect", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_inspect(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(Text::from(format!("Time({})", *this.try_downcast::<Self>()?)).into())
+	}
+
+	/// Converts `this` to a [`Number`] of seconds since the unix epoch.
+	#[instrument(name="Time::unix", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_unix(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.unix().into())
+	}
+
+	/// Get the calendar [`Date`] of `this`.
+	#[instrument(name="Time::date", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_date(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.date().into())
+	}
+
+	/// Get the year of `this`.
+	#[instrument(name="Time::year", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_year(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.0.year().into())
+	}
+
+	/// Get the month of `this`, starting at 1.
+	#[instrument(name="Time::month", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_month(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.0.month().into())
+	}
+
+	/// Get the day of the month of `this`, starting at 1.
+	#[instrument(name="Time::day", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_day(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.0.day().into())
+	}
+
+	/// Get the hour of `this`.
+	#[instrument(name="Time::hour", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_hour(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.0.hour().into())
+	}
+
+	/// Get the minute of `this`.
+	#[instrument(name="Time::minute", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_minute(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.0.minute().into())
+	}
+
+	/// Get the whole seconds of `this`.
+	#[instrument(name="Time::second", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_second(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.0.second().into())
+	}
+
+	/// Get the nanoseconds past the second of `this`.
+	#[instrument(name="Time::nanosecond", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_nanosecond(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.0.nanosecond().into())
+	}
+
+	/// Get the day of the week of `this`, from 1 (Monday) to 7 (Sunday).
+	#[instrument(name="Time::weekday", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_weekday(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.0.weekday().number_from_monday().into())
+	}
+
+	/// Get the day of the year of `this`, starting at 1.
+	#[instrument(name="Time::yday", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_yday(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(this.try_downcast::<Self>()?.0.ordinal().into())
+	}
+
+	/// Get the UTC offset of `this`, as a [`Duration`] east of UTC.
+	#[instrument(name="Time::offset", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_offset(this: &Object, _: Args) -> crate::Result<Object> {
+		let offset = this.try_downcast::<Self>()?.0.offset().local_minus_utc();
+
+		Ok(Duration::from(chrono::Duration::seconds(offset.into())).into())
+	}
+
+	/// Get the same instant as `this`, in a different time zone.
+	///
+	/// # Arguments
+	/// 1. (required) The time zone; see [`Time.now`](#method.qs_now).
+	#[instrument(name="Time::in", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_in(this: &Object, args: Args) -> crate::Result<Object> {
+		let zone = Zone::from_arg(Some(args.try_arg(0)?))?;
+
+		Ok(zone.at(this.try_downcast::<Self>()?.0.with_timezone(&Utc)).into())
+	}
+
+	/// Get the same instant as `this`, in UTC.
+	#[instrument(name="Time::utc", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_utc(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(Zone::utc().at(this.try_downcast::<Self>()?.0.with_timezone(&Utc)).into())
+	}
+
+	/// Get the same instant as `this`, in the local time zone.
+	#[instrument(name="Time::local", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_local(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(Zone::local().at(this.try_downcast::<Self>()?.0.with_timezone(&Utc)).into())
+	}
+
+	/// Hash `this`; times that refer to the same instant have the same hash.
+	#[instrument(name="Time::hash", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_hash(this: &Object, _: Args) -> crate::Result<Object> {
+		Ok(crate::utils::hash(&*this.try_downcast::<Self>()?).into())
+	}
+
+	/// See if `this` refers to the same instant as the first argument.
+	///
+	/// # Arguments
+	/// 1. (required) The other object to compare against.
+	#[instrument(name="Time::==", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_eql(this: &Object, args: Args) -> crate::Result<Object> {
+		let this = *this.try_downcast::<Self>()?;
+		let rhs = args.try_arg(0)?.downcast::<Self>();
+
+		Ok(rhs.map(|rhs| *rhs == this).unwrap_or(false).into())
+	}
+
+	/// Compares `this` to the first argument.
+	///
+	/// # Arguments
+	/// 1. (required) The time to compare against.
+	#[instrument(name="Time::<=>", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_cmp(this: &Object, args: Args) -> crate::Result<Object> {
+		let this = *this.try_downcast::<Self>()?;
+		let rhs = args.try_arg(0)?.downcast::<Self>();
+
+		Ok(rhs.map(|rhs| this.cmp(&*rhs).into()).unwrap_or_default())
+	}
+
+	/// Add a [`Duration`] to `this`.
+	///
+	/// # Arguments
+	/// 1. (required, [`Duration`]) The duration to add.
+	#[instrument(name="Time::+", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_add(this: &Object, args: Args) -> crate::Result<Object> {
+		let duration = *args.try_arg(0)?.try_downcast::<Duration>()?;
+		let this = this.try_downcast::<Self>()?;
+
+		this.0.checked_add_signed(duration.into())
+			.map(|time| Self(time).into())
+			.ok_or_else(|| ValueError::Messaged("time out of range".to_string()).into())
+	}
+
+	/// Subtract the first argument from `this`.
+	///
+	/// Subtracting a [`Duration`] returns a new [`Time`], and subtracting a [`Time`] returns the
+	/// [`Duration`] between the two.
+	///
+	/// # Arguments
+	/// 1. (required, [`Duration`] or [`Time`]) The value to subtract.
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// $start = Time(2020, 1, 1, 0, 0, 0, "UTC");
+	/// assert((start + Duration.HOUR) - start == Duration.HOUR);
+	/// ```
+	#[instrument(name="Time::-", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_sub(this: &Object, args: Args) -> crate::Result<Object> {
+		let rhs = args.try_arg(0)?;
+		let this = this.try_downcast::<Self>()?;
+
+		if let Some(rhs) = rhs.downcast::<Self>() {
+			return Ok(Duration::from(this.0.signed_duration_since(rhs.0)).into());
+		}
+
+		let duration = *rhs.try_downcast::<Duration>()?;
+
+		this.0.checked_sub_signed(duration.into())
+			.map(|time| Self(time).into())
+			.ok_or_else(|| ValueError::Messaged("time out of range".to_string()).into())
+	}
+}
+
+impl_object_type!{
+for Time [(init_parent super::Basic super::Comparable) (parents super::Basic)]:
+	"()" => method Self::qs_call,
+	"now" => method Self::qs_now,
+	"at" => method Self::qs_at,
+	"parse" => method Self::qs_parse,
+	"monotonic" => method Self::qs_monotonic,
+
+	"format" => method Self::qs_format,
+	"@text" => method Self::qs_at_text,
+	"inspect" => method Self::qs_inspect,
+	"unix" => method Self::qs_unix,
+	"date" => method Self::qs_date,
+	"year" => method Self::qs_year,
+	"month" => method Self::qs_month,
+	"day" => method Self::qs_day,
+	"hour" => method Self::qs_hour,
+	"minute" => method Self::qs_minute,
+	"second" => method Self::qs_second,
+	"nanosecond" => method Self::qs_nanosecond,
+	"weekday" => method Self::qs_weekday,
+	"yday" => method Self::qs_yday,
+	"offset" => method Self::qs_offset,
+
+	"in" => method Self::qs_in,
+	"utc" => method Self::qs_utc,
+	"local" => method Self::qs_local,
+
+	"hash" => method Self::qs_hash,
+	"==" => method Self::qs_eql,
+	"<=>" => method Self::qs_cmp,
+	"+" => method Self::qs_add,
+	"-" => method Self::qs_sub,
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn zones() {
+		let naive = NaiveDate::from_ymd(2021, 7, 1).and_hms(12, 0, 0);
+
+		assert_eq!(Zone::utc().localize(naive).to_string(), "2021-07-01T12:00:00+00:00");
+		assert_eq!(Zone::named("EST5EDT,M3.2.0,M11.1.0").unwrap().localize(naive).to_string(),
+			"2021-07-01T12:00:00-04:00");
+		assert_eq!(Zone::named("EST5EDT,M3.2.0,M11.1.0").unwrap().localize(naive.with_month(1).unwrap()).to_string(),
+			"2021-01-01T12:00:00-05:00");
+		assert_eq!(Zone::named("Not/A_Zone").unwrap_err().to_string(),
+			"value error: unknown time zone 'Not/A_Zone': not found in the tz database");
+	}
+
+	#[test]
+	fn arithmetic() {
+		crate::init();
+
+		let start = Object::from(Zone::utc().localize(NaiveDate::from_ymd(2020, 2, 28).and_hms(23, 0, 0)));
+		let day = Object::from(Duration::from(chrono::Duration::days(1)));
+
+		let next = Time::qs_add(&start, args!(day.clone())).unwrap();
+		assert_eq!(next.downcast::<Time>().unwrap().to_string(), "2020-02-29T23:00:00+00:00");
+
+		let diff = Time::qs_sub(&next, args!(start)).unwrap();
+		assert!(diff.eq_obj(&day).unwrap());
+	}
+}
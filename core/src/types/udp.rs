@@ -0,0 +1,220 @@
+use crate::{Object, Args, Result};
+use crate::types::{Text, Bytes, Null};
+use crate::types::bytes::{to_write, to_len};
+
+use std::convert::TryFrom;
+use std::io;
+use std::net::{SocketAddr, ToSocketAddrs, UdpSocket};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::instrument;
+
+/// The largest payload a UDP datagram can have, which is the default size for receiving.
+const MAX_DATAGRAM_SIZE: usize = 65_507;
+
+/// A UDP socket, along with whether it receives [`Bytes`] instead of [`Text`].
+#[derive(Debug, Clone)]
+pub struct Udp(Arc<UdpSocket>, bool);
+
+impl Udp {
+	/// Bind a socket to `addr`.
+	pub fn bind<A: ToSocketAddrs>(addr: A, binary: bool) -> io::Result<Self> {
+		Ok(Self(Arc::new(UdpSocket::bind(addr)?), binary))
+	}
+
+	/// Checks to see if the socket is in binary mode.
+	#[inline]
+	pub fn is_binary(&self) -> bool {
+		self.1
+	}
+
+	/// Get the address the socket is bound to.
+	#[inline]
+	pub fn local_addr(&self) -> io::Result<SocketAddr> {
+		self.0.local_addr()
+	}
+
+	/// Receive a datagram of at most `size` bytes (anything past that is discarded), waiting at most
+	/// `timeout` if it's given. `None` is returned if the timeout expires.
+	///
+	/// If the socket is [connected](UdpSocket::connect), only datagrams from that address are
+	/// received.
+	pub fn recv_from(&self, size: usize, timeout: Option<Duration>) -> io::Result<Option<(Vec<u8>, SocketAddr)>> {
+		// a zero timeout is an error, so we wait as short as possible instead.
+		self.0.set_read_timeout(timeout.map(|timeout| timeout.max(Duration::from_nanos(1))))?;
+
+		let mut buf = vec![0; size];
+
+		match self.0.recv_from(&mut buf) {
+			Ok((len, addr)) => {
+				buf.truncate(len);
+				Ok(Some((buf, addr)))
+			},
+			Err(err) if matches!(err.kind(), io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut) => Ok(None),
+			Err(err) => Err(err)
+		}
+	}
+
+	/// Converts a received datagram to [`Bytes`] in binary mode, and [`Text`] otherwise.
+	fn datagram(&self, data: Vec<u8>) -> Result<Object> {
+		let bytes = Bytes::from(data);
+
+		if self.is_binary() {
+			Ok(bytes.into())
+		} else {
+			Ok(Text::try_from(&bytes)?.into())
+		}
+	}
+
+	/// Parses the size and timeout arguments of [`qs_recv`](#method.qs_recv) and
+	/// [`qs_recv_from`](#method.qs_recv_from), and then receives a datagram.
+	fn recv_args(&self, args: &Args) -> Result<Option<(Object, SocketAddr)>> {
+		let size =
+			match args.arg(0) {
+				Some(size) if !size.is_a::<Null>() => to_len(size)?,
+				_ => MAX_DATAGRAM_SIZE
+			};
+		let timeout = args.arg(1).map(super::duration::to_std).transpose()?;
+
+		match self.recv_from(size, timeout)? {
+			Some((data, addr)) => Ok(Some((self.datagram(data)?, addr))),
+			None => Ok(None)
+		}
+	}
+}
+
+/// Quest methods
+impl Udp {
+	/// Bind a socket to the address given by the first argument.
+	///
+	/// # Arguments
+	/// 1. (required, `@text`) The address to bind to, such as `"127.0.0.1:8125"`. Use port `0` to have
+	///    one picked for you.
+	/// 2. (optional, `@text`) The mode; `"b"` receives [`Bytes`] instead of [`Text`].
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// $server = Udp("127.0.0.1:0");
+	/// $client = Udp("127.0.0.1:0");
+	/// client.send_to("ping", server.local_addr());
+	/// $expected = ["ping", client.local_addr()];
+	/// assert(server.recv_from() == expected);
+	/// ```
+	#[instrument(name="Udp::()", level="trace")]
+	pub fn qs_call(_: &Object, args: Args) -> Result<Object> {
+		let binary = super::tcp::parse_mode(args.arg(1))?;
+		let addr = args.try_arg(0)?.call_downcast::<Text>()?;
+
+		Ok(Self::bind(addr.as_ref(), binary)?.into())
+	}
+
+	/// Send a datagram to the given address.
+	///
+	/// # Arguments
+	/// 1. (required, [`Bytes`] or `@text`) The data to send.
+	/// 2. (required, `@text`) The address to send it to.
+	#[instrument(name="Udp::send_to", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_send_to(this: &Object, args: Args) -> Result<Object> {
+		let data = to_write(args.try_arg(0)?)?;
+		let addr = args.try_arg(1)?.call_downcast::<Text>()?;
+
+		Ok(this.try_downcast::<Self>()?.0.send_to(&data, addr.as_ref())?.into())
+	}
+
+	/// Receive a datagram, returning a [`List`](super::List) of the data and the address it came from.
+	///
+	/// [`Null`] is returned if the timeout expires.
+	///
+	/// # Arguments
+	/// 1. (optional, `@num`) The most bytes to receive; anything past that is discarded. Defaults to
+	///    the largest possible datagram.
+	/// 2. (optional, [`Duration`](super::Duration) or `@num`) How long to wait; numbers are in
+	///    seconds. If omitted, waits until a datagram arrives.
+	#[instrument(name="Udp::recv_from", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_recv_from(this: &Object, args: Args) -> Result<Object> {
+		let udp = this.try_downcast::<Self>()?.clone();
+
+		match udp.recv_args(&args)? {
+			Some((data, addr)) => Ok(vec![data, Text::from(addr.to_string()).into()].into()),
+			None => Ok(Null.into())
+		}
+	}
+
+	/// Only send to and receive from the given address.
+	///
+	/// # Arguments
+	/// 1. (required, `@text`) The address to connect to.
+	#[instrument(name="Udp::connect", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_connect(this: &Object, args: Args) -> Result<Object> {
+		let addr = args.try_arg(0)?.call_downcast::<Text>()?;
+
+		this.try_downcast::<Self>()?.0.connect(addr.as_ref())?;
+
+		Ok(this.clone())
+	}
+
+	/// Send a datagram to the [connected](#method.qs_connect) address.
+	///
+	/// # Arguments
+	/// 1. (required, [`Bytes`] or `@text`) The data to send.
+	#[instrument(name="Udp::send", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_send(this: &Object, args: Args) -> Result<Object> {
+		let data = to_write(args.try_arg(0)?)?;
+
+		Ok(this.try_downcast::<Self>()?.0.send(&data)?.into())
+	}
+
+	/// Receive a datagram, without the address it came from.
+	///
+	/// [`Null`] is returned if the timeout expires.
+	///
+	/// # Arguments
+	/// 1. (optional, `@num`) The most bytes to receive; see [`recv_from`](#method.qs_recv_from).
+	/// 2. (optional, [`Duration`](super::Duration) or `@num`) How long to wait.
+	#[instrument(name="Udp::recv", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_recv(this: &Object, args: Args) -> Result<Object> {
+		let udp = this.try_downcast::<Self>()?.clone();
+
+		Ok(udp.recv_args(&args)?.map(|(data, _)| data).unwrap_or_default())
+	}
+
+	/// Get the address `this` is bound to, such as `"127.0.0.1:8125"`.
+	#[instrument(name="Udp::local_addr", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_local_addr(this: &Object, _: Args) -> Result<Object> {
+		Ok(Text::from(this.try_downcast::<Self>()?.local_addr()?.to_string()).into())
+	}
+
+	/// Checks to see if `this` is in binary mode.
+	#[instrument(name="Udp::binary?", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_binary_q(this: &Object, _: Args) -> Result<Object> {
+		Ok(this.try_downcast::<Self>()?.is_binary().into())
+	}
+}
+
+impl_object_type!{
+for Udp [(parents super::Basic)]:
+	"()" => method Self::qs_call,
+	"send_to" => method Self::qs_send_to,
+	"recv_from" => method Self::qs_recv_from,
+	"connect" => method Self::qs_connect,
+	"send" => method Self::qs_send,
+	"recv" => method Self::qs_recv,
+	"local_addr" => method Self::qs_local_addr,
+	"binary?" => method Self::qs_binary_q,
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn send_and_receive() {
+		let server = Udp::bind("127.0.0.1:0", true).unwrap();
+		let client = UdpSocket::bind("127.0.0.1:0").unwrap();
+
+		assert_eq!(server.recv_from(16, Some(Duration::from_millis(20))).unwrap(), None);
+
+		client.send_to(b"\xffhello", server.local_addr().unwrap()).unwrap();
+		assert_eq!(server.recv_from(3, None).unwrap(), Some((b"\xffhe".to_vec(), client.local_addr().unwrap())));
+	}
+}
@@ -0,0 +1,363 @@
+use crate::{Object, Args, Literal, Result};
+use crate::types::{Text, Null};
+use crate::types::bytes::{to_write, read_from};
+
+use std::convert::TryFrom;
+use std::io::{self, Write};
+use std::os::unix::net::{self, UnixStream};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::instrument;
+
+/// How long [`UnixListener::accept`] waits between checking for new connections.
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// A connection to a Unix-domain socket, along with whether it reads [`Bytes`](super::Bytes)
+/// instead of [`Text`].
+///
+/// Like [`Tcp`](super::Tcp), the last field is a clone of the stream that's used for things which
+/// shouldn't wait on a read in another thread to finish.
+#[derive(Debug, Clone)]
+pub struct UnixSocket(Arc<Mutex<UnixStream>>, bool, Arc<UnixStream>);
+
+/// Converts the address of a Unix-domain socket to its path, or [`Null`] if it doesn't have one.
+fn addr_to_object(addr: net::SocketAddr) -> Object {
+	addr.as_pathname()
+		.map(|path| Text::from(path.display().to_string()).into())
+		.unwrap_or_default()
+}
+
+impl UnixSocket {
+	/// Connect to the socket at `path`.
+	#[inline]
+	pub fn connect<P: AsRef<Path>>(path: P, binary: bool) -> io::Result<Self> {
+		Self::from_stream(UnixStream::connect(path)?, binary)
+	}
+
+	/// Wraps an already-connected stream.
+	pub fn from_stream(stream: UnixStream, binary: bool) -> io::Result<Self> {
+		let handle = stream.try_clone()?;
+
+		Ok(Self(Arc::new(Mutex::new(stream)), binary, Arc::new(handle)))
+	}
+
+	/// Create a pair of sockets that are connected to each other.
+	pub fn pair(binary: bool) -> io::Result<(Self, Self)> {
+		let (lhs, rhs) = UnixStream::pair()?;
+
+		Ok((Self::from_stream(lhs, binary)?, Self::from_stream(rhs, binary)?))
+	}
+
+	/// Checks to see if the connection is in binary mode.
+	#[inline]
+	pub fn is_binary(&self) -> bool {
+		self.1
+	}
+}
+
+/// Quest methods
+impl UnixSocket {
+	/// Connect to the socket at the path given by the first argument.
+	///
+	/// # Arguments
+	/// 1. (required, `@text`) The path of the socket.
+	/// 2. (optional, `@text`) The mode; `"b"` reads [`Bytes`](super::Bytes) instead of [`Text`].
+	#[instrument(name="UnixSocket::()", level="trace")]
+	pub fn qs_call(_: &Object, args: Args) -> Result<Object> {
+		let binary = super::tcp::parse_mode(args.arg(1))?;
+		let path = args.try_arg(0)?.call_downcast::<Text>()?;
+
+		Ok(Self::connect(path.as_ref(), binary)?.into())
+	}
+
+	/// Create a pair of connected sockets, returned as a [`List`](super::List).
+	///
+	/// # Arguments
+	/// 1. (optional, `@text`) The mode; `"b"` reads [`Bytes`](super::Bytes) instead of [`Text`].
+	///
+	/// # Quest Examples
+	/// ```quest
+	/// $pair = UnixSocket.pair();
+	/// pair.get(0).write("hi");
+	/// assert(pair.get(1).read(2) == "hi");
+	/// ```
+	#[instrument(name="UnixSocket::pair", level="trace")]
+	pub fn qs_pair(_: &Object, args: Args) -> Result<Object> {
+		let (lhs, rhs) = Self::pair(super::tcp::parse_mode(args.arg(0))?)?;
+
+		Ok(vec![lhs.into(), rhs.into()].into())
+	}
+
+	/// Listen for connections at the path given by the first argument.
+	///
+	/// This is identical to calling [`UnixListener`].
+	#[instrument(name="UnixSocket::listen", level="trace")]
+	pub fn qs_listen(this: &Object, args: Args) -> Result<Object> {
+		UnixListener::qs_call(this, args)
+	}
+
+	/// Read from `this`: either the amount of bytes given, or until the connection is closed.
+	///
+	/// In binary mode, [`Bytes`](super::Bytes) are returned. Otherwise, the data is returned as [`Text`], and must
+	/// be valid UTF-8.
+	///
+	/// # Arguments
+	/// 1. (optional, `@num`) The amount of bytes to read.
+	#[instrument(name="UnixSocket::read", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_read(this: &Object, args: Args) -> Result<Object> {
+		let this = this.try_downcast::<Self>()?.clone();
+		let bytes = read_from(&mut *this.0.lock().unwrap(), args.arg(0))?;
+
+		if this.is_binary() {
+			Ok(bytes.into())
+		} else {
+			Ok(Text::try_from(&bytes)?.into())
+		}
+	}
+
+	/// Write to `this`, returning the amount of bytes written.
+	///
+	/// # Arguments
+	/// 1. (required, [`Bytes`](super::Bytes) or `@text`) The data to write.
+	#[instrument(name="UnixSocket::write", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_write(this: &Object, args: Args) -> Result<Object> {
+		let data = to_write(args.try_arg(0)?)?;
+
+		// we write through the handle so that writing isn't blocked by a read in another thread.
+		(&*this.try_downcast::<Self>()?.2).write_all(&data)?;
+
+		Ok(data.len().into())
+	}
+
+	/// Shut down both halves of the connection.
+	#[instrument(name="UnixSocket::shutdown", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_shutdown(this: &Object, _: Args) -> Result<Object> {
+		match this.try_downcast::<Self>()?.2.shutdown(std::net::Shutdown::Both) {
+			Ok(()) => Ok(this.clone()),
+			Err(err) if err.kind() == io::ErrorKind::NotConnected => Ok(this.clone()),
+			Err(err) => Err(err.into())
+		}
+	}
+
+	/// Get the path of the local end of the connection, or [`Null`] if it's unnamed.
+	#[instrument(name="UnixSocket::local_addr", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_local_addr(this: &Object, _: Args) -> Result<Object> {
+		Ok(addr_to_object(this.try_downcast::<Self>()?.2.local_addr()?))
+	}
+
+	/// Get the path of the remote end of the connection, or [`Null`] if it's unnamed.
+	#[instrument(name="UnixSocket::peer_addr", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_peer_addr(this: &Object, _: Args) -> Result<Object> {
+		Ok(addr_to_object(this.try_downcast::<Self>()?.2.peer_addr()?))
+	}
+
+	/// Checks to see if `this` is in binary mode.
+	#[instrument(name="UnixSocket::binary?", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_binary_q(this: &Object, _: Args) -> Result<Object> {
+		Ok(this.try_downcast::<Self>()?.is_binary().into())
+	}
+}
+
+impl_object_type!{
+for UnixSocket [(parents super::Basic)]:
+	"()" => method Self::qs_call,
+	"pair" => method Self::qs_pair,
+	"listen" => method Self::qs_listen,
+	"read" => method Self::qs_read,
+	"write" => method Self::qs_write,
+	"shutdown" => method Self::qs_shutdown,
+	"local_addr" => method Self::qs_local_addr,
+	"peer_addr" => method Self::qs_peer_addr,
+	"binary?" => method Self::qs_binary_q,
+}
+
+/// A Unix-domain socket that listens for connections.
+///
+/// This works just like [`TcpServer`](super::TcpServer), except that shutting it down also removes
+/// the socket's file.
+#[derive(Debug, Clone)]
+pub struct UnixListener(Arc<Inner>);
+
+#[derive(Debug)]
+struct Inner {
+	// this is `None` once the listener has been shut down.
+	listener: parking_lot::Mutex<Option<net::UnixListener>>,
+	path: PathBuf,
+	binary: bool
+}
+
+impl UnixListener {
+	/// Start listening at `path`, which mustn't exist already.
+	pub fn bind<P: AsRef<Path>>(path: P, binary: bool) -> io::Result<Self> {
+		let listener = net::UnixListener::bind(&path)?;
+		listener.set_nonblocking(true)?;
+
+		Ok(Self(Arc::new(Inner {
+			listener: parking_lot::Mutex::new(Some(listener)),
+			path: path.as_ref().to_path_buf(),
+			binary
+		})))
+	}
+
+	/// Get the path the listener is bound to.
+	#[inline]
+	pub fn path(&self) -> &Path {
+		&self.0.path
+	}
+
+	/// Checks to see if the listener has been shut down.
+	#[inline]
+	pub fn is_closed(&self) -> bool {
+		self.0.listener.lock().is_none()
+	}
+
+	/// Stop listening for connections, and remove the socket's file.
+	pub fn shutdown(&self) -> io::Result<()> {
+		if self.0.listener.lock().take().is_some() {
+			std::fs::remove_file(&self.0.path)?;
+		}
+
+		Ok(())
+	}
+
+	/// Wait for a connection, giving up after `timeout` if it's given.
+	///
+	/// `None` is returned if the timeout expires or the listener is shut down.
+	pub fn accept(&self, timeout: Option<Duration>) -> io::Result<Option<UnixSocket>> {
+		let deadline = timeout.map(|timeout| Instant::now() + timeout);
+
+		loop {
+			match self.0.listener.lock().as_ref().map(net::UnixListener::accept) {
+				None => return Ok(None),
+				Some(Ok((stream, _))) => {
+					stream.set_nonblocking(false)?;
+					return UnixSocket::from_stream(stream, self.0.binary).map(Some);
+				},
+				Some(Err(err)) if err.kind() == io::ErrorKind::WouldBlock => {},
+				Some(Err(err)) => return Err(err)
+			}
+
+			if matches!(deadline, Some(deadline) if deadline <= Instant::now()) {
+				return Ok(None);
+			}
+
+			std::thread::sleep(POLL_INTERVAL);
+		}
+	}
+}
+
+/// Quest methods
+impl UnixListener {
+	/// Listen for connections at the path given by the first argument.
+	///
+	/// # Arguments
+	/// 1. (required, `@text`) The path of the socket, which mustn't exist already.
+	/// 2. (optional, `@text`) The mode; `"b"` means accepted connections read [`Bytes`](
+	///    super::Bytes) instead of [`Text`].
+	#[instrument(name="UnixListener::()", level="trace")]
+	pub fn qs_call(_: &Object, args: Args) -> Result<Object> {
+		let binary = super::tcp::parse_mode(args.arg(1))?;
+		let path = args.try_arg(0)?.call_downcast::<Text>()?;
+
+		Ok(Self::bind(path.as_ref(), binary)?.into())
+	}
+
+	/// Wait for a connection, returning it as a [`UnixSocket`].
+	///
+	/// [`Null`] is returned if the timeout expires, or if the listener is shut down.
+	///
+	/// # Arguments
+	/// 1. (optional, [`Duration`](super::Duration) or `@num`) How long to wait; numbers are in
+	///    seconds. If omitted, waits until a connection is made.
+	#[instrument(name="UnixListener::accept", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_accept(this: &Object, args: Args) -> Result<Object> {
+		let timeout = args.arg(0).map(super::duration::to_std).transpose()?;
+		let this = this.try_downcast::<Self>()?.clone();
+
+		match this.accept(timeout)? {
+			Some(socket) => Ok(socket.into()),
+			None => Ok(Null.into())
+		}
+	}
+
+	/// Accept connections until the listener is shut down, calling the first argument with each one.
+	///
+	/// # Arguments
+	/// 1. (required, callable) The block to call with each [`UnixSocket`] connection.
+	#[instrument(name="UnixListener::each", level="trace", skip(this, args), fields(self=?this, ?args))]
+	pub fn qs_each(this: &Object, args: Args) -> Result<Object> {
+		let block = args.try_arg(0)?;
+		let listener = this.try_downcast::<Self>()?.clone();
+
+		while let Some(socket) = listener.accept(None)? {
+			block.call_attr_lit(&Literal::CALL, &[&socket.into()])?;
+		}
+
+		Ok(this.clone())
+	}
+
+	/// Get the path `this` is listening at.
+	#[instrument(name="UnixListener::local_addr", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_local_addr(this: &Object, _: Args) -> Result<Object> {
+		Ok(Text::from(this.try_downcast::<Self>()?.path().display().to_string()).into())
+	}
+
+	/// Stop listening for connections and remove the socket's file.
+	#[instrument(name="UnixListener::shutdown", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_shutdown(this: &Object, _: Args) -> Result<Object> {
+		this.try_downcast::<Self>()?.shutdown()?;
+
+		Ok(this.clone())
+	}
+
+	/// Checks to see if `this` has been shut down.
+	#[instrument(name="UnixListener::closed?", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_closed_q(this: &Object, _: Args) -> Result<Object> {
+		Ok(this.try_downcast::<Self>()?.is_closed().into())
+	}
+
+	/// Checks to see if accepted connections are in binary mode.
+	#[instrument(name="UnixListener::binary?", level="trace", skip(this), fields(self=?this))]
+	pub fn qs_binary_q(this: &Object, _: Args) -> Result<Object> {
+		Ok(this.try_downcast::<Self>()?.0.binary.into())
+	}
+}
+
+impl_object_type!{
+for UnixListener [(parents super::Basic)]:
+	"()" => method Self::qs_call,
+	"accept" => method Self::qs_accept,
+	"each" => method Self::qs_each,
+	"local_addr" => method Self::qs_local_addr,
+	"shutdown" => method Self::qs_shutdown,
+	"closed?" => method Self::qs_closed_q,
+	"binary?" => method Self::qs_binary_q,
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::types::Bytes;
+
+	#[test]
+	fn listen_and_connect() {
+		let path = std::env::temp_dir().join(format!("quest-unix-socket-test-{}", std::process::id()));
+		let _ = std::fs::remove_file(&path);
+
+		let listener = UnixListener::bind(&path, true).unwrap();
+		assert!(listener.accept(Some(Duration::from_millis(20))).unwrap().is_none());
+
+		let client = UnixSocket::connect(&path, false).unwrap();
+		let conn = listener.accept(Some(Duration::from_secs(5))).unwrap().expect("no connection");
+		assert!(conn.is_binary());
+
+		(&*client.2).write_all(b"\xff\x00").unwrap();
+		client.2.shutdown(std::net::Shutdown::Write).unwrap();
+		assert_eq!(read_from(&mut *conn.0.lock().unwrap(), None).unwrap(), Bytes::from(vec![0xff, 0]));
+
+		listener.shutdown().unwrap();
+		assert!(listener.is_closed());
+		assert!(!path.exists());
+	}
+}
@@ -7,8 +7,9 @@ use crate::stream::{Context, Contexted};
 use crate::expression::{Constructable, Expression, PutBack, Executable};
 use std::fmt::{self, Debug, Display, Formatter};
 
+/// A single line within a [`Block`].
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub(crate) enum Line {
+pub enum Line {
 	Single(Expression),
 	Multiple(Vec<Expression>)
 }
@@ -174,6 +175,18 @@ impl Block {
 		self.paren_type
 	}
 
+	#[must_use]
+	#[inline]
+	pub fn context(&self) -> &Context {
+		&self.context
+	}
+
+	#[must_use]
+	#[inline]
+	pub fn lines(&self) -> &[Line] {
+		&self.lines
+	}
+
 	#[tracing::instrument(name="Block::run_block")]
 	pub(super) fn run_block(&self) -> quest_core::Result<Option<LineResult>> {
 		if let Some((last, rest)) = self.lines.split_last() {
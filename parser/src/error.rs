@@ -36,34 +36,16 @@ impl Error {
 	pub fn new(context: Context, r#type: ErrorType) -> Self {
 		Error { context, r#type }
 	}
+
+	/// Get the type of error that occurred.
+	pub fn error_type(&self) -> &ErrorType {
+		&self.r#type
+	}
 }
 
 impl Display for Error {
 	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-		const TAB_REPLACEMENT: &str = "  ";
-
-		let Context { ref file, lineno, mut column, ref line } = self.context;
-		let file = file.as_ref()
-			.map(|x| x.to_string_lossy().to_owned().to_string())
-			.unwrap_or_else(|| "<eval>".to_string());
-
-		// replace tabs with a standardized representation for error messages
-		let mut line = line.clone();
-		while let Some(tab_pos) = line.find('\t') {
-			line.replace_range(tab_pos..=tab_pos, TAB_REPLACEMENT);
-			column += TAB_REPLACEMENT.len() - 1;
-		}
-
-		write!(f, concat!("{file}:{lineno}:{column}: parse error, {error}",
-					 "\n    |",
-					 "\n {lineno:<3}| {context}",
-					 "\n    |{padding}^ here"),
-			file=file,
-			lineno=lineno,
-			column=column,
-			error=self.r#type,
-			context=line.trim_end(),
-			padding=" ".repeat(column))
+		self.context.write_snippet(f, format_args!("parse error, {}", self.r#type))
 	}
 }
 
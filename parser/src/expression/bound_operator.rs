@@ -4,8 +4,9 @@ use crate::stream::Contexted;
 use crate::Result;
 use std::fmt::{self, Display, Formatter};
 
+/// The arguments that are passed to a [`BoundOperator`].
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub(crate) enum OperArgs {
+pub enum OperArgs {
 	Unary,
 	Binary(Expression),
 	Ternary(Expression, Expression)
@@ -43,6 +44,23 @@ impl Display for BoundOperator {
 }
 
 impl BoundOperator {
+	#[must_use]
+	#[inline]
+	pub fn oper(&self) -> Operator {
+		self.oper
+	}
+
+	#[must_use]
+	#[inline]
+	pub fn this(&self) -> &Expression {
+		&self.this
+	}
+
+	#[must_use]
+	#[inline]
+	pub fn args(&self) -> &OperArgs {
+		&self.args
+	}
 }
 impl Executable for BoundOperator {
 
@@ -0,0 +1,360 @@
+//! Support for importing other Quest files as modules.
+//!
+//! Each module is evaluated once, in its own [`Scope`], and the resulting scope is cached so that
+//! subsequent imports of the same file return the same object.
+
+use crate::{Block, Expression, Stream};
+use crate::expression::Executable;
+use crate::stream::BufStream;
+use quest_core::{Object, Args, Binding, Error};
+use quest_core::types::{Text, Boolean, Scope, List};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::cell::RefCell;
+use std::convert::TryFrom;
+use std::sync::{Mutex, Condvar};
+use std::thread::{self, ThreadId};
+use lazy_static::lazy_static;
+
+/// The environment variable containing additional directories to search for modules.
+pub const QUEST_PATH: &str = "QUEST_PATH";
+
+/// The extension that's tried if a module can't be found as-is.
+const EXTENSION: &str = "qs";
+
+/// The state of a module within [`MODULES`].
+enum Module {
+	/// The module is currently being loaded by the given thread.
+	Loading(ThreadId),
+	/// The module has been loaded.
+	Loaded(Object)
+}
+
+#[derive(Default)]
+struct Modules {
+	modules: HashMap<PathBuf, Module>,
+	/// The module each thread is waiting on another thread to finish loading.
+	waiting: HashMap<ThreadId, PathBuf>
+}
+
+impl Modules {
+	/// Checks to see if waiting for `path` to be loaded would end up waiting on the current thread.
+	fn would_deadlock<'a>(&'a self, mut path: &'a Path) -> bool {
+		let current = thread::current().id();
+
+		while let Some(Module::Loading(loader)) = self.modules.get(path) {
+			if *loader == current {
+				return true;
+			}
+
+			match self.waiting.get(loader) {
+				Some(next) => path = next,
+				None => return false
+			}
+		}
+
+		false
+	}
+}
+
+lazy_static! {
+	static ref MODULES: Mutex<Modules> = Mutex::new(Modules::default());
+
+	/// Notified whenever a module in [`MODULES`] finishes loading, successfully or not.
+	static ref MODULE_LOADED: Condvar = Condvar::new();
+}
+
+thread_local! {
+	/// The modules that are currently being loaded by this thread, used to detect cycles.
+	static LOADING: RefCell<Vec<PathBuf>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Get the file that the currently executing code came from, if it came from one.
+///
+/// Stackframes for blocks have the block as their first parent, so we use the file that block
+/// was parsed from. Otherwise, we're at the top level of a file, which has its `__file__` set.
+fn importing_file() -> Option<PathBuf> {
+	let binding = Binding::instance();
+
+	let block = binding.get_attr_lit("__parents__").ok()
+		.and_then(|parents| parents.downcast::<List>().and_then(|parents| parents.iter().next().cloned()))
+		.and_then(|parent| parent.downcast::<Block>().map(|block| block.context.file.clone()));
+
+	if let Some(file) = block {
+		return file;
+	}
+
+	binding.get_attr_lit("__file__").ok()
+		.and_then(|file| file.downcast::<Text>().map(|file| PathBuf::from(file.as_ref())))
+}
+
+/// Checks to see if `path` is a file, and if it isn't, whether `path` with the extension is.
+fn try_file(path: PathBuf) -> Option<PathBuf> {
+	if path.is_file() {
+		return Some(path);
+	}
+
+	let mut with_extension = path.into_os_string();
+	with_extension.push(".");
+	with_extension.push(EXTENSION);
+
+	Some(PathBuf::from(with_extension)).filter(|path| path.is_file())
+}
+
+/// Resolve `name` into the path of a module.
+///
+/// Names starting with `.` or `..` (and absolute paths) are only resolved relative to the
+/// importing file's directory. All other names are searched for in the importing file's
+/// directory, and then each directory within [`QUEST_PATH`].
+fn resolve(name: &str) -> quest_core::Result<PathBuf> {
+	use std::path::Component;
+
+	let path = Path::new(name);
+	let dir = importing_file()
+		.and_then(|file| file.parent().map(Path::to_path_buf))
+		.unwrap_or_default();
+
+	let mut searched = vec![dir.clone()];
+
+	let is_relative = matches!(path.components().next(),
+		Some(Component::CurDir) | Some(Component::ParentDir) | Some(Component::RootDir));
+
+	if !is_relative {
+		if let Some(quest_path) = std::env::var_os(QUEST_PATH) {
+			searched.extend(std::env::split_paths(&quest_path));
+		}
+	}
+
+	searched.iter()
+		.find_map(|dir| try_file(dir.join(path)))
+		.map(|path| path.canonicalize().unwrap_or(path))
+		.ok_or_else(|| Error::Messaged(format!("unable to find module {:?} (searched: {:?})", name, searched)))
+}
+
+/// Removes the top module from [`LOADING`] when dropped.
+struct LoadingGuard;
+
+impl Drop for LoadingGuard {
+	fn drop(&mut self) {
+		LOADING.with(|loading| loading.borrow_mut().pop());
+	}
+}
+
+/// Removes `path` from [`MODULES`] if it's still being loaded when dropped (ie loading it failed),
+/// and wakes up anyone waiting on it.
+struct ModuleGuard<'a>(&'a Path);
+
+impl Drop for ModuleGuard<'_> {
+	fn drop(&mut self) {
+		let mut modules = MODULES.lock().unwrap_or_else(|err| err.into_inner());
+
+		if let Some(Module::Loading(_)) = modules.modules.get(self.0) {
+			modules.modules.remove(self.0);
+		}
+
+		MODULE_LOADED.notify_all();
+	}
+}
+
+/// Checks to see if importing `path` from the current thread would cause an import cycle.
+fn check_cycle(path: &Path) -> quest_core::Result<()> {
+	let cycle = LOADING.with(|loading| {
+		let loading = loading.borrow();
+		let start = loading.iter().position(|loaded| loaded == path)?;
+		let mut cycle = loading[start..].to_vec();
+		cycle.push(path.to_path_buf());
+		Some(cycle)
+	});
+
+	match cycle {
+		Some(cycle) => Err(Error::Messaged(format!("import cycle detected: {}",
+			cycle.iter().map(|path| path.display().to_string()).collect::<Vec<_>>().join(" -> ")))),
+		None => Ok(())
+	}
+}
+
+/// Evaluate the file at `path` in a brand new [`Scope`], returning the scope.
+fn load(path: &Path) -> quest_core::Result<Object> {
+	LOADING.with(|loading| loading.borrow_mut().push(path.to_path_buf()));
+	let _guard = LoadingGuard;
+
+	let stream = BufStream::try_from(path).map_err(|err| Box::new(err) as Box<_>)?;
+	let expression = Expression::parse_stream(stream.tokens()).map_err(|err| Box::new(err) as Box<_>)?;
+
+	let module = Object::new(Scope);
+	module.set_attr_lit("name", path.file_stem().unwrap_or_default().to_string_lossy().into_owned().into())?;
+	module.set_attr_lit("__file__", path.display().to_string().into())?;
+
+	Binding::run_stackframe(module.clone().into(), |_| expression.execute())?;
+
+	Ok(module)
+}
+
+/// Import the module `name`, returning it and whether it was newly loaded.
+///
+/// If another thread is currently loading the module, this waits for it to finish instead of
+/// loading it a second time.
+pub fn import(name: &str) -> quest_core::Result<(Object, bool)> {
+	let path = resolve(name)?;
+	check_cycle(&path)?;
+
+	let current = thread::current().id();
+	let mut modules = MODULES.lock().unwrap();
+
+	loop {
+		match modules.modules.get(&path) {
+			Some(Module::Loaded(module)) => return Ok((module.clone(), false)),
+			Some(Module::Loading(_)) if modules.would_deadlock(&path) =>
+				return Err(Error::Messaged(format!(
+					"import cycle detected: {} is being loaded by a thread waiting on this one", path.display()))),
+			Some(Module::Loading(_)) => {
+				modules.waiting.insert(current, path.clone());
+				modules = MODULE_LOADED.wait(modules).unwrap();
+				modules.waiting.remove(&current);
+			},
+			None => break
+		}
+	}
+
+	modules.modules.insert(path.clone(), Module::Loading(current));
+	drop(modules);
+
+	let _guard = ModuleGuard(&path);
+	let module = load(&path)?;
+
+	MODULES.lock().unwrap().modules.insert(path.clone(), Module::Loaded(module.clone()));
+
+	Ok((module, true))
+}
+
+/// Imports the module named by the first argument, returning the module.
+///
+/// See [`import`] for details on how modules are resolved.
+pub fn qs_import(args: Args) -> quest_core::Result<Object> {
+	let name = args.try_arg(0)?.call_downcast::<Text>()?.to_string();
+
+	import(&name).map(|(module, _)| module)
+}
+
+/// Imports the module named by the first argument, returning whether it was newly loaded.
+pub fn qs_require(args: Args) -> quest_core::Result<Object> {
+	let name = args.try_arg(0)?.call_downcast::<Text>()?.to_string();
+
+	import(&name).map(|(_, loaded)| Boolean::from(loaded).into())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::fs;
+	use std::sync::atomic::{AtomicUsize, Ordering};
+	use quest_core::types::{ObjectType, RustFn, Kernel};
+
+	fn init() {
+		quest_core::init();
+		crate::init();
+	}
+
+	/// Run `func` with the current file set to `file`, as if it were called at the top level.
+	fn within(file: &Path, func: impl FnOnce() -> quest_core::Result<Object>) -> quest_core::Result<Object> {
+		let scope = Object::new(Scope);
+		scope.set_attr_lit("__file__", file.display().to_string().into())?;
+		Binding::run_stackframe(scope.into(), |_| func())
+	}
+
+	#[test]
+	fn resolution() {
+		init();
+
+		let dir = tempfile::tempdir().unwrap();
+		let dir = dir.path().canonicalize().unwrap();
+		let lib = tempfile::tempdir().unwrap();
+		let lib = lib.path().canonicalize().unwrap();
+
+		fs::write(dir.join("sibling.qs"), "").unwrap();
+		fs::write(dir.join("noext"), "").unwrap();
+		fs::write(lib.join("library.qs"), "").unwrap();
+
+		within(&dir.join("main.qs"), || {
+			assert_eq!(resolve("sibling")?, dir.join("sibling.qs"));
+			assert_eq!(resolve("sibling.qs")?, dir.join("sibling.qs"));
+			assert_eq!(resolve("./sibling")?, dir.join("sibling.qs"));
+			assert_eq!(resolve("noext")?, dir.join("noext"));
+			assert!(resolve("library").is_err());
+
+			std::env::set_var(QUEST_PATH, &lib);
+			assert_eq!(resolve("library")?, lib.join("library.qs"));
+			// relative names aren't searched for in `QUEST_PATH`.
+			assert!(resolve("./library").is_err());
+			std::env::remove_var(QUEST_PATH);
+
+			Ok(Object::default())
+		}).unwrap();
+	}
+
+	#[test]
+	fn cycles() {
+		init();
+
+		let dir = tempfile::tempdir().unwrap();
+		let dir = dir.path().canonicalize().unwrap();
+		fs::write(dir.join("a.qs"), "import('./b');").unwrap();
+		fs::write(dir.join("b.qs"), "import('./a');").unwrap();
+
+		let err = import(&dir.join("a.qs").display().to_string()).unwrap_err();
+		assert!(err.to_string().contains("import cycle detected"), "bad error: {}", err);
+
+		// failed imports aren't cached.
+		fs::write(dir.join("b.qs"), "").unwrap();
+		assert!(import(&dir.join("a.qs").display().to_string()).unwrap().1);
+	}
+
+	#[test]
+	fn concurrent_imports() {
+		static LOADS: AtomicUsize = AtomicUsize::new(0);
+
+		init();
+		Kernel::mapping().set_value_lit("__test_count_load", RustFn::function("__test_count_load", |_| {
+			LOADS.fetch_add(1, Ordering::SeqCst);
+			Ok(Object::default())
+		})).unwrap();
+
+		let dir = tempfile::tempdir().unwrap();
+		let path = dir.path().canonicalize().unwrap().join("slow.qs").display().to_string();
+		fs::write(&path, "__test_count_load(); sleep(0.1); x = 1;").unwrap();
+
+		let threads = (0..4)
+			.map(|_| {
+				let path = path.clone();
+				thread::spawn(move || import(&path).map(|(module, _)| module.id()).map_err(|err| err.to_string()))
+			})
+			.collect::<Vec<_>>();
+
+		let ids = threads.into_iter().map(|thread| thread.join().unwrap().unwrap()).collect::<Vec<_>>();
+
+		assert!(ids.iter().all(|&id| id == ids[0]));
+		assert_eq!(LOADS.load(Ordering::SeqCst), 1);
+	}
+
+	#[test]
+	fn cross_thread_cycles() {
+		init();
+
+		let dir = tempfile::tempdir().unwrap();
+		let dir = dir.path().canonicalize().unwrap();
+		fs::write(dir.join("left.qs"), "sleep(0.1); import('./right');").unwrap();
+		fs::write(dir.join("right.qs"), "sleep(0.1); import('./left');").unwrap();
+
+		let threads = ["left.qs", "right.qs"].iter()
+			.map(|name| {
+				let path = dir.join(name).display().to_string();
+				thread::spawn(move || import(&path).map(|_| ()).map_err(|err| err.to_string()))
+			})
+			.collect::<Vec<_>>();
+
+		for thread in threads {
+			let err = thread.join().unwrap().unwrap_err();
+			assert!(err.contains("import cycle detected"), "bad error: {}", err);
+		}
+	}
+}
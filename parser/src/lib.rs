@@ -26,6 +26,12 @@ pub fn init() {
 					.execute()
 			})
 		})).expect("couldn't define `eval`");
+
+		Kernel::mapping().set_value_lit("import", RustFn::function("Kernel::import", import::qs_import))
+			.expect("couldn't define `import`");
+
+		Kernel::mapping().set_value_lit("require", RustFn::function("Kernel::require", import::qs_require))
+			.expect("couldn't define `require`");
 	});
 }
 
@@ -36,6 +42,7 @@ pub mod expression;
 pub mod token;
 pub mod stream;
 pub mod block;
+pub mod import;
 
 // TODO: change public exports to more minimal.
 pub use block::Block;
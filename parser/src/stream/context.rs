@@ -1,4 +1,5 @@
 use std::path::PathBuf;
+use std::fmt::{self, Display, Formatter};
 
 /// Types implementing this trait should be able to supply a "current execution context".
 /// 
@@ -27,6 +28,35 @@ impl Context {
 	pub const fn new(file: Option<PathBuf>) -> Self {
 		Self { file, lineno: 0, column: 0, line: String::new() }
 	}
+
+	/// Writes `message`, prefixed by the location of `self`, followed by a snippet of the line
+	/// with a caret pointing at the column.
+	pub fn write_snippet(&self, f: &mut Formatter, message: impl Display) -> fmt::Result {
+		const TAB_REPLACEMENT: &str = "  ";
+
+		let Self { ref file, lineno, mut column, ref line } = *self;
+		let file = file.as_ref()
+			.map(|x| x.to_string_lossy().to_string())
+			.unwrap_or_else(|| "<eval>".to_string());
+
+		// replace tabs with a standardized representation for error messages
+		let mut line = line.clone();
+		while let Some(tab_pos) = line.find('\t') {
+			line.replace_range(tab_pos..=tab_pos, TAB_REPLACEMENT);
+			column += TAB_REPLACEMENT.len() - 1;
+		}
+
+		write!(f, concat!("{file}:{lineno}:{column}: {message}",
+					 "\n    |",
+					 "\n {lineno:<3}| {context}",
+					 "\n    |{padding}^ here"),
+			file=file,
+			lineno=lineno,
+			column=column,
+			message=message,
+			context=line.trim_end(),
+			padding=" ".repeat(column))
+	}
 }
 
 impl<T: Into<PathBuf>> From<T> for Context {
@@ -63,4 +93,25 @@ mod tests {
 			Context::from("/plato/meno.txt"),
 			Context { file: Some("/plato/meno.txt".into()), ..Context::default() });
 	}
-}
\ No newline at end of file
+
+	#[test]
+	fn write_snippet() {
+		struct Snippet(Context);
+		impl Display for Snippet {
+			fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+				self.0.write_snippet(f, "oops")
+			}
+		}
+
+		let context = Context {
+			file: Some("/plato/apology.txt".into()),
+			lineno: 3,
+			column: 2,
+			line: "\tab".into()
+		};
+
+		assert_eq!(
+			Snippet(context).to_string(),
+			"/plato/apology.txt:3:3: oops\n    |\n 3  |   ab\n    |   ^ here");
+	}
+}
@@ -253,6 +253,12 @@ mod tests {
 			assert!(ttr(buf!("g"), 16).is_err());
 		}
 
+		#[test]
+		fn long() {
+			assert_eq!(num!(u64::MAX), ttr(buf!("ffff_ffff_ffff_ffff"), 16).unwrap());
+			assert_eq!(num!(1u128 << 100), ttr(buf!(format!("1{}", "0".repeat(100))), 2).unwrap());
+		}
+
 		#[test]
 		fn empty() {
 			assert!(ttr(buf!(""), 10).is_err());
@@ -284,6 +290,12 @@ mod tests {
 			assert_eq!(num!(1_234_567), ttb(buf!("1__2_34_56__7")).unwrap());
 		}
 
+		#[test]
+		fn long_integers() {
+			assert_eq!(num!(9_223_372_036_854_775_808u64), ttb(buf!("9223372036854775808")).unwrap());
+			assert_eq!(num!(u128::MAX), ttb(buf!("340_282_366_920_938_463_463_374_607_431_768_211_455")).unwrap());
+		}
+
 		#[test]
 		fn decimal() {
 			assert_eq!(num!(0), ttb(buf!("0.0")).unwrap());
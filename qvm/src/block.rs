@@ -0,0 +1,67 @@
+use crate::Code;
+use quest_core::{Object, Args, Binding, impl_object_type};
+use quest_parser::Block;
+use std::fmt::{self, Debug, Formatter};
+use std::sync::Arc;
+
+/// A [`Block`](quest_parser::Block) that's been compiled to [`Code`].
+#[derive(Clone)]
+pub struct CompiledBlock(Arc<Code>);
+
+impl Debug for CompiledBlock {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		match self.0.source() {
+			Some(source) => f.debug_tuple("CompiledBlock").field(source).finish(),
+			None => f.debug_tuple("CompiledBlock").finish()
+		}
+	}
+}
+
+impl CompiledBlock {
+	#[inline]
+	pub fn new(code: Arc<Code>) -> Self {
+		Self(code)
+	}
+
+	#[inline]
+	pub fn code(&self) -> &Code {
+		&self.0
+	}
+}
+
+impl CompiledBlock {
+	#[inline]
+	pub fn qs_call(this: &Object, args: Args) -> quest_core::Result<Object> {
+		let code = this.try_downcast::<Self>()?.0.clone();
+
+		Binding::new_stackframe(Some(this.clone()), args, move |binding| {
+			if let Some(source) = code.source().and_then(|source| source.downcast::<Block>()) {
+				binding.as_ref().set_attr_lit("source_location", format!("{:?}", source.context()).into())?;
+			}
+
+			crate::run(&code)
+		})
+	}
+
+	#[inline]
+	pub fn qs_at_text(this: &Object, _: Args) -> quest_core::Result<Object> {
+		match this.try_downcast::<Self>()?.0.source() {
+			Some(source) => source.call_attr_lit("@text", &[]),
+			None => Ok("<compiled block>".into())
+		}
+	}
+
+	#[inline]
+	pub fn qs_call_noscope(this: &Object, _: Args) -> quest_core::Result<Object> {
+		let code = this.try_downcast::<Self>()?.0.clone();
+
+		crate::run(&code)
+	}
+}
+
+impl_object_type!{
+for CompiledBlock [(parents quest_core::types::Function)]:
+	"@text" => method Self::qs_at_text,
+	"()" => method Self::qs_call,
+	"call_noscope" => method Self::qs_call_noscope,
+}
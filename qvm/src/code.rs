@@ -0,0 +1,61 @@
+use quest_core::Object;
+use quest_parser::{Block, Context};
+use quest_parser::token::Primitive;
+use std::sync::Arc;
+
+/// A single instruction, executed by [`run`](crate::run).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Op {
+	/// Push `null`.
+	Null,
+
+	/// Push the result of executing the primitive at the given index.
+	Primitive(usize),
+
+	/// Push a new block, whose parent is the current stackframe, for the code at the given index.
+	Block(usize),
+
+	/// Pop `argc` arguments and then the receiver, and push the result of calling `attr` on the
+	/// receiver with the arguments.
+	CallAttr {
+		/// The attribute to call.
+		attr: &'static str,
+		/// The amount of arguments to pass.
+		argc: usize
+	},
+
+	/// Pop the given amount of values, and push a list of them.
+	List(usize),
+
+	/// Discard the top value.
+	Pop,
+}
+
+/// A compiled [`Block`](quest_parser::Block) (or top-level expression).
+#[derive(Debug, Clone, Default)]
+pub struct Code {
+	pub(crate) ops: Vec<Op>,
+	pub(crate) primitives: Vec<Primitive>,
+	pub(crate) blocks: Vec<Arc<Code>>,
+	pub(crate) source: Option<Object>,
+}
+
+impl Code {
+	/// The instructions of this code.
+	#[inline]
+	pub fn ops(&self) -> &[Op] {
+		&self.ops
+	}
+
+	/// The [`Block`] this code was compiled from, if it came from one.
+	#[inline]
+	pub fn source(&self) -> Option<&Object> {
+		self.source.as_ref()
+	}
+
+	/// The context of the block this code was compiled from, if it came from one.
+	pub fn context(&self) -> Option<Context> {
+		self.source.as_ref()
+			.and_then(|source| source.downcast::<Block>().map(|block| block.context().clone()))
+	}
+}
@@ -0,0 +1,137 @@
+//! Compiling [`Expression`]s into [`Code`].
+use crate::{Code, Op};
+use quest_parser::{Block, Expression};
+use quest_parser::block::Line;
+use quest_parser::expression::{BoundOperator, OperArgs};
+use quest_parser::token::{Operator, ParenType};
+use std::sync::Arc;
+
+/// Compiles the top-level `expression` into [`Code`].
+pub fn compile(expression: &Expression) -> Code {
+	let mut code = Code::default();
+	Compiler(&mut code).expression(expression);
+	code
+}
+
+/// Compiles the body of `block`, as it'd be run when the block is called.
+pub fn compile_block(block: &Block) -> Code {
+	let mut code = Code { source: Some(block.clone().into()), ..Code::default() };
+	Compiler(&mut code).block_value(block);
+	code
+}
+
+struct Compiler<'a>(&'a mut Code);
+
+impl Compiler<'_> {
+	#[inline]
+	fn emit(&mut self, op: Op) {
+		self.0.ops.push(op);
+	}
+
+	fn expression(&mut self, expression: &Expression) {
+		match expression {
+			Expression::Primitive(prim) => {
+				self.0.primitives.push(prim.clone());
+				self.emit(Op::Primitive(self.0.primitives.len() - 1));
+			},
+			Expression::Block(block) if block.paren_type() == ParenType::Curly => {
+				self.0.blocks.push(Arc::new(compile_block(block)));
+				self.emit(Op::Block(self.0.blocks.len() - 1));
+			},
+			Expression::Block(block) => self.block_value(block),
+			Expression::Operator(oper) => self.operator(oper),
+			Expression::FunctionCall(this, block) => {
+				let attr = if block.paren_type() == ParenType::Square { "[]" } else { "()" };
+
+				self.expression(this);
+				let argc = self.block_args(block);
+				self.emit(Op::CallAttr { attr, argc });
+			}
+		}
+	}
+
+	fn operator(&mut self, oper: &BoundOperator) {
+		self.expression(oper.this());
+
+		let attr = oper.oper().repr();
+		let argc =
+			match oper.args() {
+				OperArgs::Binary(Expression::Block(block))
+					if oper.oper() == Operator::Call && block.paren_type() == ParenType::Round
+						=> self.block_args(block),
+				OperArgs::Unary => 0,
+				OperArgs::Binary(rhs) => {
+					self.expression(rhs);
+					1
+				},
+				OperArgs::Ternary(mid, rhs) => {
+					self.expression(mid);
+					self.expression(rhs);
+					2
+				}
+			};
+
+		self.emit(Op::CallAttr { attr, argc });
+	}
+
+	/// Compiles every line but the last of `block`, discarding their values, and returns the last
+	/// line (if any).
+	fn leading_lines<'b>(&mut self, block: &'b Block) -> Option<&'b Line> {
+		let (last, rest) = block.lines().split_last()?;
+
+		for line in rest {
+			match line {
+				Line::Single(expr) => {
+					self.expression(expr);
+					self.emit(Op::Pop);
+				},
+				Line::Multiple(exprs) => for expr in exprs {
+					self.expression(expr);
+					self.emit(Op::Pop);
+				}
+			}
+		}
+
+		Some(last)
+	}
+
+	/// Compiles `block` so that its last line is left on the stack as arguments, returning the
+	/// amount of arguments.
+	fn block_args(&mut self, block: &Block) -> usize {
+		match self.leading_lines(block) {
+			Some(Line::Single(expr)) => {
+				self.expression(expr);
+				1
+			},
+			Some(Line::Multiple(exprs)) => {
+				exprs.iter().for_each(|expr| self.expression(expr));
+				exprs.len()
+			},
+			None => 0
+		}
+	}
+
+	/// Compiles `block` so that its value is left on the stack.
+	///
+	/// Like when running a block, a single value is left as-is, multiple values are converted to a
+	/// list, and square blocks always result in a list.
+	fn block_value(&mut self, block: &Block) {
+		let is_square = block.paren_type() == ParenType::Square;
+
+		match self.leading_lines(block) {
+			Some(Line::Single(expr)) => {
+				self.expression(expr);
+
+				if is_square {
+					self.emit(Op::List(1));
+				}
+			},
+			Some(Line::Multiple(exprs)) => {
+				exprs.iter().for_each(|expr| self.expression(expr));
+				self.emit(Op::List(exprs.len()));
+			},
+			None if is_square => self.emit(Op::List(0)),
+			None => self.emit(Op::Null)
+		}
+	}
+}
@@ -0,0 +1,101 @@
+//! A bytecode compiler and stack-based virtual machine for Quest.
+//!
+//! Instead of walking the [`Expression`](quest_parser::Expression) tree every time a block is
+//! called, each block is compiled once into a flat list of [`Op`]s, which are then run on a stack.
+//! Operators are still dispatched by calling attributes, so Quest's semantics are unchanged.
+
+mod code;
+mod compile;
+mod vm;
+mod block;
+
+pub use code::{Code, Op};
+pub use compile::{compile, compile_block};
+pub use vm::run;
+pub use block::CompiledBlock;
+
+/// Setup the vm. This should be run after [`quest_parser::init`], and before anything within `qvm`
+/// is used.
+pub fn init() {
+	use quest_core::types::ObjectType;
+	use std::sync::Once;
+
+	static INITIALIZE: Once = Once::new();
+
+	INITIALIZE.call_once(|| {
+		CompiledBlock::initialize().expect("couldn't initialize compiled block");
+	});
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use quest_parser::{Expression, Stream};
+	use quest_parser::expression::Executable;
+	use quest_parser::stream::BufStream;
+
+	fn parse(source: &str) -> Expression {
+		Expression::parse_stream(BufStream::from(source.to_string()).tokens()).unwrap()
+	}
+
+	fn setup() {
+		quest_core::init();
+		quest_parser::init();
+		init();
+	}
+
+	fn assert_same(source: &str) {
+		setup();
+
+		let expression = parse(source);
+		let walked = expression.execute().unwrap();
+		let compiled = run(&compile(&expression)).unwrap();
+
+		assert!(walked.eq_obj(&compiled).unwrap(), "{:?}: {:?} != {:?}", source, walked, compiled);
+	}
+
+	#[test]
+	fn matches_tree_walker() {
+		assert_same("1 + 2 * 3");
+		assert_same("[1, 2, (3, 4)]");
+		assert_same("x = 3; x += 4; x");
+		assert_same("{ _0 * 2 }(4)");
+		assert_same("fib = { (_0 <= 1).then(_0.itself).else({ fib(_0 - 1) + fib(_0 - 2) }) }; fib(10)");
+		assert_same("()");
+	}
+
+	#[test]
+	fn control_flow() {
+		assert_same("i = 0; while({ i < 5 }, { i += 1 }); i");
+		assert_same("if(true, { 1 }, { 2 })");
+		assert_same("if(false, { 1 }, { 2 })");
+		assert_same("{ 3.return(); 4 }()");
+		assert_same("[1, 2, 3, 4].map({ (_0 == 2).then('two'.return); _0 }).@list()");
+		assert_same("{ { 1.return(:1) }(); 2 }()");
+	}
+
+	#[test]
+	fn closures() {
+		assert_same("{ n = _0; { n * 2 } }(21)()");
+		assert_same("make = { x = _0; { x += 1 } }; counter = make(10); counter(); counter()");
+		assert_same("add = { x = _0; { x + _0 } }; [add(1)(2), add(10)(20)]");
+		assert_same("{ source_location }()");
+	}
+
+	#[test]
+	fn errors() {
+		setup();
+
+		for source in &["oops", "{ { 1 + oops }() }()", "{ 1.nope() }()", "{ [1].fetch(2) }()"] {
+			let expression = parse(source);
+			let walked = expression.execute().unwrap_err();
+			let compiled = run(&compile(&expression)).unwrap_err();
+
+			// messages can contain object ids, so we compare the stacks and kinds instead.
+			assert_eq!(walked.backtrace().map(<[_]>::len), compiled.backtrace().map(<[_]>::len), "{:?}", source);
+			assert_same(&format!("catch({{ {} }}).kind()", source));
+		}
+
+		assert_same("catch({ { throw('inner') }() }).@text()");
+	}
+}
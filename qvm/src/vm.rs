@@ -0,0 +1,37 @@
+//! Running compiled [`Code`].
+use crate::{Code, Op, CompiledBlock};
+use quest_core::{Object, Args, Binding};
+use quest_parser::expression::Executable;
+
+/// Runs `code` within the current stackframe, returning the value it leaves on the stack.
+#[tracing::instrument(name="qvm::run", level="trace", skip(code))]
+pub fn run(code: &Code) -> quest_core::Result<Object> {
+	let mut stack = Vec::<Object>::with_capacity(8);
+
+	for op in code.ops() {
+		match *op {
+			Op::Null => stack.push(Object::default()),
+			Op::Primitive(idx) => stack.push(code.primitives[idx].execute()?),
+			Op::Block(idx) => {
+				let block = Object::from(CompiledBlock::new(code.blocks[idx].clone()));
+				block.add_parent(Binding::instance().as_ref().clone())?;
+				stack.push(block);
+			},
+			Op::CallAttr { attr, argc } => {
+				let args = stack.split_off(stack.len() - argc);
+				let this = stack.pop().expect("no receiver on the stack?");
+
+				stack.push(this.call_attr_lit(attr, args.iter().collect::<Args>())?);
+			},
+			Op::List(len) => {
+				let list = stack.split_off(stack.len() - len);
+				stack.push(list.into());
+			},
+			Op::Pop => { stack.pop(); }
+		}
+	}
+
+	debug_assert!(stack.len() <= 1, "too many values left on the stack: {:?}", stack);
+
+	Ok(stack.pop().unwrap_or_default())
+}